@@ -115,6 +115,29 @@ impl<'a> From<&'a StaticCatchInfo> for Catcher {
     }
 }
 
+/// A typed reference to a catcher declared with `#[catch]`, returned by
+/// [`uri!`] when invoked on a catcher instead of a route.
+///
+/// Unlike a route, a catcher has no path of its own, so there's no `Origin`
+/// to build; this only carries the catcher's registered status code, which
+/// is enough to identify which catcher `uri!` was pointed at, for example
+/// when asserting on the order catchers were registered in.
+///
+/// [`uri!`]: ../macro.uri.html
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct CatcherUri {
+    /// The HTTP status code the catcher handles.
+    pub code: u16,
+}
+
+impl CatcherUri {
+    #[doc(hidden)]
+    #[inline(always)]
+    pub fn new(code: u16) -> CatcherUri {
+        CatcherUri { code }
+    }
+}
+
 impl fmt::Display for Catcher {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         write!(f, "{}", Blue.paint(&self.code))
@@ -13,12 +13,21 @@ pub struct StaticRouteInfo {
     pub method: Method,
     /// The route's path, without the base mount point.
     pub path: &'static str,
-    /// The route's format, if any.
-    pub format: Option<MediaType>,
+    /// The route's accepted formats, if any were declared.
+    pub format: Option<&'static [MediaType]>,
+    /// A route-local override of the data limit, in bytes, if one was
+    /// declared with `data_limit`.
+    pub data_limit: Option<u64>,
+    /// A route-local override of CORS handling, if one was declared with
+    /// `cors`.
+    pub cors: Option<bool>,
     /// The route's handler, i.e, the annotated function.
     pub handler: StaticHandler,
     /// The route's rank, if any.
     pub rank: Option<isize>,
+    /// An offset to apply to the route's automatically computed rank, if any.
+    /// Only meaningful when `rank` is `None`.
+    pub rank_offset: Option<isize>,
 }
 
 /// Information generated by the `catch` attribute during codegen.
@@ -1,7 +1,8 @@
 use std::collections::HashMap;
 use std::path::{Path, PathBuf};
 
-use crate::config::{Result, Config, Value, Environment, Limits, LoggingLevel};
+use crate::config::{Result, Config, Value, Environment, Limits, LoggingLevel, TrustedProxies};
+use crate::http::CookiePolicy;
 
 /// Structure following the builder pattern for building `Config` structures.
 #[derive(Clone)]
@@ -20,10 +21,17 @@ pub struct ConfigBuilder {
     pub log_level: LoggingLevel,
     /// The secret key.
     pub secret_key: Option<String>,
+    /// Previous secret keys still accepted when verifying signed or private
+    /// cookies.
+    pub secret_key_fallbacks: Vec<String>,
     /// TLS configuration (path to certificates file, path to private key file).
     pub tls: Option<(String, String)>,
     /// Size limits.
     pub limits: Limits,
+    /// IP ranges trusted to report a client's true address.
+    pub proxies: TrustedProxies,
+    /// Default attributes applied to cookies added via `CookieJar`.
+    pub cookies: CookiePolicy,
     /// Any extra parameters that aren't part of Rocket's config.
     pub extras: HashMap<String, Value>,
     /// The root directory of this config, if any.
@@ -59,8 +67,11 @@ impl ConfigBuilder {
             keep_alive: config.keep_alive.unwrap_or(0),
             log_level: config.log_level,
             secret_key: None,
+            secret_key_fallbacks: Vec::new(),
             tls: None,
             limits: config.limits,
+            proxies: config.proxies,
+            cookies: config.cookies,
             extras: config.extras,
             root: None,
         }
@@ -184,6 +195,26 @@ impl ConfigBuilder {
         self
     }
 
+    /// Sets the `secret_key_fallbacks` in the configuration being built.
+    /// These keys are tried, in order, when verifying a signed or private
+    /// cookie fails against the active `secret_key`, allowing `secret_key`
+    /// to be rotated without invalidating existing cookies.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use rocket::config::{Config, Environment};
+    ///
+    /// let old_key = "8Xui8SN4mI+7egV/9dlfYYLGQJeEx4+DwmSQLwDVXJg=";
+    /// let mut config = Config::build(Environment::Staging)
+    ///     .secret_key_fallbacks(vec![old_key])
+    ///     .unwrap();
+    /// ```
+    pub fn secret_key_fallbacks<K: Into<String>>(mut self, keys: Vec<K>) -> Self {
+        self.secret_key_fallbacks = keys.into_iter().map(Into::into).collect();
+        self
+    }
+
     /// Sets the `limits` in the configuration being built.
     ///
     /// # Example
@@ -200,6 +231,43 @@ impl ConfigBuilder {
         self
     }
 
+    /// Sets the trusted proxy IP ranges in the configuration being built.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use rocket::config::{Config, Environment, TrustedProxies};
+    ///
+    /// let proxies = TrustedProxies::parse(vec!["10.0.0.0/8"]).unwrap();
+    /// let config = Config::build(Environment::Staging)
+    ///     .proxies(proxies)
+    ///     .unwrap();
+    /// ```
+    #[inline]
+    pub fn proxies(mut self, proxies: TrustedProxies) -> Self {
+        self.proxies = proxies;
+        self
+    }
+
+    /// Sets the default cookie attributes policy in the configuration being
+    /// built.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use rocket::config::{Config, Environment};
+    /// use rocket::http::CookiePolicy;
+    ///
+    /// let config = Config::build(Environment::Staging)
+    ///     .cookies(CookiePolicy { secure: Some(true), ..CookiePolicy::default() })
+    ///     .unwrap();
+    /// ```
+    #[inline]
+    pub fn cookies(mut self, policy: CookiePolicy) -> Self {
+        self.cookies = policy;
+        self
+    }
+
     /// Sets the TLS configuration in the configuration being built.
     ///
     /// Certificates are read from `certs_path`. The certificate chain must be
@@ -321,6 +389,8 @@ impl ConfigBuilder {
         config.set_log_level(self.log_level);
         config.set_extras(self.extras);
         config.set_limits(self.limits);
+        config.set_proxies(self.proxies);
+        config.set_cookies(self.cookies);
 
         if let Some(root) = self.root {
             config.set_root(root);
@@ -334,6 +404,10 @@ impl ConfigBuilder {
             config.set_secret_key(key)?;
         }
 
+        if !self.secret_key_fallbacks.is_empty() {
+            config.set_secret_key_fallbacks(self.secret_key_fallbacks)?;
+        }
+
         Ok(config)
     }
 
@@ -8,6 +8,7 @@ use crate::config::Environment::*;
 use crate::config::{Result, ConfigBuilder, Environment, ConfigError, LoggingLevel};
 use crate::config::{Table, Value, Array, Datetime};
 use crate::http::private::Key;
+use crate::http::CookiePolicy;
 
 use super::custom_values::*;
 
@@ -49,10 +50,18 @@ pub struct Config {
     pub log_level: LoggingLevel,
     /// The secret key.
     pub(crate) secret_key: SecretKey,
+    /// Previous secret keys still accepted when verifying signed or private
+    /// cookies, to allow rotating `secret_key` without invalidating cookies
+    /// issued under the old one.
+    pub(crate) secret_key_fallbacks: Vec<Key>,
     /// TLS configuration.
     pub(crate) tls: Option<TlsConfig>,
     /// Streaming read size limits.
     pub limits: Limits,
+    /// IP ranges trusted to report a client's true address.
+    pub proxies: TrustedProxies,
+    /// Default attributes applied to cookies added via `CookieJar`.
+    pub cookies: CookiePolicy,
     /// Extra parameters that aren't part of Rocket's core config.
     pub extras: HashMap<String, Value>,
     /// The path to the configuration file this config was loaded from, if any.
@@ -230,8 +239,11 @@ impl Config {
                     keep_alive: Some(5),
                     log_level: LoggingLevel::Normal,
                     secret_key: key,
+                    secret_key_fallbacks: Vec::new(),
                     tls: None,
                     limits: Limits::default(),
+                    proxies: TrustedProxies::default(),
+                    cookies: CookiePolicy::default(),
                     extras: HashMap::new(),
                     config_file_path: None,
                     root_path: None,
@@ -246,8 +258,11 @@ impl Config {
                     keep_alive: Some(5),
                     log_level: LoggingLevel::Normal,
                     secret_key: key,
+                    secret_key_fallbacks: Vec::new(),
                     tls: None,
                     limits: Limits::default(),
+                    proxies: TrustedProxies::default(),
+                    cookies: CookiePolicy::default(),
                     extras: HashMap::new(),
                     config_file_path: None,
                     root_path: None,
@@ -262,8 +277,11 @@ impl Config {
                     keep_alive: Some(5),
                     log_level: LoggingLevel::Critical,
                     secret_key: key,
+                    secret_key_fallbacks: Vec::new(),
                     tls: None,
                     limits: Limits::default(),
+                    proxies: TrustedProxies::default(),
+                    cookies: CookiePolicy::default(),
                     extras: HashMap::new(),
                     config_file_path: None,
                     root_path: None,
@@ -298,7 +316,10 @@ impl Config {
     ///   * **keep_alive**: Integer
     ///   * **log**: String
     ///   * **secret_key**: String (256-bit base64 or base16)
+    ///   * **secret_key_fallbacks**: Array of String (256-bit base64 or base16)
     ///   * **tls**: Table (`certs` (path as String), `key` (path as String))
+    ///   * **cookies**: Table (`secure` (bool), `http_only` (bool),
+    ///     `default_same_site` (one of "strict", "lax", or "none"))
     pub(crate) fn set_raw(&mut self, name: &str, val: &Value) -> Result<()> {
         let (id, ok) = (|val| val, |_| Ok(()));
         config_from_raw!(self, name, val,
@@ -308,8 +329,11 @@ impl Config {
             keep_alive => (u32, set_keep_alive, ok),
             log => (log_level, set_log_level, ok),
             secret_key => (str, set_secret_key, id),
+            secret_key_fallbacks => (secret_key_fallbacks, set_secret_key_fallbacks, id),
             tls => (tls_config, set_raw_tls, id),
             limits => (limits, set_limits, ok),
+            proxies => (proxies, set_proxies, ok),
+            cookies => (cookies, set_cookies, ok),
             | _ => {
                 self.extras.insert(name.into(), val.clone());
                 Ok(())
@@ -450,16 +474,45 @@ impl Config {
     pub fn set_secret_key<K: Into<String>>(&mut self, key: K) -> Result<()> {
         let key = key.into();
         let e = self.bad_type("secret_key", "string", "a 256-bit base64 or hex encoded string");
+        self.secret_key = SecretKey::Provided(decode_secret_key(&key).ok_or(e)?);
+        Ok(())
+    }
 
-        // `binascii` requires a bit more space than actual output for padding
-        let mut bytes = [0u8; 36];
-        let bytes = match key.len() {
-            44 => binascii::b64decode(key.as_bytes(), &mut bytes).map_err(|_| e)?,
-            64 => binascii::hex2bin(key.as_bytes(), &mut bytes).map_err(|_| e)?,
-            _ => return Err(e)
-        };
+    /// Sets the `secret_key_fallbacks` in `self` to `keys`, each of which
+    /// must be a 256-bit base64 or base16 (hex) encoded string, just like
+    /// [`Config::set_secret_key()`]. When verifying a signed or private
+    /// cookie, these keys are tried, in order, after the active
+    /// `secret_key` fails, so that `secret_key` can be rotated without
+    /// invalidating cookies issued under the keys being retired.
+    ///
+    /// # Errors
+    ///
+    /// If any key in `keys` is not a valid 256-bit encoded string, returns a
+    /// `BadType` error.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use rocket::config::{Config, Environment};
+    ///
+    /// let mut config = Config::new(Environment::Staging);
+    ///
+    /// let old_key = "8Xui8SN4mI+7egV/9dlfYYLGQJeEx4+DwmSQLwDVXJg=";
+    /// assert!(config.set_secret_key_fallbacks(vec![old_key]).is_ok());
+    ///
+    /// assert!(config.set_secret_key_fallbacks(vec!["hello? anyone there?"]).is_err());
+    /// ```
+    pub fn set_secret_key_fallbacks<K: Into<String>>(&mut self, keys: Vec<K>) -> Result<()> {
+        let e = || self.bad_type("secret_key_fallbacks", "string",
+            "a 256-bit base64 or hex encoded string");
+
+        let mut decoded = Vec::with_capacity(keys.len());
+        for key in keys {
+            let key = key.into();
+            decoded.push(decode_secret_key(&key).ok_or_else(e)?);
+        }
 
-        self.secret_key = SecretKey::Provided(Key::from_master(&bytes));
+        self.secret_key_fallbacks = decoded;
         Ok(())
     }
 
@@ -494,6 +547,37 @@ impl Config {
         self.limits = limits;
     }
 
+    /// Sets the trusted proxy IP ranges in `self` to `proxies`.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use rocket::config::{Config, TrustedProxies};
+    ///
+    /// let mut config = Config::development();
+    /// config.set_proxies(TrustedProxies::parse(vec!["10.0.0.0/8"]).unwrap());
+    /// ```
+    #[inline]
+    pub fn set_proxies(&mut self, proxies: TrustedProxies) {
+        self.proxies = proxies;
+    }
+
+    /// Sets the default cookie attributes policy in `self` to `policy`.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use rocket::config::Config;
+    /// use rocket::http::CookiePolicy;
+    ///
+    /// let mut config = Config::development();
+    /// config.set_cookies(CookiePolicy { secure: Some(true), ..CookiePolicy::default() });
+    /// ```
+    #[inline]
+    pub fn set_cookies(&mut self, policy: CookiePolicy) {
+        self.cookies = policy;
+    }
+
     /// Sets the TLS configuration in `self`.
     ///
     /// Certificates are read from `certs_path`. The certificate chain must be
@@ -625,6 +709,12 @@ impl Config {
         self.secret_key.inner()
     }
 
+    /// Retrieves the fallback secret keys from `self`.
+    #[inline]
+    pub(crate) fn secret_key_fallbacks(&self) -> &[Key] {
+        &self.secret_key_fallbacks
+    }
+
     /// Attempts to retrieve the extra named `name` as a raw value.
     ///
     /// # Errors
@@ -949,3 +1039,17 @@ impl PartialEq for Config {
             && self.extras == other.extras
     }
 }
+
+/// Decodes a 256-bit secret key from either a 44-character base64 string or
+/// a 64-character hex string, returning `None` if `key` is neither.
+fn decode_secret_key(key: &str) -> Option<Key> {
+    // `binascii` requires a bit more space than actual output for padding
+    let mut bytes = [0u8; 36];
+    let bytes = match key.len() {
+        44 => binascii::b64decode(key.as_bytes(), &mut bytes).ok()?,
+        64 => binascii::hex2bin(key.as_bytes(), &mut bytes).ok()?,
+        _ => return None
+    };
+
+    Some(Key::from_master(&bytes))
+}
@@ -1,8 +1,10 @@
 use std::fmt;
+use std::net::IpAddr;
 
 #[cfg(feature = "tls")] use crate::http::tls::{Certificate, PrivateKey};
 
 use crate::http::private::Key;
+use crate::http::{CookiePolicy, SameSite};
 use crate::config::{Result, Config, Value, ConfigError, LoggingLevel};
 
 #[derive(Clone)]
@@ -53,6 +55,98 @@ pub struct TlsConfig {
 #[derive(Clone)]
 pub struct TlsConfig;
 
+/// A count of bytes, used for the limit arithmetic that backs [`Limits`].
+///
+/// `ByteUnit` is a thin wrapper around a `u64` byte count. Its only purpose
+/// is to provide saturating arithmetic for the kind of limit math a
+/// [`FromData`](crate::data::FromData) implementation does when, say,
+/// splitting a configured limit across several sub-reads: subtracting a
+/// buffer's size from a remaining limit shouldn't panic on underflow just
+/// because the buffer happened to be larger than what was left.
+///
+/// # Example
+///
+/// ```rust
+/// use rocket::config::ByteUnit;
+///
+/// let limit = ByteUnit::from(1024);
+/// let buffered = ByteUnit::from(2048);
+///
+/// // Plain `-` would panic; `saturating_sub` clamps to zero instead.
+/// assert_eq!(limit.saturating_sub(buffered), ByteUnit::from(0));
+///
+/// assert_eq!(limit.min(buffered), limit);
+/// assert_eq!(limit.max(buffered), buffered);
+/// ```
+#[derive(Debug, Copy, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct ByteUnit(u64);
+
+impl ByteUnit {
+    /// Subtracts `other` from `self`, saturating at `0` instead of
+    /// underflowing (and panicking, in a debug build) if `other` is larger
+    /// than `self`.
+    #[inline]
+    pub fn saturating_sub(self, other: ByteUnit) -> ByteUnit {
+        ByteUnit(self.0.saturating_sub(other.0))
+    }
+
+    /// Returns the smaller of `self` and `other`.
+    #[inline]
+    pub fn min(self, other: ByteUnit) -> ByteUnit {
+        ByteUnit(std::cmp::min(self.0, other.0))
+    }
+
+    /// Returns the larger of `self` and `other`.
+    #[inline]
+    pub fn max(self, other: ByteUnit) -> ByteUnit {
+        ByteUnit(std::cmp::max(self.0, other.0))
+    }
+
+    /// Returns the byte count as a `u64`.
+    #[inline]
+    pub fn as_u64(self) -> u64 {
+        self.0
+    }
+
+    /// Returns the byte count as a `usize`.
+    #[inline]
+    pub fn as_usize(self) -> usize {
+        self.0 as usize
+    }
+}
+
+impl From<u64> for ByteUnit {
+    #[inline]
+    fn from(bytes: u64) -> ByteUnit {
+        ByteUnit(bytes)
+    }
+}
+
+impl From<ByteUnit> for u64 {
+    #[inline]
+    fn from(unit: ByteUnit) -> u64 {
+        unit.0
+    }
+}
+
+impl std::ops::Sub for ByteUnit {
+    type Output = ByteUnit;
+
+    /// Subtracts `rhs` from `self`. Panics on underflow in a debug build,
+    /// matching `u64`'s own `Sub` behavior; use
+    /// [`saturating_sub()`](ByteUnit::saturating_sub()) to avoid that.
+    #[inline]
+    fn sub(self, rhs: ByteUnit) -> ByteUnit {
+        ByteUnit(self.0 - rhs.0)
+    }
+}
+
+impl fmt::Display for ByteUnit {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.0)
+    }
+}
+
 /// Mapping from data type to size limits.
 ///
 /// A `Limits` structure contains a mapping from a given data type ("forms",
@@ -205,6 +299,27 @@ pub fn str<'a>(conf: &Config, name: &str, v: &'a Value) -> Result<&'a str> {
     v.as_str().ok_or_else(|| conf.bad_type(name, v.type_str(), "a string"))
 }
 
+/// A list of 256-bit secret keys, each base64 or hex encoded, used as
+/// fallback [`Config::secret_key`] values when verifying signed or private
+/// cookies. See [`Config::set_secret_key_fallbacks()`] for how these are
+/// used.
+///
+/// [`Config::secret_key`]: crate::config::Config::set_secret_key()
+/// [`Config::set_secret_key_fallbacks()`]: crate::config::Config::set_secret_key_fallbacks()
+pub fn secret_key_fallbacks(conf: &Config, name: &str, value: &Value) -> Result<Vec<String>> {
+    let array = value.as_array()
+        .ok_or_else(|| conf.bad_type(name, value.type_str(), "an array"))?;
+
+    array.iter()
+        .map(|v| v.as_str().map(String::from)
+            .ok_or_else(|| conf.bad_type(name, v.type_str(), "a string")))
+        .collect()
+}
+
+pub fn bool(conf: &Config, name: &str, value: &Value) -> Result<bool> {
+    value.as_bool().ok_or_else(|| conf.bad_type(name, value.type_str(), "a boolean"))
+}
+
 pub fn u64(conf: &Config, name: &str, value: &Value) -> Result<u64> {
     match value.as_integer() {
         Some(x) if x >= 0 => Ok(x as u64),
@@ -271,3 +386,135 @@ pub fn limits(conf: &Config, name: &str, value: &Value) -> Result<Limits> {
 
     Ok(limits)
 }
+
+/// A set of IP ranges, in CIDR notation, trusted to report a client's true
+/// address via "X-Forwarded-For". See [`Request::true_client_ip()`] for how
+/// this set is used.
+///
+/// [`Request::true_client_ip()`]: crate::Request::true_client_ip()
+#[derive(Debug, Clone, Default)]
+pub struct TrustedProxies(Vec<(IpAddr, u8)>);
+
+impl TrustedProxies {
+    /// Parses `ranges`, a list of CIDR strings like `"10.0.0.0/8"`, into a
+    /// `TrustedProxies`. An address with no `/prefix` is treated as a single
+    /// host.
+    pub fn parse<'a, I: IntoIterator<Item = &'a str>>(ranges: I) -> std::result::Result<Self, String> {
+        let mut parsed = Vec::new();
+        for range in ranges {
+            parsed.push(parse_cidr(range).ok_or_else(|| range.to_string())?);
+        }
+
+        Ok(TrustedProxies(parsed))
+    }
+
+    /// Returns `true` if `ip` falls within any of the ranges in `self`.
+    pub fn contains(&self, ip: &IpAddr) -> bool {
+        self.0.iter().any(|(base, prefix)| ip_in_range(*base, *prefix, *ip))
+    }
+}
+
+fn parse_cidr(range: &str) -> Option<(IpAddr, u8)> {
+    let mut parts = range.splitn(2, '/');
+    let addr: IpAddr = parts.next()?.trim().parse().ok()?;
+    let max_prefix = if addr.is_ipv4() { 32 } else { 128 };
+    let prefix = match parts.next() {
+        Some(bits) => bits.trim().parse().ok()?,
+        None => max_prefix,
+    };
+
+    if prefix > max_prefix {
+        return None;
+    }
+
+    Some((addr, prefix))
+}
+
+fn ip_in_range(base: IpAddr, prefix: u8, ip: IpAddr) -> bool {
+    match (base, ip) {
+        (IpAddr::V4(base), IpAddr::V4(ip)) => {
+            let mask = (!0u32).checked_shl(32 - u32::from(prefix)).unwrap_or(0);
+            (u32::from(base) & mask) == (u32::from(ip) & mask)
+        }
+        (IpAddr::V6(base), IpAddr::V6(ip)) => {
+            let mask = (!0u128).checked_shl(128 - u32::from(prefix)).unwrap_or(0);
+            (u128::from(base) & mask) == (u128::from(ip) & mask)
+        }
+        _ => false,
+    }
+}
+
+pub fn proxies(conf: &Config, name: &str, value: &Value) -> Result<TrustedProxies> {
+    let table = value.as_table()
+        .ok_or_else(|| conf.bad_type(name, value.type_str(), "a table"))?;
+
+    let trusted = match table.get("trusted") {
+        Some(value) => value.as_array()
+            .ok_or_else(|| conf.bad_type("proxies.trusted", value.type_str(), "an array"))?,
+        None => return Ok(TrustedProxies::default()),
+    };
+
+    let ranges = trusted.iter()
+        .map(|v| v.as_str().ok_or_else(|| conf.bad_type("proxies.trusted", v.type_str(), "a string")))
+        .collect::<Result<Vec<_>>>()?;
+
+    TrustedProxies::parse(ranges)
+        .map_err(|_| conf.bad_type("proxies.trusted", "string", "a valid CIDR range (e.g. '10.0.0.0/8')"))
+}
+
+pub fn cookies(conf: &Config, name: &str, value: &Value) -> Result<CookiePolicy> {
+    let table = value.as_table()
+        .ok_or_else(|| conf.bad_type(name, value.type_str(), "a table"))?;
+
+    let env = conf.environment;
+    let mut policy = CookiePolicy::default();
+    for (key, value) in table {
+        match key.as_str() {
+            "secure" => policy.secure = Some(bool(conf, "cookies.secure", value)?),
+            "http_only" => policy.http_only = Some(bool(conf, "cookies.http_only", value)?),
+            "default_same_site" => {
+                let same_site = str(conf, "cookies.default_same_site", value)?;
+                policy.same_site = Some(match same_site {
+                    "strict" => SameSite::Strict,
+                    "lax" => SameSite::Lax,
+                    "none" => SameSite::None,
+                    _ => return Err(conf.bad_type("cookies.default_same_site", "string",
+                        "one of 'strict', 'lax', or 'none'")),
+                });
+            }
+            _ => return Err(ConfigError::UnknownKey(format!("{}.cookies.{}", env, key)))
+        }
+    }
+
+    Ok(policy)
+}
+
+#[cfg(test)]
+mod byte_unit_tests {
+    use super::ByteUnit;
+
+    #[test]
+    fn saturating_sub_clamps_to_zero_on_underflow() {
+        let limit = ByteUnit::from(1024);
+        let buffered = ByteUnit::from(2048);
+        assert_eq!(limit.saturating_sub(buffered), ByteUnit::from(0));
+    }
+
+    #[test]
+    fn saturating_sub_behaves_like_sub_when_not_underflowing() {
+        let limit = ByteUnit::from(2048);
+        let buffered = ByteUnit::from(1024);
+        assert_eq!(limit.saturating_sub(buffered), limit - buffered);
+        assert_eq!(limit.saturating_sub(buffered), ByteUnit::from(1024));
+    }
+
+    #[test]
+    fn min_and_max_pick_the_right_side() {
+        let small = ByteUnit::from(10);
+        let large = ByteUnit::from(20);
+        assert_eq!(small.min(large), small);
+        assert_eq!(small.max(large), large);
+        assert_eq!(large.min(small), small);
+        assert_eq!(large.max(small), large);
+    }
+}
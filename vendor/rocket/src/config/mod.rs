@@ -197,13 +197,17 @@ use std::env;
 
 use toml;
 
-pub use self::custom_values::Limits;
+pub use self::custom_values::{Limits, TrustedProxies, ByteUnit};
 pub use toml::value::{Array, Table, Value, Datetime};
 pub use self::error::ConfigError;
 pub use self::environment::Environment;
 pub use self::config::Config;
 pub use self::builder::ConfigBuilder;
 pub use crate::logger::LoggingLevel;
+pub use self::toml_ext::{merge, MergeStrategy, find, flatten};
+pub use self::toml_ext::{as_i64, as_f64, as_u16, as_u32, as_usize};
+pub use self::toml_ext::{interpolate_env, MissingEnvVar};
+pub use self::toml_ext::ValueExt;
 pub(crate) use self::toml_ext::LoggedValue;
 
 use crate::logger;
@@ -1,7 +1,9 @@
-use std::fmt;
+use std::convert::TryInto;
+use std::{env, fmt};
 use std::result::Result as StdResult;
 
 use crate::config::Value;
+use crate::http::uncased::uncased_eq;
 
 use pear::{Result, parser, switch};
 use pear::parsers::*;
@@ -79,6 +81,163 @@ pub fn parse_simple_toml_value(mut input: &str) -> StdResult<Value, String> {
     parse!(value: &mut input).map_err(|e| e.to_string())
 }
 
+/// Extension trait adding loose parsing helpers to [`Value`].
+///
+/// `Value` already distinguishes a typed [`Value::Boolean`] from a
+/// [`Value::String`], but config sourced from environment variables often
+/// arrives as a string like `"1"` or `"yes"` even when it's conceptually a
+/// boolean. [`ValueExt::as_bool_loose()`] closes that gap.
+///
+/// `as_bool_loose()` was requested against `figment::value::Value`; this
+/// Rocket revision doesn't vendor figment, so it's implemented here as an
+/// extension trait on the `config::Value` this crate actually has.
+pub trait ValueExt {
+    /// Interprets `self` as a `bool`, accepting an actual `Value::Boolean` or
+    /// a `Value::String` holding (case-insensitively) `true`, `false`, `1`,
+    /// `0`, `yes`, `no`, `on`, or `off`. Returns `None` for anything else.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use rocket::config::Value;
+    /// use rocket::config::ValueExt;
+    ///
+    /// assert_eq!(Value::Boolean(true).as_bool_loose(), Some(true));
+    /// assert_eq!(Value::String("YES".into()).as_bool_loose(), Some(true));
+    /// assert_eq!(Value::String("0".into()).as_bool_loose(), Some(false));
+    /// assert_eq!(Value::String("nope".into()).as_bool_loose(), None);
+    /// assert_eq!(Value::Integer(1).as_bool_loose(), None);
+    /// ```
+    fn as_bool_loose(&self) -> Option<bool>;
+
+    /// Returns `true` if `self` is an empty string, an empty array, or an
+    /// empty table. A missing key isn't itself a `Value`, so it's not
+    /// considered here; callers distinguish "missing" from "present but
+    /// empty" before calling this, typically via [`find()`](super::find).
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use rocket::config::Value;
+    /// use rocket::config::ValueExt;
+    ///
+    /// assert!(Value::String("".into()).is_empty());
+    /// assert!(Value::Array(vec![]).is_empty());
+    /// assert!(Value::Table(Default::default()).is_empty());
+    /// assert!(!Value::String(" ".into()).is_empty());
+    /// assert!(!Value::Integer(0).is_empty());
+    /// ```
+    fn is_empty(&self) -> bool;
+
+    /// Returns `fallback` if `self` [`is_empty()`](ValueExt::is_empty),
+    /// `self` otherwise. Lets config layering express "use this unless it's
+    /// blank" without an explicit `is_empty()` check at every call site.
+    ///
+    /// This, and `is_empty()` it's built on, were requested as an
+    /// `Empty`-aware `figment::value::Value`; figment isn't vendored at
+    /// this Rocket revision, so both are methods on `config::Value` here
+    /// instead.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use rocket::config::Value;
+    /// use rocket::config::ValueExt;
+    ///
+    /// let blank = Value::String("".into());
+    /// let fallback = Value::String("default".into());
+    /// assert_eq!(blank.or(fallback.clone()), fallback);
+    ///
+    /// let present = Value::String("custom".into());
+    /// assert_eq!(present.clone().or(fallback), present);
+    /// ```
+    fn or(self, fallback: Value) -> Value;
+
+    /// Renders `self` as a TOML document, by way of `Value`'s existing
+    /// [`Serialize`](serde::Serialize) implementation.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use rocket::config::{Value, Table};
+    /// use rocket::config::ValueExt;
+    ///
+    /// let mut table = Table::new();
+    /// table.insert("port".into(), 8000.into());
+    ///
+    /// let rendered = Value::Table(table).to_toml_string().expect("serializable");
+    /// assert_eq!(rendered, "port = 8000\n");
+    /// ```
+    fn to_toml_string(&self) -> StdResult<String, toml::ser::Error>;
+
+    /// Renders `self` as a JSON document, by way of `Value`'s existing
+    /// [`Serialize`](serde::Serialize) implementation. Requires the `json`
+    /// feature.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// # #[cfg(feature = "json")] fn test() {
+    /// use rocket::config::{Value, Table};
+    /// use rocket::config::ValueExt;
+    ///
+    /// let mut table = Table::new();
+    /// table.insert("port".into(), 8000.into());
+    ///
+    /// let rendered = Value::Table(table).to_json_string().expect("serializable");
+    /// assert_eq!(rendered, r#"{"port":8000}"#);
+    /// # }
+    /// # #[cfg(feature = "json")] test();
+    /// ```
+    #[cfg(feature = "json")]
+    fn to_json_string(&self) -> StdResult<String, serde_json::Error>;
+}
+
+impl ValueExt for Value {
+    fn as_bool_loose(&self) -> Option<bool> {
+        match self {
+            Value::Boolean(b) => Some(*b),
+            Value::String(s) => {
+                let s = s.as_str();
+                if uncased_eq(s, "true") || uncased_eq(s, "1")
+                    || uncased_eq(s, "yes") || uncased_eq(s, "on")
+                {
+                    Some(true)
+                } else if uncased_eq(s, "false") || uncased_eq(s, "0")
+                    || uncased_eq(s, "no") || uncased_eq(s, "off")
+                {
+                    Some(false)
+                } else {
+                    None
+                }
+            }
+            _ => None,
+        }
+    }
+
+    fn is_empty(&self) -> bool {
+        match self {
+            Value::String(s) => s.is_empty(),
+            Value::Array(a) => a.is_empty(),
+            Value::Table(t) => t.is_empty(),
+            _ => false,
+        }
+    }
+
+    fn or(self, fallback: Value) -> Value {
+        if self.is_empty() { fallback } else { self }
+    }
+
+    fn to_toml_string(&self) -> StdResult<String, toml::ser::Error> {
+        toml::to_string(self)
+    }
+
+    #[cfg(feature = "json")]
+    fn to_json_string(&self) -> StdResult<String, serde_json::Error> {
+        serde_json::to_string(self)
+    }
+}
+
 /// A simple wrapper over a `Value` reference with a custom implementation of
 /// `Display`. This is used to log config values at initialization.
 pub struct LoggedValue<'a>(pub &'a Value);
@@ -104,6 +263,267 @@ impl fmt::Display for LoggedValue<'_> {
     }
 }
 
+/// Strategy for combining two [`Value::Array`]s in [`merge()`].
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+pub enum MergeStrategy {
+    /// `other`'s array entirely replaces `base`'s.
+    Replace,
+    /// `other`'s entries are appended after `base`'s.
+    Concatenate,
+}
+
+/// Deep-merges `other` into `base`, returning the result.
+///
+/// This is the `figment::value::Value::merge` this was originally requested
+/// against, implemented over this Rocket revision's own pre-figment
+/// `config::Value` instead, since figment isn't a dependency here.
+///
+/// If `base` and `other` are both [`Value::Table`]s, they're merged
+/// key-by-key: a key present in both is merged recursively, while a key
+/// present in only one is kept as-is. If both are [`Value::Array`]s, they're
+/// combined according to `strategy`. In every other case, including when the
+/// two values are of different kinds, `other` replaces `base` outright.
+///
+/// This is useful for layering config profiles programmatically, such as
+/// applying a set of overrides on top of a base `Value::Table` read from a
+/// config file.
+pub fn merge(base: Value, other: Value, strategy: MergeStrategy) -> Value {
+    match (base, other) {
+        (Value::Table(mut base), Value::Table(other)) => {
+            for (key, other_val) in other {
+                let merged = match base.remove(&key) {
+                    Some(base_val) => merge(base_val, other_val, strategy),
+                    None => other_val,
+                };
+
+                base.insert(key, merged);
+            }
+
+            Value::Table(base)
+        }
+        (Value::Array(base), Value::Array(other)) => match strategy {
+            MergeStrategy::Replace => Value::Array(other),
+            MergeStrategy::Concatenate => {
+                let mut base = base;
+                base.extend(other);
+                Value::Array(base)
+            }
+        },
+        (_, other) => other,
+    }
+}
+
+/// Navigates `value` using `dotted_key`, a `.`-separated path of table keys
+/// and, for arrays, 0-indexed numeric indices, returning the leaf value if
+/// every component of the path resolves, or `None` as soon as one doesn't.
+///
+/// This was requested as `figment::value::Value::find`; figment isn't
+/// vendored at this Rocket revision, so this looks the path up on the
+/// `toml`-backed `config::Value` this crate actually has instead.
+///
+/// # Example
+///
+/// ```rust
+/// use rocket::config::{find, Value, Table};
+///
+/// let mut servers = Table::new();
+/// let mut first = Table::new();
+/// first.insert("port".into(), 8000.into());
+/// servers.insert("servers".into(), vec![Value::from(first)].into());
+///
+/// let root = Value::from(servers);
+/// assert_eq!(find(&root, "servers.0.port"), Some(&Value::from(8000)));
+/// assert_eq!(find(&root, "servers.1.port"), None);
+/// ```
+pub fn find<'v>(value: &'v Value, dotted_key: &str) -> Option<&'v Value> {
+    let mut current = value;
+    for part in dotted_key.split('.') {
+        current = match current {
+            Value::Table(map) => map.get(part)?,
+            Value::Array(arr) => arr.get(part.parse::<usize>().ok()?)?,
+            _ => return None,
+        };
+    }
+
+    Some(current)
+}
+
+/// Recursively walks `value`, returning a `(dotted_key, leaf)` pair for every
+/// scalar leaf reachable by descending through [`Value::Table`]s and
+/// [`Value::Array`]s. This is the inverse of looking a leaf up with
+/// [`find()`]'s dotted-key syntax: an array index becomes a plain numeric key
+/// segment (`"servers.0.port"`), matching how `find()` looks one up, not
+/// `servers[0].port`.
+///
+/// Useful for logging or diffing an entire configuration table leaf-by-leaf
+/// without writing a recursive walk by hand every time.
+///
+/// This was requested as `figment::value::Value::flatten`; since figment
+/// isn't vendored at this Rocket revision, it's written here against the
+/// `toml`-backed `config::Value` this crate has instead.
+///
+/// # Example
+///
+/// ```rust
+/// use std::collections::BTreeMap;
+/// use rocket::config::{Value, flatten};
+///
+/// let mut table = BTreeMap::new();
+/// table.insert("port".to_string(), Value::from(8000));
+///
+/// let leaves = flatten(&Value::Table(table));
+/// assert_eq!(leaves, vec![("port".to_string(), &Value::from(8000))]);
+/// ```
+pub fn flatten(value: &Value) -> Vec<(String, &Value)> {
+    let mut leaves = Vec::new();
+    flatten_into(value, String::new(), &mut leaves);
+    leaves
+}
+
+fn flatten_into<'v>(value: &'v Value, prefix: String, leaves: &mut Vec<(String, &'v Value)>) {
+    match value {
+        Value::Table(map) => {
+            for (key, val) in map {
+                let key = if prefix.is_empty() { key.clone() } else { format!("{}.{}", prefix, key) };
+                flatten_into(val, key, leaves);
+            }
+        }
+        Value::Array(arr) => {
+            for (i, val) in arr.iter().enumerate() {
+                let key = if prefix.is_empty() { i.to_string() } else { format!("{}.{}", prefix, i) };
+                flatten_into(val, key, leaves);
+            }
+        }
+        leaf => leaves.push((prefix, leaf)),
+    }
+}
+
+/// Returns `value` as an `i64`, accepting a [`Value::Float`] only if it has
+/// no fractional component.
+///
+/// These four conversions stand in for the checked numeric helpers
+/// `figment::value::Num` would otherwise provide; figment isn't vendored at
+/// this Rocket revision, so they're written against `config::Value` here.
+///
+/// This is the basis for [`as_u16`], [`as_u32`], and [`as_usize`]: reading a
+/// config value into a narrower integer type with an `as` cast can silently
+/// truncate an out-of-range value (a `port` larger than `u16::MAX`, say), so
+/// these conversions return `None` instead.
+pub fn as_i64(value: &Value) -> Option<i64> {
+    match value {
+        Value::Integer(i) => Some(*i),
+        Value::Float(f) if f.fract() == 0.0 => Some(*f as i64),
+        _ => None,
+    }
+}
+
+/// Returns `value` as an `f64`. A [`Value::Integer`] is converted with `as`,
+/// so magnitudes above 2^53 may lose precision.
+pub fn as_f64(value: &Value) -> Option<f64> {
+    match value {
+        Value::Integer(i) => Some(*i as f64),
+        Value::Float(f) => Some(*f),
+        _ => None,
+    }
+}
+
+/// Returns `value` as a `u16`, or `None` if it isn't a whole number or
+/// doesn't fit. See [`as_i64`] for the exact rules.
+pub fn as_u16(value: &Value) -> Option<u16> {
+    as_i64(value)?.try_into().ok()
+}
+
+/// Returns `value` as a `u32`, or `None` if it isn't a whole number or
+/// doesn't fit. See [`as_i64`] for the exact rules.
+pub fn as_u32(value: &Value) -> Option<u32> {
+    as_i64(value)?.try_into().ok()
+}
+
+/// Returns `value` as a `usize`, or `None` if it isn't a whole number or
+/// doesn't fit. See [`as_i64`] for the exact rules.
+pub fn as_usize(value: &Value) -> Option<usize> {
+    as_i64(value)?.try_into().ok()
+}
+
+/// How [`interpolate_env`] treats a `${VAR}` reference when `VAR` isn't set
+/// in the process environment.
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+pub enum MissingEnvVar {
+    /// Leave the `${VAR}` placeholder in the string untouched.
+    Keep,
+    /// Replace the placeholder with an empty string.
+    Empty,
+}
+
+fn interpolate_string(input: &str, on_missing: MissingEnvVar) -> String {
+    let mut out = String::with_capacity(input.len());
+    let mut chars = input.chars().peekable();
+    while let Some(c) = chars.next() {
+        if c != '$' {
+            out.push(c);
+            continue;
+        }
+
+        match chars.peek() {
+            Some('$') => {
+                chars.next();
+                out.push('$');
+            }
+            Some('{') => {
+                chars.next();
+                let name: String = chars.by_ref().take_while(|&c| c != '}').collect();
+                match env::var(&name) {
+                    Ok(val) => out.push_str(&val),
+                    Err(_) if on_missing == MissingEnvVar::Keep => {
+                        out.push_str("${");
+                        out.push_str(&name);
+                        out.push('}');
+                    }
+                    Err(_) => { /* drop the placeholder */ }
+                }
+            }
+            _ => out.push('$'),
+        }
+    }
+
+    out
+}
+
+/// Walks `value`'s string leaves, substituting `${VAR}` with `VAR`'s value in
+/// the process environment. A literal `$` is written as `$$`. `on_missing`
+/// controls what happens to a `${VAR}` whose `VAR` isn't set.
+///
+/// # Example
+///
+/// ```rust
+/// use rocket::config::{interpolate_env, find, MissingEnvVar, Value, Table};
+///
+/// std::env::set_var("ROCKET_DOC_TEST_HOST", "example.com");
+///
+/// let mut map = Table::new();
+/// map.insert("url".into(), "https://${ROCKET_DOC_TEST_HOST}/api".into());
+/// map.insert("literal".into(), "price: $$5".into());
+///
+/// let interpolated = interpolate_env(Value::from(map), MissingEnvVar::Keep);
+/// assert_eq!(
+///     find(&interpolated, "url"),
+///     Some(&Value::from("https://example.com/api"))
+/// );
+/// assert_eq!(find(&interpolated, "literal"), Some(&Value::from("price: $5")));
+/// ```
+pub fn interpolate_env(value: Value, on_missing: MissingEnvVar) -> Value {
+    match value {
+        Value::String(s) => Value::String(interpolate_string(&s, on_missing)),
+        Value::Array(arr) => {
+            Value::Array(arr.into_iter().map(|v| interpolate_env(v, on_missing)).collect())
+        }
+        Value::Table(map) => {
+            Value::Table(map.into_iter().map(|(k, v)| (k, interpolate_env(v, on_missing))).collect())
+        }
+        other => other,
+    }
+}
+
 #[cfg(test)]
 mod test {
     use std::collections::BTreeMap;
@@ -168,4 +588,286 @@ mod test {
             map
         }));
     }
+
+    use super::{merge, MergeStrategy};
+
+    fn table(pairs: Vec<(&str, Value)>) -> Value {
+        let mut map = BTreeMap::new();
+        for (key, val) in pairs {
+            map.insert(key.into(), val);
+        }
+
+        Table(map)
+    }
+
+    #[test]
+    fn merge_scalar_conflicts_take_other() {
+        assert_eq!(merge(1.into(), 2.into(), MergeStrategy::Replace), Value::from(2));
+        assert_eq!(merge("a".into(), "b".into(), MergeStrategy::Replace), Value::from("b"));
+    }
+
+    #[test]
+    fn merge_tables_recurses_and_keeps_unique_keys() {
+        let base = table(vec![
+            ("a", 1.into()),
+            ("nested", table(vec![("x", 1.into()), ("y", 2.into())])),
+        ]);
+
+        let other = table(vec![
+            ("b", 2.into()),
+            ("nested", table(vec![("y", 3.into())])),
+        ]);
+
+        let merged = merge(base, other, MergeStrategy::Replace);
+        assert_eq!(merged, table(vec![
+            ("a", 1.into()),
+            ("b", 2.into()),
+            ("nested", table(vec![("x", 1.into()), ("y", 3.into())])),
+        ]));
+    }
+
+    #[test]
+    fn merge_arrays_replace_strategy() {
+        let base: Value = vec![1, 2, 3].into();
+        let other: Value = vec![4, 5].into();
+        assert_eq!(merge(base, other, MergeStrategy::Replace), vec![4, 5].into());
+    }
+
+    #[test]
+    fn merge_arrays_concatenate_strategy() {
+        let base: Value = vec![1, 2, 3].into();
+        let other: Value = vec![4, 5].into();
+        let merged = merge(base, other, MergeStrategy::Concatenate);
+        assert_eq!(merged, vec![1, 2, 3, 4, 5].into());
+    }
+
+    #[test]
+    fn merge_table_with_non_table_takes_other() {
+        let base = table(vec![("a", 1.into())]);
+        let merged = merge(base, 5.into(), MergeStrategy::Replace);
+        assert_eq!(merged, Value::from(5));
+    }
+
+    use super::find;
+
+    #[test]
+    fn find_walks_nested_tables() {
+        let root = table(vec![
+            ("a", table(vec![("b", table(vec![("c", 42.into())]))])),
+        ]);
+
+        assert_eq!(find(&root, "a.b.c"), Some(&Value::from(42)));
+        assert_eq!(find(&root, "a.b"), Some(&table(vec![("c", 42.into())])));
+        assert_eq!(find(&root, "a.b.d"), None);
+        assert_eq!(find(&root, "x"), None);
+    }
+
+    #[test]
+    fn find_supports_numeric_array_indices() {
+        let mut server0 = BTreeMap::new();
+        server0.insert("port".into(), Value::from(8000));
+
+        let root = table(vec![
+            ("servers", Value::Array(vec![Table(server0)])),
+        ]);
+
+        assert_eq!(find(&root, "servers.0.port"), Some(&Value::from(8000)));
+        assert_eq!(find(&root, "servers.1.port"), None);
+        assert_eq!(find(&root, "servers.not_a_number"), None);
+    }
+
+    use super::flatten;
+
+    #[test]
+    fn flatten_yields_dotted_keys_for_nested_tables() {
+        let root = table(vec![
+            ("a", table(vec![("b", 1.into()), ("c", 2.into())])),
+            ("d", 3.into()),
+        ]);
+
+        let mut leaves = flatten(&root);
+        leaves.sort_by(|a, b| a.0.cmp(&b.0));
+
+        assert_eq!(leaves, vec![
+            ("a.b".to_string(), &Value::from(1)),
+            ("a.c".to_string(), &Value::from(2)),
+            ("d".to_string(), &Value::from(3)),
+        ]);
+    }
+
+    #[test]
+    fn flatten_uses_numeric_segments_for_arrays() {
+        let mut server0 = BTreeMap::new();
+        server0.insert("port".into(), Value::from(8000));
+
+        let root = table(vec![
+            ("servers", Value::Array(vec![Table(server0)])),
+        ]);
+
+        assert_eq!(flatten(&root), vec![("servers.0.port".to_string(), &Value::from(8000))]);
+    }
+
+    #[test]
+    fn flatten_round_trips_with_find() {
+        let root = table(vec![
+            ("a", table(vec![("b", table(vec![("c", 42.into())]))])),
+        ]);
+
+        for (key, leaf) in flatten(&root) {
+            assert_eq!(find(&root, &key), Some(leaf));
+        }
+    }
+
+    #[test]
+    fn is_empty_recognizes_empty_strings_arrays_and_tables() {
+        assert!(Value::String("".into()).is_empty());
+        assert!(Value::Array(vec![]).is_empty());
+        assert!(Value::Table(Default::default()).is_empty());
+    }
+
+    #[test]
+    fn is_empty_rejects_non_empty_and_non_collection_values() {
+        assert!(!Value::String(" ".into()).is_empty());
+        assert!(!Value::Array(vec![Value::from(1)]).is_empty());
+        assert!(!table(vec![("a", 1.into())]).is_empty());
+        assert!(!Value::Integer(0).is_empty());
+        assert!(!Value::Boolean(false).is_empty());
+    }
+
+    #[test]
+    fn or_falls_back_only_when_self_is_empty() {
+        let fallback = Value::String("default".into());
+
+        assert_eq!(Value::String("".into()).or(fallback.clone()), fallback);
+        assert_eq!(Value::Array(vec![]).or(fallback.clone()), fallback);
+        assert_eq!(Value::Table(Default::default()).or(fallback.clone()), fallback);
+
+        let present = Value::String("custom".into());
+        assert_eq!(present.clone().or(fallback.clone()), present);
+        assert_eq!(Value::Integer(0).or(fallback), Value::Integer(0));
+    }
+
+    #[test]
+    fn or_distinguishes_an_explicit_empty_dict_from_a_missing_key() {
+        let root = table(vec![("present_but_empty", Value::Table(Default::default()))]);
+        let fallback = Value::String("default".into());
+
+        let present = find(&root, "present_but_empty").cloned().expect("key exists");
+        assert_eq!(present.or(fallback.clone()), fallback);
+
+        assert_eq!(find(&root, "missing"), None);
+    }
+
+    use super::{as_i64, as_f64, as_u16, as_u32, as_usize};
+
+    #[test]
+    fn as_u16_rejects_out_of_range_integers() {
+        assert_eq!(as_u16(&Value::from(8000)), Some(8000));
+        assert_eq!(as_u16(&Value::from(70000)), None);
+        assert_eq!(as_u16(&Value::from(-1)), None);
+    }
+
+    #[test]
+    fn as_u32_and_as_usize_reject_negatives() {
+        assert_eq!(as_u32(&Value::from(42)), Some(42));
+        assert_eq!(as_u32(&Value::from(-1)), None);
+        assert_eq!(as_usize(&Value::from(42)), Some(42));
+        assert_eq!(as_usize(&Value::from(-1)), None);
+    }
+
+    #[test]
+    fn integer_conversions_reject_non_numeric_and_fractional_floats() {
+        assert_eq!(as_u32(&Value::from("nope")), None);
+        assert_eq!(as_u32(&Value::from(1.5)), None);
+        assert_eq!(as_i64(&Value::from(8.0)), Some(8));
+    }
+
+    #[test]
+    fn as_f64_widens_integers_exactly() {
+        assert_eq!(as_f64(&Value::from(2)), Some(2.0));
+        assert_eq!(as_f64(&Value::from(3.5)), Some(3.5));
+        assert_eq!(as_f64(&Value::from("nope")), None);
+    }
+
+    use super::{interpolate_env, MissingEnvVar};
+
+    #[test]
+    fn interpolate_env_substitutes_set_variables() {
+        std::env::set_var("ROCKET_TEST_INTERPOLATE_VAR", "/home/user");
+        let value = Value::from("${ROCKET_TEST_INTERPOLATE_VAR}/data");
+        let interpolated = interpolate_env(value, MissingEnvVar::Keep);
+        assert_eq!(interpolated, Value::from("/home/user/data"));
+    }
+
+    #[test]
+    fn interpolate_env_escapes_literal_dollar() {
+        let value = Value::from("price: $$5, not ${ROCKET_TEST_UNSET_VAR}");
+        assert_eq!(
+            interpolate_env(value, MissingEnvVar::Keep),
+            Value::from("price: $5, not ${ROCKET_TEST_UNSET_VAR}")
+        );
+    }
+
+    #[test]
+    fn interpolate_env_missing_var_empty_strategy() {
+        let value = Value::from("${ROCKET_TEST_UNSET_VAR}fallback");
+        assert_eq!(
+            interpolate_env(value, MissingEnvVar::Empty),
+            Value::from("fallback")
+        );
+    }
+
+    #[test]
+    fn interpolate_env_walks_nested_dicts_and_arrays() {
+        std::env::set_var("ROCKET_TEST_INTERPOLATE_VAR", "/home/user");
+        let root = table(vec![
+            ("path", Value::Array(vec![
+                "${ROCKET_TEST_INTERPOLATE_VAR}".into(),
+                table(vec![("nested", "${ROCKET_TEST_INTERPOLATE_VAR}/x".into())]),
+            ])),
+        ]);
+
+        let interpolated = interpolate_env(root, MissingEnvVar::Keep);
+        assert_eq!(find(&interpolated, "path.0"), Some(&Value::from("/home/user")));
+        assert_eq!(find(&interpolated, "path.1.nested"), Some(&Value::from("/home/user/x")));
+    }
+
+    use super::ValueExt;
+
+    #[test]
+    fn as_bool_loose_accepts_typed_booleans() {
+        assert_eq!(Value::Boolean(true).as_bool_loose(), Some(true));
+        assert_eq!(Value::Boolean(false).as_bool_loose(), Some(false));
+    }
+
+    #[test]
+    fn as_bool_loose_accepts_common_truthy_and_falsy_strings() {
+        for truthy in &["true", "TRUE", "1", "yes", "YES", "on", "On"] {
+            assert_eq!(Value::from(*truthy).as_bool_loose(), Some(true), "{}", truthy);
+        }
+
+        for falsy in &["false", "FALSE", "0", "no", "NO", "off", "Off"] {
+            assert_eq!(Value::from(*falsy).as_bool_loose(), Some(false), "{}", falsy);
+        }
+    }
+
+    #[test]
+    fn as_bool_loose_rejects_anything_else() {
+        assert_eq!(Value::from("nope").as_bool_loose(), None);
+        assert_eq!(Value::Integer(1).as_bool_loose(), None);
+        assert_eq!(Value::Float(1.0).as_bool_loose(), None);
+    }
+
+    #[test]
+    fn to_toml_string_renders_a_table() {
+        let root = table(vec![("a", 1.into()), ("b", "two".into())]);
+        assert_eq!(root.to_toml_string().expect("serializable"), "a = 1\nb = \"two\"\n");
+    }
+
+    #[cfg(feature = "json")]
+    #[test]
+    fn to_json_string_renders_a_table() {
+        let root = table(vec![("a", 1.into())]);
+        assert_eq!(root.to_json_string().expect("serializable"), r#"{"a":1}"#);
+    }
 }
@@ -0,0 +1,182 @@
+use std::io::{self, Read};
+use std::ops::{Deref, DerefMut};
+
+use crate::request::Request;
+use crate::http::Status;
+use crate::data::{Data, FromDataSimple, Outcome};
+use crate::outcome::Outcome::*;
+
+/// A data guard that wraps another value read from the request body,
+/// recording whether the body was truncated by the configured size limit
+/// rather than ending naturally.
+///
+/// Unlike reading data through a plain limit (`data.open().take(limit)`),
+/// which silently stops at `limit` with no indication that more data
+/// existed, `Capped<T>` lets a handler distinguish a body that happened to be
+/// exactly the limit from one that was cut off, and, if desired, respond
+/// with a `413 Payload Too Large` instead of proceeding with a partial body.
+///
+/// # Example
+///
+/// ```rust
+/// # #![feature(proc_macro_hygiene)]
+/// # #[macro_use] extern crate rocket;
+/// use rocket::data::Capped;
+/// use rocket::http::Status;
+///
+/// #[post("/upload", data = "<body>")]
+/// fn upload(body: Capped<String>) -> Result<String, Status> {
+///     if !body.is_complete() {
+///         return Err(Status::PayloadTooLarge);
+///     }
+///
+///     Ok(body.into_inner())
+/// }
+/// # fn main() { }
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Capped<T> {
+    value: T,
+    complete: bool,
+}
+
+impl<T> Capped<T> {
+    /// Wraps `value`, recording whether the body it was read from was
+    /// consumed in its entirety (`complete`) or truncated at the configured
+    /// limit.
+    #[inline(always)]
+    pub fn new(value: T, complete: bool) -> Capped<T> {
+        Capped { value, complete }
+    }
+
+    /// Returns `true` if the body ended on its own before the configured
+    /// limit was reached, and `false` if it was truncated at the limit.
+    #[inline(always)]
+    pub fn is_complete(&self) -> bool {
+        self.complete
+    }
+
+    /// Consumes `self`, returning the wrapped value.
+    #[inline(always)]
+    pub fn into_inner(self) -> T {
+        self.value
+    }
+}
+
+impl<T> Deref for Capped<T> {
+    type Target = T;
+
+    #[inline(always)]
+    fn deref(&self) -> &T {
+        &self.value
+    }
+}
+
+impl<T> DerefMut for Capped<T> {
+    #[inline(always)]
+    fn deref_mut(&mut self) -> &mut T {
+        &mut self.value
+    }
+}
+
+/// The default limit, in bytes, applied to `Capped<String>` and
+/// `Capped<Vec<u8>>` when the request's configured limits don't set a
+/// `"string"` or `"bytes"` limit, respectively.
+const DEFAULT_LIMIT: u64 = 1 * 1024 * 1024;
+
+impl FromDataSimple for Capped<String> {
+    type Error = io::Error;
+
+    fn from_data(request: &Request<'_>, data: Data) -> Outcome<Self, Self::Error> {
+        let limit = request.limits().get("string").unwrap_or(DEFAULT_LIMIT);
+        let mut stream = data.open_limited(limit);
+        let mut string = String::new();
+        match stream.read_to_string(&mut string) {
+            Ok(_) => Success(Capped::new(string, !stream.was_truncated())),
+            Err(e) => Failure((Status::BadRequest, e))
+        }
+    }
+}
+
+impl FromDataSimple for Capped<Vec<u8>> {
+    type Error = io::Error;
+
+    fn from_data(request: &Request<'_>, data: Data) -> Outcome<Self, Self::Error> {
+        let limit = request.limits().get("bytes").unwrap_or(DEFAULT_LIMIT);
+        let mut stream = data.open_limited(limit);
+        let mut bytes = Vec::new();
+        match stream.read_to_end(&mut bytes) {
+            Ok(_) => Success(Capped::new(bytes, !stream.was_truncated())),
+            Err(e) => Failure((Status::BadRequest, e))
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::config::{Config, Environment, Limits};
+    use crate::http::Method;
+    use crate::http::uri::Origin;
+
+    fn capped_string(body: &[u8], limits: Limits) -> Capped<String> {
+        let config = Config::build(Environment::Development)
+            .limits(limits)
+            .finalize()
+            .expect("valid config");
+
+        let rocket = crate::custom(config);
+        let request = Request::new(&rocket, Method::Post, Origin::dummy());
+        let data = Data::local(body.to_vec(), 512);
+        match Capped::<String>::from_data(&request, data) {
+            Success(capped) => capped,
+            _ => panic!("expected Success"),
+        }
+    }
+
+    fn capped_bytes(body: &[u8], limits: Limits) -> Capped<Vec<u8>> {
+        let config = Config::build(Environment::Development)
+            .limits(limits)
+            .finalize()
+            .expect("valid config");
+
+        let rocket = crate::custom(config);
+        let request = Request::new(&rocket, Method::Post, Origin::dummy());
+        let data = Data::local(body.to_vec(), 512);
+        match Capped::<Vec<u8>>::from_data(&request, data) {
+            Success(capped) => capped,
+            _ => panic!("expected Success"),
+        }
+    }
+
+    #[test]
+    fn string_under_limit_is_complete() {
+        let capped = capped_string(b"hello", Limits::new());
+        assert_eq!(&*capped, "hello");
+        assert!(capped.is_complete());
+    }
+
+    #[test]
+    fn string_exactly_at_limit_is_complete() {
+        let limits = Limits::new().limit("string", 5);
+        let capped = capped_string(b"hello", limits);
+        assert_eq!(&*capped, "hello");
+        assert!(capped.is_complete());
+    }
+
+    #[test]
+    fn string_over_limit_is_truncated() {
+        let limits = Limits::new().limit("string", 3);
+        let capped = capped_string(b"hello", limits);
+        assert_eq!(&*capped, "hel");
+        assert!(!capped.is_complete());
+    }
+
+    #[test]
+    fn bytes_over_limit_is_truncated() {
+        let limits = Limits::new().limit("bytes", 3);
+        let capped = capped_bytes(b"hello", limits);
+        assert_eq!(&*capped, b"hel");
+        assert!(!capped.is_complete());
+    }
+}
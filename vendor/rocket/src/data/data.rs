@@ -1,11 +1,11 @@
 use std::io::{self, Read, Write, Cursor, Chain};
 use std::path::Path;
-use std::fs::File;
+use std::fs::{self, File};
 use std::time::Duration;
 
 #[cfg(feature = "tls")] use super::net_stream::HttpsStream;
 
-use super::data_stream::{DataStream, kill_stream};
+use super::data_stream::{DataStream, LimitedDataStream, kill_stream};
 use super::net_stream::NetStream;
 use crate::ext::ReadExt;
 
@@ -13,6 +13,7 @@ use crate::http::hyper;
 use crate::http::hyper::h1::HttpReader;
 use crate::http::hyper::h1::HttpReader::*;
 use crate::http::hyper::net::{HttpStream, NetworkStream};
+use crate::config::Limits;
 
 pub type HyperBodyReader<'a, 'b> =
     self::HttpReader<&'a mut hyper::buffer::BufReader<&'b mut dyn NetworkStream>>;
@@ -20,9 +21,39 @@ pub type HyperBodyReader<'a, 'b> =
 //                              |---- from hyper ----|
 pub type BodyReader = HttpReader<Chain<Cursor<Vec<u8>>, NetStream>>;
 
-/// The number of bytes to read into the "peek" buffer.
+/// The default number of bytes to read into the "peek" buffer, used unless
+/// overridden by a `peek` limit in a `Rocket` instance's configured
+/// [`Limits`].
 const PEEK_BYTES: usize = 512;
 
+/// Determines the peek buffer capacity to use given a set of configured
+/// `Limits`, falling back to the default of 512 bytes if no `peek` limit was
+/// set.
+pub(crate) fn peek_cap(limits: &Limits) -> usize {
+    limits.get("peek").unwrap_or(PEEK_BYTES as u64) as usize
+}
+
+/// Error returned by [`Data::peek_bytes_exact()`] when the requested number
+/// of bytes could not be produced.
+#[derive(Debug)]
+pub enum PeekError {
+    /// Fewer than the requested number of bytes are available in the body.
+    /// `available` is the number of bytes that were actually buffered.
+    Incomplete { available: usize },
+    /// An I/O error occurred while filling the peek buffer.
+    Io(io::Error),
+}
+
+/// Signals whether [`Data::stream_to_file_with()`] should keep reading or
+/// abort the transfer.
+#[derive(Debug, PartialEq, Eq)]
+pub enum ControlFlow {
+    /// Keep streaming.
+    Continue,
+    /// Abort the transfer. The partially written file is removed.
+    Break,
+}
+
 /// Type representing the data in the body of an incoming request.
 ///
 /// This type is the only means by which the body of a request can be retrieved.
@@ -59,6 +90,7 @@ pub struct Data {
     buffer: Vec<u8>,
     is_complete: bool,
     stream: BodyReader,
+    peek_cap: usize,
 }
 
 impl Data {
@@ -90,8 +122,52 @@ impl Data {
         DataStream(Cursor::new(buffer).chain(stream))
     }
 
+    /// Like [`open()`](Data::open()), but caps the returned stream to at most
+    /// `limit` bytes and allows the caller to later check whether the body
+    /// had more data than that via
+    /// [`LimitedDataStream::was_truncated()`](crate::data::LimitedDataStream::was_truncated()).
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use rocket::Data;
+    ///
+    /// fn handler(data: Data) {
+    ///     let stream = data.open_limited(1024);
+    /// }
+    /// ```
+    #[inline(always)]
+    pub fn open_limited(self, limit: u64) -> LimitedDataStream {
+        LimitedDataStream::new(self.open(), limit)
+    }
+
+    /// Like [`open_limited()`](Data::open_limited()), but wraps the returned
+    /// stream in a [`BufRead`](io::BufRead), which is more convenient for
+    /// reading line-oriented bodies such as NDJSON.
+    ///
+    /// The peek buffer bytes are included in the returned reader, and
+    /// `limit` is still enforced: reads past it return `Ok(0)`, just as with
+    /// [`LimitedDataStream`].
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use rocket::Data;
+    ///
+    /// fn handler(data: Data) {
+    ///     let mut reader = data.open_buffered(4096);
+    /// }
+    /// ```
+    #[inline(always)]
+    pub fn open_buffered(self, limit: u64) -> io::BufReader<LimitedDataStream> {
+        io::BufReader::new(self.open_limited(limit))
+    }
+
     // FIXME: This is absolutely terrible (downcasting!), thanks to Hyper.
-    pub(crate) fn from_hyp(mut body: HyperBodyReader<'_, '_>) -> Result<Data, &'static str> {
+    pub(crate) fn from_hyp(
+        mut body: HyperBodyReader<'_, '_>,
+        peek_cap: usize
+    ) -> Result<Data, &'static str> {
         #[inline(always)]
         #[cfg(feature = "tls")]
         fn concrete_stream(stream: &mut dyn NetworkStream) -> Option<NetStream> {
@@ -134,15 +210,17 @@ impl Data {
             ChunkedReader(_, n) => ChunkedReader(inner_data, n)
         };
 
-        Ok(Data::new(http_stream))
+        Ok(Data::new(http_stream, peek_cap))
     }
 
     /// Retrieve the `peek` buffer.
     ///
-    /// The peek buffer contains at most 512 bytes of the body of the request.
-    /// The actual size of the returned buffer varies by web request. The
-    /// [`peek_complete`](#method.peek_complete) method can be used to determine
-    /// if this buffer contains _all_ of the data in the body of the request.
+    /// The peek buffer contains at most [`Limits::get("peek")`](Limits::get())
+    /// bytes of the body of the request, or 512 bytes if no such limit was
+    /// configured. The actual size of the returned buffer varies by web
+    /// request. The [`peek_complete`](#method.peek_complete) method can be
+    /// used to determine if this buffer contains _all_ of the data in the
+    /// body of the request.
     ///
     /// # Example
     ///
@@ -155,13 +233,152 @@ impl Data {
     /// ```
     #[inline(always)]
     pub fn peek(&self) -> &[u8] {
-        if self.buffer.len() > PEEK_BYTES {
-            &self.buffer[..PEEK_BYTES]
+        if self.buffer.len() > self.peek_cap {
+            &self.buffer[..self.peek_cap]
         } else {
             &self.buffer
         }
     }
 
+    /// Grows the peek buffer, if necessary, so that it holds up to `num`
+    /// bytes, and returns the resulting buffer.
+    ///
+    /// Unlike [`peek()`](Data::peek()), which is capped at the configured
+    /// peek capacity, this permanently raises that cap to `num` for the
+    /// lifetime of this `Data` instance and reads more of the stream into the
+    /// buffer to fill it. If the body has fewer than `num` bytes,
+    /// `peek_complete()` becomes `true` and the returned slice holds
+    /// whatever was available. This never allocates more than `num` bytes,
+    /// but a large `num` still means the bytes are held in memory for the
+    /// rest of the request; callers that derive `num` from untrusted input
+    /// should bound it themselves to avoid a memory-exhaustion
+    /// denial-of-service.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use rocket::Data;
+    ///
+    /// fn handler(mut data: Data) {
+    ///     let sniffed = data.peek_max(4096);
+    ///     println!("sniffed {} bytes", sniffed.len());
+    /// }
+    /// ```
+    pub fn peek_max(&mut self, num: usize) -> &[u8] {
+        if num > self.peek_cap {
+            self.peek_cap = num;
+        }
+
+        if self.buffer.len() < num && !self.is_complete {
+            let start = self.buffer.len();
+            self.buffer.resize(num, 0);
+            match self.stream.read_max(&mut self.buffer[start..]) {
+                Ok(n) => {
+                    self.buffer.truncate(start + n);
+                    if n < num - start {
+                        self.is_complete = true;
+                    }
+                }
+                Err(e) => {
+                    error_!("Failed to read into peek buffer: {:?}.", e);
+                    self.buffer.truncate(start);
+                    self.is_complete = true;
+                }
+            }
+        }
+
+        let len = std::cmp::min(self.buffer.len(), num);
+        &self.buffer[..len]
+    }
+
+    /// Peeks into the data stream, returning exactly `num` bytes, capped at
+    /// the configured peek capacity (512 bytes by default; see
+    /// [`Limits::get("peek")`](Limits::get())), or an error describing why
+    /// that many bytes aren't available.
+    ///
+    /// Unlike [`peek()`](Data::peek()), which silently returns a shorter
+    /// slice when the body is smaller than `num` or a read error occurs, this
+    /// method distinguishes the two cases via [`PeekError`]. The returned
+    /// bytes are read into and served from the same internal buffer consulted
+    /// by `peek()`, so a later call to [`open()`](Data::open()) still
+    /// observes the entirety of the body.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use rocket::Data;
+    /// use rocket::data::PeekError;
+    ///
+    /// fn handler(mut data: Data) {
+    ///     match data.peek_bytes_exact(4) {
+    ///         Ok(bytes) => println!("Got magic bytes: {:?}", bytes),
+    ///         Err(PeekError::Incomplete { available }) => {
+    ///             println!("Body is too short; only {} bytes available", available);
+    ///         }
+    ///         Err(PeekError::Io(e)) => println!("I/O error while peeking: {:?}", e),
+    ///     }
+    /// }
+    /// ```
+    pub fn peek_bytes_exact(&mut self, num: usize) -> Result<&[u8], PeekError> {
+        let num = std::cmp::min(num, self.peek_cap);
+        if self.buffer.len() < num && !self.is_complete {
+            let start = self.buffer.len();
+            self.buffer.resize(num, 0);
+            match self.stream.read_max(&mut self.buffer[start..]) {
+                Ok(n) => {
+                    self.buffer.truncate(start + n);
+                    if start + n < num {
+                        self.is_complete = true;
+                    }
+                }
+                Err(e) => {
+                    self.buffer.truncate(start);
+                    return Err(PeekError::Io(e));
+                }
+            }
+        }
+
+        if self.buffer.len() < num {
+            Err(PeekError::Incomplete { available: self.buffer.len() })
+        } else {
+            Ok(&self.buffer[..num])
+        }
+    }
+
+    /// Returns whether the stream may contain more data past the current
+    /// `peek` buffer, without reading any of it.
+    ///
+    /// Returns `Some(true)` if the buffer is as large as the configured peek
+    /// capacity and the stream has not yet been read to completion, `Some(false)` if
+    /// [`peek_complete()`](Data::peek_complete()) is `true`, and `None` if
+    /// neither can be determined from the current buffer and `is_complete`
+    /// flag alone. A body whose length is exactly the peek capacity also
+    /// yields `Some(true)`, since its end hasn't been read yet. This never
+    /// reads from the stream, so a guard can use it to decide whether to
+    /// forward without the side effect of consuming data.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use rocket::Data;
+    ///
+    /// fn handler(data: Data) {
+    ///     if data.peek_remaining() == Some(true) {
+    ///         println!("there's more body beyond the peek buffer");
+    ///     }
+    /// }
+    /// ```
+    #[inline(always)]
+    pub fn peek_remaining(&self) -> Option<bool> {
+        if self.is_complete {
+            Some(false)
+        } else if self.buffer.len() >= self.peek_cap {
+            Some(true)
+        } else {
+            None
+        }
+    }
+
     /// Returns true if the `peek` buffer contains all of the data in the body
     /// of the request. Returns `false` if it does not or if it is not known if
     /// it does.
@@ -225,14 +442,106 @@ impl Data {
         io::copy(&mut self.open(), &mut File::create(path)?)
     }
 
+    /// A helper method to write the body of the request to a file at the path
+    /// determined by `path`, failing once more than `limit` bytes are read.
+    ///
+    /// Unlike [`stream_to_file()`](Data::stream_to_file()), this method
+    /// enforces `limit`: if the body contains more than `limit` bytes, the
+    /// write is stopped and an `io::Error` of kind `InvalidData` is returned
+    /// rather than silently truncating the file at `limit` bytes. The file
+    /// then holds the first `limit + 1` bytes of the body.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use std::io;
+    /// use rocket::Data;
+    ///
+    /// fn handler(mut data: Data) -> io::Result<String> {
+    ///     data.stream_to_file_limited("/static/file", 1024 * 1024)
+    ///         .map(|n| format!("Wrote {} bytes to /static/file", n))
+    /// }
+    /// ```
+    pub fn stream_to_file_limited<P: AsRef<Path>>(self, path: P, limit: u64) -> io::Result<u64> {
+        let mut stream = self.open().take(limit + 1);
+        let n = io::copy(&mut stream, &mut File::create(path)?)?;
+        if n > limit {
+            return Err(io::Error::new(
+                io::ErrorKind::InvalidData,
+                "data exceeded the configured limit"
+            ));
+        }
+
+        Ok(n)
+    }
+
+    /// Like [`stream_to_file()`](Data::stream_to_file()), but invokes
+    /// `progress` with the cumulative number of bytes written after every
+    /// buffer flush, giving the caller visibility into (and a way to abort)
+    /// an otherwise-opaque transfer.
+    ///
+    /// If `progress` returns [`ControlFlow::Break`], the transfer stops,
+    /// the partially written file at `path` is removed, and an `io::Error`
+    /// of kind `Interrupted` is returned.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use std::io;
+    /// use rocket::Data;
+    /// use rocket::data::ControlFlow;
+    ///
+    /// fn handler(mut data: Data) -> io::Result<String> {
+    ///     data.stream_to_file_with("/static/file", |written| {
+    ///         if written > 10 * 1024 * 1024 {
+    ///             ControlFlow::Break
+    ///         } else {
+    ///             ControlFlow::Continue
+    ///         }
+    ///     }).map(|n| format!("Wrote {} bytes to /static/file", n))
+    /// }
+    /// ```
+    pub fn stream_to_file_with<P, F>(self, path: P, mut progress: F) -> io::Result<u64>
+        where P: AsRef<Path>, F: FnMut(u64) -> ControlFlow
+    {
+        const BUF_SIZE: usize = 8 * 1024;
+
+        let path = path.as_ref();
+        let mut file = File::create(path)?;
+        let mut stream = self.open();
+        let mut buf = vec![0; BUF_SIZE];
+        let mut written = 0u64;
+
+        loop {
+            let n = stream.read(&mut buf)?;
+            if n == 0 {
+                break;
+            }
+
+            file.write_all(&buf[..n])?;
+            written += n as u64;
+
+            if let ControlFlow::Break = progress(written) {
+                drop(file);
+                let _ = fs::remove_file(path);
+                return Err(io::Error::new(
+                    io::ErrorKind::Interrupted,
+                    "transfer aborted by progress callback"
+                ));
+            }
+        }
+
+        Ok(written)
+    }
+
     // Creates a new data object with an internal buffer `buf`, where the cursor
     // in the buffer is at `pos` and the buffer has `cap` valid bytes. Thus, the
     // bytes `vec[pos..cap]` are buffered and unread. The remainder of the data
     // bytes can be read from `stream`.
     #[inline(always)]
-    pub(crate) fn new(mut stream: BodyReader) -> Data {
+    pub(crate) fn new(mut stream: BodyReader, peek_cap: usize) -> Data {
         trace_!("Data::new({:?})", stream);
-        let mut peek_buf: Vec<u8> = vec![0; PEEK_BYTES];
+        let mut peek_buf: Vec<u8> = vec![0; peek_cap];
 
         // Fill the buffer with as many bytes as possible. If we read less than
         // that buffer's length, we know we reached the EOF. Otherwise, it's
@@ -244,7 +553,7 @@ impl Data {
                 // take the performance hit to avoid `unsafe`. All of this code
                 // should go away when we migrate away from hyper 0.10.x.
                 peek_buf.truncate(n);
-                n < PEEK_BYTES
+                n < peek_cap
             }
             Err(e) => {
                 error_!("Failed to read into peek buffer: {:?}.", e);
@@ -254,19 +563,20 @@ impl Data {
             },
         };
 
-        trace_!("Peek bytes: {}/{} bytes.", peek_buf.len(), PEEK_BYTES);
-        Data { buffer: peek_buf, stream, is_complete: eof }
+        trace_!("Peek bytes: {}/{} bytes.", peek_buf.len(), peek_cap);
+        Data { buffer: peek_buf, stream, is_complete: eof, peek_cap }
     }
 
     /// This creates a `data` object from a local data source `data`.
     #[inline]
-    pub(crate) fn local(data: Vec<u8>) -> Data {
+    pub(crate) fn local(data: Vec<u8>, peek_cap: usize) -> Data {
         let empty_stream = Cursor::new(vec![]).chain(NetStream::Empty);
 
         Data {
             buffer: data,
             stream: HttpReader::SizedReader(empty_stream, 0),
             is_complete: true,
+            peek_cap,
         }
     }
 }
@@ -276,3 +586,100 @@ impl Drop for Data {
         kill_stream(&mut self.stream);
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn peek_bytes_exact_returns_full_slice() {
+        let mut data = Data::local(b"hello, world!".to_vec(), PEEK_BYTES);
+        assert_eq!(data.peek_bytes_exact(5).unwrap(), b"hello");
+    }
+
+    #[test]
+    fn peek_bytes_exact_reports_incomplete_for_short_body() {
+        let mut data = Data::local(b"hi".to_vec(), PEEK_BYTES);
+        match data.peek_bytes_exact(5) {
+            Err(PeekError::Incomplete { available }) => assert_eq!(available, 2),
+            other => panic!("expected Incomplete, got {:?}", other),
+        }
+    }
+
+    #[test]
+    fn peek_remaining_is_false_for_local_data() {
+        let data = Data::local(b"hello".to_vec(), PEEK_BYTES);
+        assert_eq!(data.peek_remaining(), Some(false));
+    }
+
+    #[test]
+    fn peek_honors_configured_cap() {
+        let body = vec![b'a'; 2048];
+        let data = Data::local(body, 4096);
+        assert_eq!(data.peek().len(), 2048);
+        assert_eq!(data.peek_remaining(), Some(false));
+    }
+
+    #[test]
+    fn peek_max_returns_available_bytes_when_body_is_shorter() {
+        let mut data = Data::local(b"short".to_vec(), PEEK_BYTES);
+        assert_eq!(data.peek_max(4096), b"short");
+        assert!(data.peek_complete());
+    }
+
+    #[test]
+    fn stream_to_file_limited_errors_when_exceeded() {
+        let data = Data::local(b"0123456789".to_vec(), PEEK_BYTES);
+        let path = std::env::temp_dir().join("rocket-test-stream-to-file-limited");
+        let err = data.stream_to_file_limited(&path, 5).unwrap_err();
+        assert_eq!(err.kind(), io::ErrorKind::InvalidData);
+        let _ = std::fs::remove_file(path);
+    }
+
+    #[test]
+    fn stream_to_file_limited_succeeds_within_limit() {
+        let data = Data::local(b"0123456789".to_vec(), PEEK_BYTES);
+        let path = std::env::temp_dir().join("rocket-test-stream-to-file-limited-ok");
+        let n = data.stream_to_file_limited(&path, 10).unwrap();
+        assert_eq!(n, 10);
+        let _ = std::fs::remove_file(path);
+    }
+
+    #[test]
+    fn stream_to_file_with_aborts_and_removes_partial_file() {
+        let body = vec![b'a'; 1024 * 1024];
+        let data = Data::local(body, PEEK_BYTES);
+        let path = std::env::temp_dir().join("rocket-test-stream-to-file-with-abort");
+
+        let err = data.stream_to_file_with(&path, |written| {
+            if written >= 256 * 1024 {
+                ControlFlow::Break
+            } else {
+                ControlFlow::Continue
+            }
+        }).unwrap_err();
+
+        assert_eq!(err.kind(), io::ErrorKind::Interrupted);
+        assert!(!path.exists());
+    }
+
+    #[test]
+    fn stream_to_file_with_reports_final_byte_count() {
+        let body = vec![b'a'; 1024 * 1024];
+        let data = Data::local(body, PEEK_BYTES);
+        let path = std::env::temp_dir().join("rocket-test-stream-to-file-with-ok");
+
+        let n = data.stream_to_file_with(&path, |_| ControlFlow::Continue).unwrap();
+        assert_eq!(n, 1024 * 1024);
+        let _ = std::fs::remove_file(&path);
+    }
+
+    #[test]
+    fn peek_cap_uses_configured_limit() {
+        let limits = Limits::new().limit("peek", 4096);
+        assert_eq!(peek_cap(&limits), 4096);
+
+        let limits = Limits::new();
+        assert_eq!(peek_cap(&limits), PEEK_BYTES);
+    }
+}
@@ -1,5 +1,7 @@
 use std::io::{self, Read, Cursor, Chain};
 use std::net::Shutdown;
+use std::rc::Rc;
+use std::cell::RefCell;
 
 use super::data::BodyReader;
 use crate::http::hyper::net::NetworkStream;
@@ -53,3 +55,205 @@ impl Drop for DataStream {
         kill_stream(&mut self.0.get_mut().1);
     }
 }
+
+/// A [`DataStream`] capped to at most a configured number of bytes.
+///
+/// Unlike `data.open().take(limit)`, which silently stops yielding bytes once
+/// `limit` is reached with no indication that more data existed, this type
+/// can report via [`was_truncated()`](LimitedDataStream::was_truncated())
+/// whether the underlying stream had more data beyond the limit once it's
+/// been read to exhaustion.
+pub struct LimitedDataStream {
+    inner: DataStream,
+    remaining: u64,
+    truncated: bool,
+}
+
+impl LimitedDataStream {
+    pub(crate) fn new(inner: DataStream, limit: u64) -> Self {
+        LimitedDataStream { inner, remaining: limit, truncated: false }
+    }
+
+    /// Returns `true` if reading stopped because `limit` was reached and the
+    /// underlying stream had at least one more byte to give, as opposed to
+    /// the body simply ending at or before the limit.
+    #[inline(always)]
+    pub fn was_truncated(&self) -> bool {
+        self.truncated
+    }
+}
+
+impl Read for LimitedDataStream {
+    fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+        if self.remaining == 0 {
+            let mut probe = [0u8; 1];
+            if self.inner.read(&mut probe)? > 0 {
+                self.truncated = true;
+            }
+
+            return Ok(0);
+        }
+
+        let max = std::cmp::min(buf.len() as u64, self.remaining) as usize;
+        let n = self.inner.read(&mut buf[..max])?;
+        self.remaining -= n as u64;
+        Ok(n)
+    }
+}
+
+/// A running hash or checksum that can be fed bytes incrementally.
+///
+/// Implement this trait for a hashing algorithm to use it with
+/// [`DataStream::hashing()`] or [`LimitedDataStream::hashing()`], which feed
+/// every byte of a request body through the hasher as it's read, without a
+/// second pass over the data.
+pub trait Hasher: Default {
+    /// The digest produced by this hasher.
+    type Digest;
+
+    /// Feeds `bytes` into the running hash.
+    fn update(&mut self, bytes: &[u8]);
+
+    /// Computes the digest of all bytes seen so far.
+    ///
+    /// This does not consume or reset the hasher; it may be called again
+    /// after further calls to [`update()`](Hasher::update).
+    fn digest(&self) -> Self::Digest;
+}
+
+/// A handle to the digest being computed by a [`HashingStream`], obtained
+/// from [`DataStream::hashing()`] or [`LimitedDataStream::hashing()`].
+///
+/// The handle can be read at any time, but the digest it reports only
+/// reflects the bytes read through the stream so far; read the stream to
+/// completion before calling [`digest()`](DigestHandle::digest()) if a
+/// digest of the entire body is needed.
+pub struct DigestHandle<H: Hasher>(Rc<RefCell<H>>);
+
+impl<H: Hasher> DigestHandle<H> {
+    /// Computes the digest of the bytes read through the corresponding
+    /// [`HashingStream`] so far.
+    pub fn digest(&self) -> H::Digest {
+        self.0.borrow().digest()
+    }
+}
+
+impl<H: Hasher> Clone for DigestHandle<H> {
+    fn clone(&self) -> Self {
+        DigestHandle(self.0.clone())
+    }
+}
+
+/// A [`Read`] adapter that feeds every byte read through a [`Hasher`],
+/// returned by [`DataStream::hashing()`] or [`LimitedDataStream::hashing()`].
+pub struct HashingStream<R, H: Hasher> {
+    inner: R,
+    hasher: Rc<RefCell<H>>,
+}
+
+impl<R: Read, H: Hasher> HashingStream<R, H> {
+    fn new(inner: R) -> (Self, DigestHandle<H>) {
+        let hasher = Rc::new(RefCell::new(H::default()));
+        let handle = DigestHandle(hasher.clone());
+        (HashingStream { inner, hasher }, handle)
+    }
+}
+
+impl<R: Read, H: Hasher> Read for HashingStream<R, H> {
+    fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+        let n = self.inner.read(buf)?;
+        self.hasher.borrow_mut().update(&buf[..n]);
+        Ok(n)
+    }
+}
+
+impl DataStream {
+    /// Wraps `self` so that every byte read is also fed into a new `H`,
+    /// returning the wrapped stream along with a [`DigestHandle`] that can
+    /// be used to retrieve the running digest.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use rocket::Data;
+    /// use rocket::data::Hasher;
+    ///
+    /// # #[derive(Default)]
+    /// # struct NullHasher;
+    /// # impl Hasher for NullHasher {
+    /// #     type Digest = ();
+    /// #     fn update(&mut self, _: &[u8]) {}
+    /// #     fn digest(&self) -> Self::Digest {}
+    /// # }
+    /// fn handler(data: Data) {
+    ///     let (mut stream, digest) = data.open().hashing::<NullHasher>();
+    ///     let mut buf = Vec::new();
+    ///     std::io::copy(&mut stream, &mut buf).expect("read body");
+    ///     let _ = digest.digest();
+    /// }
+    /// ```
+    #[inline(always)]
+    pub fn hashing<H: Hasher>(self) -> (HashingStream<Self, H>, DigestHandle<H>) {
+        HashingStream::new(self)
+    }
+}
+
+impl LimitedDataStream {
+    /// Wraps `self` so that every byte read is also fed into a new `H`,
+    /// returning the wrapped stream along with a [`DigestHandle`] that can
+    /// be used to retrieve the running digest.
+    ///
+    /// See [`DataStream::hashing()`] for an example.
+    #[inline(always)]
+    pub fn hashing<H: Hasher>(self) -> (HashingStream<Self, H>, DigestHandle<H>) {
+        HashingStream::new(self)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::data::Data;
+
+    #[derive(Default)]
+    struct ByteSum(u64);
+
+    impl Hasher for ByteSum {
+        type Digest = u64;
+
+        fn update(&mut self, bytes: &[u8]) {
+            self.0 += bytes.iter().map(|&b| b as u64).sum::<u64>();
+        }
+
+        fn digest(&self) -> u64 {
+            self.0
+        }
+    }
+
+    #[test]
+    fn digest_reflects_bytes_read_so_far() {
+        let data = Data::local(vec![1u8, 2, 3], 512);
+        let (mut stream, digest) = data.open().hashing::<ByteSum>();
+        assert_eq!(digest.digest(), 0);
+
+        let mut buf = [0u8; 2];
+        stream.read(&mut buf).unwrap();
+        assert_eq!(digest.digest(), 3);
+
+        let mut rest = Vec::new();
+        stream.read_to_end(&mut rest).unwrap();
+        assert_eq!(digest.digest(), 6);
+    }
+
+    #[test]
+    fn handle_clone_shares_state() {
+        let data = Data::local(vec![4u8, 5], 512);
+        let (mut stream, digest) = data.open_limited(512).hashing::<ByteSum>();
+        let clone = digest.clone();
+
+        let mut buf = Vec::new();
+        stream.read_to_end(&mut buf).unwrap();
+        assert_eq!(digest.digest(), 9);
+        assert_eq!(clone.digest(), 9);
+    }
+}
@@ -4,7 +4,10 @@ mod data;
 mod data_stream;
 mod net_stream;
 mod from_data;
+mod capped;
 
-pub use self::data::Data;
-pub use self::data_stream::DataStream;
+pub use self::data::{Data, PeekError, ControlFlow};
+pub(crate) use self::data::peek_cap;
+pub use self::data_stream::{DataStream, LimitedDataStream, Hasher, HashingStream, DigestHandle};
 pub use self::from_data::{FromData, FromDataSimple, Outcome, Transform, Transformed};
+pub use self::capped::Capped;
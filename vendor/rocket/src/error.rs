@@ -26,6 +26,10 @@ pub enum LaunchErrorKind {
     Collision(Vec<(Route, Route)>),
     /// A launch fairing reported an error.
     FailedFairings(Vec<&'static str>),
+    /// The server was asked to stop via a [`Shutdown`](crate::Shutdown)
+    /// handle and did so cleanly; this isn't really an error, but `launch()`
+    /// has no other way to report that it returned on purpose.
+    Shutdown,
     /// An otherwise uncategorized error occurred during launch.
     Unknown(Box<dyn std::error::Error + Send + Sync>)
 }
@@ -145,6 +149,7 @@ impl fmt::Display for LaunchErrorKind {
             LaunchErrorKind::Io(ref e) => write!(f, "I/O error: {}", e),
             LaunchErrorKind::Collision(_) => write!(f, "route collisions detected"),
             LaunchErrorKind::FailedFairings(_) => write!(f, "a launch fairing failed"),
+            LaunchErrorKind::Shutdown => write!(f, "the server was gracefully shut down"),
             LaunchErrorKind::Unknown(ref e) => write!(f, "unknown error: {}", e)
         }
     }
@@ -175,6 +180,7 @@ impl std::error::Error for LaunchError {
             LaunchErrorKind::Io(_) => "an I/O error occurred during launch",
             LaunchErrorKind::Collision(_) => "route collisions were detected",
             LaunchErrorKind::FailedFairings(_) => "a launch fairing reported an error",
+            LaunchErrorKind::Shutdown => "the server was gracefully shut down",
             LaunchErrorKind::Unknown(_) => "an unknown error occurred during launch"
         }
     }
@@ -212,6 +218,9 @@ impl Drop for LaunchError {
 
                 panic!("launch fairing failure");
             }
+            LaunchErrorKind::Shutdown => {
+                // This isn't a failure: the server was asked to stop.
+            }
             LaunchErrorKind::Unknown(ref e) => {
                 error!("Rocket failed to launch due to an unknown error.");
                 panic!("{}", e);
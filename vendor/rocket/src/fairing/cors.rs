@@ -0,0 +1,394 @@
+//! A [`Fairing`] that answers CORS preflight requests and annotates
+//! responses with the appropriate `Access-Control-Allow-*` headers.
+
+use std::collections::HashSet;
+use std::str::FromStr;
+use std::sync::{Arc, RwLock};
+
+use crate::{Request, Response, Rocket};
+use crate::fairing::{Fairing, Info, Kind};
+use crate::http::{Method, Status};
+use crate::http::route::Kind as SegmentKind;
+use crate::http::uncased::uncased_eq;
+use crate::config::Value;
+
+/// A route's method and static path shape, snapshotted at attach time so
+/// that preflight handling doesn't need a live reference back into
+/// [`Rocket`] (which a [`Request`] doesn't carry).
+struct RouteEntry {
+    method: Method,
+    path_segments: Vec<(SegmentKind, String)>,
+    cors: Option<bool>,
+}
+
+impl RouteEntry {
+    fn from_route(route: &crate::Route) -> RouteEntry {
+        let path_segments = route.metadata.path_segments.iter()
+            .map(|s| (s.kind, s.string.to_string()))
+            .collect();
+
+        RouteEntry { method: route.method, path_segments, cors: route.cors }
+    }
+
+    /// Mirrors the router's own static-path matching (see
+    /// `router::collider::paths_match`), which can't be called directly
+    /// since it's private to that module and takes a live `Route` rather
+    /// than this snapshot. Ignores query and format, which aren't needed to
+    /// tell whether *some* route could answer a preflight's path.
+    fn matches_path(&self, request: &Request<'_>) -> bool {
+        let request_segments: Vec<_> = request.raw_path_segments().collect();
+        if self.path_segments.len() > request_segments.len() {
+            return false;
+        }
+
+        for ((kind, string), req_seg) in self.path_segments.iter().zip(request_segments.iter()) {
+            match kind {
+                SegmentKind::Multi => return true,
+                SegmentKind::Static if string.as_str() != req_seg.as_str() => return false,
+                _ => continue,
+            }
+        }
+
+        self.path_segments.len() == request_segments.len()
+    }
+}
+
+/// The set of origins a [`Cors`] fairing allows.
+#[derive(Debug, Clone)]
+enum AllowedOrigins {
+    Any,
+    Some(HashSet<String>),
+}
+
+impl AllowedOrigins {
+    fn allows(&self, origin: &str) -> bool {
+        match self {
+            AllowedOrigins::Any => true,
+            AllowedOrigins::Some(origins) => origins.iter().any(|o| uncased_eq(o, origin)),
+        }
+    }
+}
+
+/// The resolved configuration a [`Cors`] fairing consults on every request,
+/// built once from `cors.*` config values and the mounted route table in
+/// [`Fairing::on_attach()`].
+struct CorsConfig {
+    allowed_origins: AllowedOrigins,
+    allowed_methods: HashSet<Method>,
+    allowed_headers: HashSet<String>,
+    allow_credentials: bool,
+    max_age: Option<u64>,
+    routes: Vec<RouteEntry>,
+}
+
+impl CorsConfig {
+    /// Reads `cors.allowed_origins`, `cors.allowed_methods`,
+    /// `cors.allowed_headers`, `cors.allow_credentials`, and `cors.max_age`
+    /// out of `rocket`'s configured extras. A missing `cors` table, or a
+    /// missing individual key, falls back to the most restrictive default:
+    /// no allowed origins, no allowed methods, no allowed headers, no
+    /// credentials, and no `Access-Control-Max-Age` header.
+    fn from_rocket(rocket: &Rocket) -> Result<CorsConfig, String> {
+        let table = rocket.config().get_table("cors").ok();
+
+        let string_array = |key: &str| -> Vec<String> {
+            table.and_then(|t| t.get(key))
+                .and_then(Value::as_array)
+                .map(|a| a.iter().filter_map(Value::as_str).map(str::to_string).collect())
+                .unwrap_or_default()
+        };
+
+        let allowed_origins = string_array("allowed_origins");
+        let allowed_origins = if allowed_origins.iter().any(|o| o == "*") {
+            AllowedOrigins::Any
+        } else {
+            AllowedOrigins::Some(allowed_origins.into_iter().collect())
+        };
+
+        let allowed_methods = string_array("allowed_methods").iter()
+            .map(|m| Method::from_str(m).map_err(|_| format!("invalid `cors.allowed_methods` entry: `{}`", m)))
+            .collect::<Result<HashSet<_>, _>>()?;
+
+        let allowed_headers = string_array("allowed_headers").into_iter().collect();
+
+        let allow_credentials = table.and_then(|t| t.get("allow_credentials"))
+            .and_then(Value::as_bool)
+            .unwrap_or(false);
+
+        let max_age = table.and_then(|t| t.get("max_age"))
+            .and_then(Value::as_integer)
+            .map(|n| n.max(0) as u64);
+
+        let is_wildcard = match allowed_origins {
+            AllowedOrigins::Any => true,
+            AllowedOrigins::Some(_) => false,
+        };
+
+        if is_wildcard && allow_credentials {
+            return Err("`cors.allow_credentials` cannot be used with a wildcard \
+                         `cors.allowed_origins`: combining the two would let any site \
+                         read credentialed responses".into());
+        }
+
+        let routes = rocket.routes().map(RouteEntry::from_route).collect();
+
+        Ok(CorsConfig {
+            allowed_origins, allowed_methods, allowed_headers, allow_credentials, max_age, routes,
+        })
+    }
+}
+
+/// A [`Fairing`] that answers CORS preflight (`OPTIONS`) requests for
+/// mounted routes and appends `Access-Control-Allow-*` headers (including
+/// `Vary: Origin`) to every cross-origin response.
+///
+/// Configure it via a `cors` table in `Rocket.toml` (or the equivalent
+/// `ROCKET_CORS` environment variable):
+///
+/// ```toml
+/// [global.cors]
+/// allowed_origins = ["https://example.com"]
+/// allowed_methods = ["GET", "POST"]
+/// allowed_headers = ["Content-Type"]
+/// allow_credentials = false
+/// max_age = 3600
+/// ```
+///
+/// Attaching `Cors` with a wildcard `allowed_origins` (`["*"]`) together
+/// with `allow_credentials = true` is rejected at ignite time, since that
+/// combination would let any site read credentialed responses.
+///
+/// A route can opt out of CORS handling entirely with `cors = false`:
+///
+/// ```rust
+/// #[get("/internal", cors = false)]
+/// fn internal() -> &'static str { "not meant to be fetched cross-origin" }
+/// # fn main() {}
+/// ```
+///
+/// # Example
+///
+/// ```rust
+/// use rocket::fairing::Cors;
+///
+/// # let _ = || {
+/// let rocket = rocket::ignite().attach(Cors::default());
+/// # };
+/// ```
+#[derive(Default)]
+pub struct Cors {
+    config: RwLock<Option<Arc<CorsConfig>>>,
+}
+
+impl Cors {
+    fn config(&self) -> Option<Arc<CorsConfig>> {
+        self.config.read().expect("cors config lock").clone()
+    }
+}
+
+impl Fairing for Cors {
+    fn info(&self) -> Info {
+        Info {
+            name: "CORS",
+            kind: Kind::Attach | Kind::Response,
+        }
+    }
+
+    fn on_attach(&self, rocket: Rocket) -> Result<Rocket, Rocket> {
+        match CorsConfig::from_rocket(&rocket) {
+            Ok(config) => {
+                *self.config.write().expect("cors config lock") = Some(Arc::new(config));
+                Ok(rocket)
+            }
+            Err(reason) => {
+                error_!("Invalid CORS configuration: {}", reason);
+                Err(rocket)
+            }
+        }
+    }
+
+    fn on_response<'r>(&self, request: &'r Request<'_>, response: &mut Response<'r>) {
+        let config = match self.config() {
+            Some(config) => config,
+            None => return,
+        };
+
+        let origin = match request.headers().get_one("Origin") {
+            Some(origin) => origin,
+            None => return,
+        };
+
+        if !config.allowed_origins.allows(origin) {
+            return;
+        }
+
+        let preflight_method = request.method() == Method::Options
+            && request.headers().get_one("Access-Control-Request-Method").is_some();
+
+        if preflight_method {
+            // Per convention (see the note above `Fairing`'s definition),
+            // only take over an `OPTIONS` request that nothing else
+            // answered; an explicit user-provided `OPTIONS` route wins.
+            if response.status() != Status::NotFound {
+                return;
+            }
+
+            let requested_method = request.headers().get_one("Access-Control-Request-Method")
+                .and_then(|m| Method::from_str(m).ok());
+
+            let requested_method = match requested_method {
+                Some(method) => method,
+                None => return,
+            };
+
+            if !config.allowed_methods.contains(&requested_method) {
+                return;
+            }
+
+            let matching_route = config.routes.iter()
+                .find(|r| r.method == requested_method && r.matches_path(request));
+
+            let matching_route = match matching_route {
+                Some(route) => route,
+                None => return,
+            };
+
+            if matching_route.cors == Some(false) {
+                return;
+            }
+
+            response.set_status(Status::Ok);
+
+            let methods = config.allowed_methods.iter()
+                .map(Method::to_string)
+                .collect::<Vec<_>>()
+                .join(", ");
+            response.set_raw_header("Access-Control-Allow-Methods", methods);
+
+            if let Some(requested_headers) = request.headers().get_one("Access-Control-Request-Headers") {
+                let allowed = requested_headers.split(',')
+                    .map(str::trim)
+                    .filter(|h| config.allowed_headers.iter().any(|allowed| uncased_eq(allowed, h)))
+                    .collect::<Vec<_>>();
+
+                if !allowed.is_empty() {
+                    response.set_raw_header("Access-Control-Allow-Headers", allowed.join(", "));
+                }
+            }
+
+            if let Some(max_age) = config.max_age {
+                response.set_raw_header("Access-Control-Max-Age", max_age.to_string());
+            }
+        } else if request.route().and_then(|route| route.cors) == Some(false) {
+            // A real (non-preflight) response from a route that opted out.
+            // Preflight opt-outs are handled above via `matching_route`,
+            // since `request.route()` isn't trustworthy there -- the router
+            // never registered an `OPTIONS` route to begin with.
+            return;
+        }
+
+        response.set_raw_header("Access-Control-Allow-Origin", origin.to_string());
+        if config.allow_credentials {
+            response.set_raw_header("Access-Control-Allow-Credentials", "true");
+        }
+
+        response.adjoin_raw_header("Vary", "Origin");
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::local::Client;
+    use crate::config::{Config, Environment, Table};
+
+    #[get("/hello")]
+    fn hello() -> &'static str { "hi" }
+
+    #[get("/private", cors = false)]
+    fn private() -> &'static str { "nope" }
+
+    fn client(cors_config: Table) -> Client {
+        let config = Config::build(Environment::Development)
+            .extra("cors", cors_config)
+            .finalize()
+            .expect("valid config");
+
+        let rocket = crate::custom(config)
+            .attach(Cors::default())
+            .mount("/", routes![hello, private]);
+
+        Client::new(rocket).expect("valid rocket")
+    }
+
+    fn permissive_config() -> Table {
+        let mut table = Table::new();
+        table.insert("allowed_origins".into(), Value::Array(vec!["https://example.com".into()]));
+        table.insert("allowed_methods".into(), Value::Array(vec!["GET".into()]));
+        table.insert("allowed_headers".into(), Value::Array(vec!["Content-Type".into()]));
+        table
+    }
+
+    #[test]
+    fn preflight_for_an_existing_route_succeeds() {
+        let client = client(permissive_config());
+        let response = client.options("/hello")
+            .header(crate::http::Header::new("Origin", "https://example.com"))
+            .header(crate::http::Header::new("Access-Control-Request-Method", "GET"))
+            .dispatch();
+
+        assert_eq!(response.status(), Status::Ok);
+        assert_eq!(response.headers().get_one("Access-Control-Allow-Origin"), Some("https://example.com"));
+        assert_eq!(response.headers().get_one("Access-Control-Allow-Methods"), Some("GET"));
+        assert_eq!(response.headers().get_one("Vary"), Some("Origin"));
+    }
+
+    #[test]
+    fn preflight_for_a_nonexistent_route_stays_404() {
+        let client = client(permissive_config());
+        let response = client.options("/does-not-exist")
+            .header(crate::http::Header::new("Origin", "https://example.com"))
+            .header(crate::http::Header::new("Access-Control-Request-Method", "GET"))
+            .dispatch();
+
+        assert_eq!(response.status(), Status::NotFound);
+        assert_eq!(response.headers().get_one("Access-Control-Allow-Origin"), None);
+    }
+
+    #[test]
+    fn mismatched_origin_is_not_granted_cors_headers() {
+        let client = client(permissive_config());
+        let response = client.options("/hello")
+            .header(crate::http::Header::new("Origin", "https://evil.example"))
+            .header(crate::http::Header::new("Access-Control-Request-Method", "GET"))
+            .dispatch();
+
+        assert_eq!(response.headers().get_one("Access-Control-Allow-Origin"), None);
+    }
+
+    #[test]
+    fn a_route_can_opt_out_of_cors() {
+        let client = client(permissive_config());
+        let mut response = client.get("/private")
+            .header(crate::http::Header::new("Origin", "https://example.com"))
+            .dispatch();
+
+        assert_eq!(response.body_string(), Some("nope".into()));
+        assert_eq!(response.headers().get_one("Access-Control-Allow-Origin"), None);
+    }
+
+    #[test]
+    fn wildcard_origin_with_credentials_is_rejected_at_ignite() {
+        let mut cors_config = Table::new();
+        cors_config.insert("allowed_origins".into(), Value::Array(vec!["*".into()]));
+        cors_config.insert("allow_credentials".into(), Value::Boolean(true));
+
+        let config = Config::build(Environment::Development)
+            .extra("cors", cors_config)
+            .finalize()
+            .expect("valid config");
+
+        let rocket = crate::custom(config).attach(Cors::default());
+        assert!(Client::new(rocket).is_err());
+    }
+}
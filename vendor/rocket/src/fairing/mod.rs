@@ -52,10 +52,18 @@ use crate::{Rocket, Request, Response, Data};
 mod fairings;
 mod ad_hoc;
 mod info_kind;
+mod timing;
+mod request_id;
+mod cors;
+mod rate_limit;
 
 pub(crate) use self::fairings::Fairings;
 pub use self::ad_hoc::AdHoc;
 pub use self::info_kind::{Info, Kind};
+pub use self::timing::{Timing, TimingStats, RouteTiming};
+pub use self::request_id::{RequestId, RequestIdFairing};
+pub use self::cors::Cors;
+pub use self::rate_limit::{RateLimiter, RateLimitFairing, Limited, RateLimitExceeded};
 
 // We might imagine that a request fairing returns an `Outcome`. If it returns
 // `Success`, we don't do any routing and use that response directly. Same if it
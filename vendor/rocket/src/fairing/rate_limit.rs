@@ -0,0 +1,383 @@
+//! A [`Fairing`]/guard pair that rate-limits requests by client IP (or a
+//! configured header) and annotates responses with the outcome.
+//!
+//! # A Note on IP-Keyed Limits
+//!
+//! The default key, [`Request::true_client_ip()`], only trusts
+//! "X-Forwarded-For" from a peer listed in [`proxies.trusted`]; deployed
+//! without that configured, it falls back to [`Request::client_ip()`],
+//! which trusts an unauthenticated "X-Real-IP" header from *any* peer. On
+//! such a deployment, a direct client can rotate its own bucket key (or
+//! frame another client's) just by setting that header -- configure
+//! [`proxies.trusted`] to the addresses of your actual reverse proxy, or
+//! bucket by something else entirely with [`RateLimiter::header_key()`],
+//! for this limiter to resist that.
+//!
+//! [`proxies.trusted`]: crate::config::TrustedProxies
+
+use std::collections::hash_map::DefaultHasher;
+use std::collections::HashMap;
+use std::hash::{Hash, Hasher};
+use std::sync::Mutex;
+use std::time::{Duration, Instant};
+
+use crate::{Request, Response, Rocket};
+use crate::request::{self, FromRequest, State};
+use crate::fairing::{Fairing, Info, Kind};
+use crate::http::Status;
+use crate::outcome::Outcome::{Success, Failure};
+
+/// The number of independently-locked shards a [`RateLimiter`] splits its
+/// key space across, so that checking one key's budget doesn't contend with
+/// checking an unrelated one's.
+const SHARD_COUNT: usize = 16;
+
+/// How a [`RateLimiter`] derives the key it buckets requests by.
+enum KeyExtractor {
+    /// Bucket by [`Request::true_client_ip()`]. A request with no known
+    /// client IP isn't rate limited.
+    ClientIp,
+    /// Bucket by the value of the named header. A request missing the
+    /// header isn't rate limited.
+    Header(String),
+}
+
+impl KeyExtractor {
+    fn key(&self, request: &Request<'_>) -> Option<String> {
+        match self {
+            KeyExtractor::ClientIp => request.true_client_ip().map(|ip| ip.to_string()),
+            KeyExtractor::Header(name) => request.headers().get_one(name).map(str::to_string),
+        }
+    }
+}
+
+struct Bucket {
+    count: u32,
+    window_start: Instant,
+}
+
+/// The outcome of checking a key against a [`RateLimiter`]'s budget.
+#[derive(Debug, Clone, Copy)]
+struct RateLimitDecision {
+    allowed: bool,
+    remaining: u32,
+    retry_after: Duration,
+}
+
+/// Managed state that rate-limits requests by key, tracking counts per key
+/// in a sharded in-memory store. A key's window resets lazily, the next time
+/// it's checked after the window has elapsed, rather than on a background
+/// timer.
+///
+/// Attach [`RateLimitFairing`] to build one from config and copy its
+/// decisions onto responses, and guard routes with [`Limited`] to enforce
+/// it:
+///
+/// ```rust
+/// use rocket::fairing::Limited;
+///
+/// #[get("/")]
+/// fn index(_limit: Limited) -> &'static str {
+///     "within budget"
+/// }
+/// # fn main() {}
+/// ```
+pub struct RateLimiter {
+    limit: u32,
+    window: Duration,
+    key: KeyExtractor,
+    shards: Vec<Mutex<HashMap<String, Bucket>>>,
+}
+
+impl RateLimiter {
+    /// Constructs a rate limiter that allows `limit` requests per `window`
+    /// for each key, bucketing by [`Request::true_client_ip()`] by default.
+    /// See the [module-level note](self#a-note-on-ip-keyed-limits) on
+    /// configuring [`proxies.trusted`](crate::config::TrustedProxies) for
+    /// that default to resist a spoofed "X-Real-IP"/"X-Forwarded-For".
+    pub fn new(limit: u32, window: Duration) -> Self {
+        let shards = (0..SHARD_COUNT).map(|_| Mutex::new(HashMap::new())).collect();
+        RateLimiter { limit, window, key: KeyExtractor::ClientIp, shards }
+    }
+
+    /// Buckets requests by the value of the `name` header instead of the
+    /// client IP. A request missing the header isn't rate limited.
+    pub fn header_key(mut self, name: impl Into<String>) -> Self {
+        self.key = KeyExtractor::Header(name.into());
+        self
+    }
+
+    /// Reads `rate_limit.limit`, `rate_limit.window_secs`, and
+    /// `rate_limit.key_header` out of `rocket`'s configured extras. Returns
+    /// `None` if no `rate_limit` table, or no `limit`/`window_secs` pair, is
+    /// configured -- in which case [`RateLimitFairing`] attaches without
+    /// managing a `RateLimiter` at all, and [`Limited`] lets every request
+    /// through unchecked.
+    fn from_rocket(rocket: &Rocket) -> Option<RateLimiter> {
+        let table = rocket.config().get_table("rate_limit").ok()?;
+        let limit = table.get("limit")?.as_integer()?.max(0) as u32;
+        let window_secs = table.get("window_secs")?.as_integer()?.max(0) as u64;
+
+        let mut limiter = RateLimiter::new(limit, Duration::from_secs(window_secs));
+        if let Some(header) = table.get("key_header").and_then(|v| v.as_str()) {
+            limiter = limiter.header_key(header);
+        }
+
+        Some(limiter)
+    }
+
+    fn shard(&self, key: &str) -> &Mutex<HashMap<String, Bucket>> {
+        let mut hasher = DefaultHasher::new();
+        key.hash(&mut hasher);
+        &self.shards[hasher.finish() as usize % self.shards.len()]
+    }
+
+    fn check(&self, key: &str) -> RateLimitDecision {
+        let mut shard = self.shard(key).lock().expect("rate limiter shard lock");
+        let now = Instant::now();
+        let bucket = shard.entry(key.to_string())
+            .or_insert_with(|| Bucket { count: 0, window_start: now });
+
+        if now.duration_since(bucket.window_start) >= self.window {
+            bucket.count = 0;
+            bucket.window_start = now;
+        }
+
+        let retry_after = self.window.saturating_sub(now.duration_since(bucket.window_start));
+        if bucket.count >= self.limit {
+            return RateLimitDecision { allowed: false, remaining: 0, retry_after };
+        }
+
+        bucket.count += 1;
+        RateLimitDecision { allowed: true, remaining: self.limit - bucket.count, retry_after }
+    }
+}
+
+/// A request guard that enforces a managed [`RateLimiter`]'s budget for the
+/// request's key, succeeding without a check when no `RateLimiter` is
+/// managed or the request has no key to bucket by.
+pub struct Limited;
+
+/// The error [`Limited`] fails a request's guard with when its rate limit
+/// has been exhausted.
+#[derive(Debug)]
+pub struct RateLimitExceeded;
+
+impl<'a, 'r> FromRequest<'a, 'r> for Limited {
+    type Error = RateLimitExceeded;
+
+    fn from_request(request: &'a Request<'r>) -> request::Outcome<Self, Self::Error> {
+        let limiter = match request.guard::<State<'_, RateLimiter>>().succeeded() {
+            Some(limiter) => limiter,
+            None => return Success(Limited),
+        };
+
+        let key = match limiter.key.key(request) {
+            Some(key) => key,
+            None => return Success(Limited),
+        };
+
+        let decision = limiter.check(&key);
+        request.local_cache(|| Some(decision));
+
+        if decision.allowed {
+            Success(Limited)
+        } else {
+            Failure((Status::TooManyRequests, RateLimitExceeded))
+        }
+    }
+}
+
+/// A [`Fairing`] that builds a [`RateLimiter`] from `rate_limit.*` config at
+/// ignite time and copies the outcome of each [`Limited`] guard check onto
+/// the response: an `X-RateLimit-Remaining` header on every checked request,
+/// plus a `Retry-After` header when the budget was exhausted.
+///
+/// Configure it via a `rate_limit` table in `Rocket.toml`:
+///
+/// ```toml
+/// [global.rate_limit]
+/// limit = 60
+/// window_secs = 60
+/// key_header = "X-Api-Key"
+/// ```
+///
+/// `key_header`, if present, buckets requests by that header's value instead
+/// of the default, `Request::true_client_ip()` -- see the
+/// [module-level note](self#a-note-on-ip-keyed-limits) on configuring
+/// [`proxies.trusted`](crate::config::TrustedProxies) for that default to
+/// mean anything on a deployment without a reverse proxy in front of it.
+///
+/// # Example
+///
+/// ```rust
+/// use rocket::fairing::RateLimitFairing;
+///
+/// # let _ = || {
+/// let rocket = rocket::ignite().attach(RateLimitFairing);
+/// # };
+/// ```
+pub struct RateLimitFairing;
+
+impl Fairing for RateLimitFairing {
+    fn info(&self) -> Info {
+        Info {
+            name: "Rate Limit",
+            kind: Kind::Attach | Kind::Response,
+        }
+    }
+
+    fn on_attach(&self, rocket: Rocket) -> Result<Rocket, Rocket> {
+        match RateLimiter::from_rocket(&rocket) {
+            Some(limiter) => Ok(rocket.manage(limiter)),
+            None => Ok(rocket),
+        }
+    }
+
+    fn on_response<'r>(&self, request: &'r Request<'_>, response: &mut Response<'r>) {
+        let decision = match *request.local_cache(|| Option::<RateLimitDecision>::None) {
+            Some(decision) => decision,
+            None => return,
+        };
+
+        response.set_raw_header("X-RateLimit-Remaining", decision.remaining.to_string());
+
+        if !decision.allowed {
+            response.set_raw_header("Retry-After", decision.retry_after.as_secs().to_string());
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::local::Client;
+    use crate::config::{Config, Environment, Table, TrustedProxies};
+    use crate::http::Header;
+
+    #[get("/")]
+    fn index(_limit: Limited) -> &'static str { "ok" }
+
+    fn client(limit: i64, window_secs: i64) -> Client {
+        let mut rate_limit = Table::new();
+        rate_limit.insert("limit".into(), limit.into());
+        rate_limit.insert("window_secs".into(), window_secs.into());
+
+        let config = Config::build(Environment::Development)
+            .extra("rate_limit", rate_limit)
+            .finalize()
+            .expect("valid config");
+
+        let rocket = crate::custom(config)
+            .attach(RateLimitFairing)
+            .mount("/", routes![index]);
+
+        Client::new(rocket).expect("valid rocket")
+    }
+
+    fn client_behind_trusted_proxy(limit: i64, window_secs: i64) -> Client {
+        let mut rate_limit = Table::new();
+        rate_limit.insert("limit".into(), limit.into());
+        rate_limit.insert("window_secs".into(), window_secs.into());
+
+        let proxies = TrustedProxies::parse(vec!["10.0.0.0/8"]).unwrap();
+        let config = Config::build(Environment::Development)
+            .extra("rate_limit", rate_limit)
+            .proxies(proxies)
+            .finalize()
+            .expect("valid config");
+
+        let rocket = crate::custom(config)
+            .attach(RateLimitFairing)
+            .mount("/", routes![index]);
+
+        Client::new(rocket).expect("valid rocket")
+    }
+
+    #[test]
+    fn a_spoofed_x_forwarded_for_cant_rotate_the_bucket_behind_a_trusted_proxy() {
+        let client = client_behind_trusted_proxy(1, 60);
+
+        // The first request exhausts the limit for the real client behind
+        // the trusted proxy, `203.0.113.5`.
+        let response = client.get("/")
+            .remote("10.0.0.1:1234".parse().unwrap())
+            .header(Header::new("X-Forwarded-For", "203.0.113.5, 10.0.0.1"))
+            .dispatch();
+        assert_eq!(response.status(), Status::Ok);
+
+        // A second request through the same trusted proxy, claiming a
+        // different client via the same header, doesn't get a fresh
+        // bucket: walking from the right past the trusted `10.0.0.1`, the
+        // walk stops at the first untrusted hop, `203.0.113.5`, so the
+        // claimed `6.6.6.6` further left in the header is never reached.
+        let response = client.get("/")
+            .remote("10.0.0.1:1234".parse().unwrap())
+            .header(Header::new("X-Forwarded-For", "6.6.6.6, 203.0.113.5, 10.0.0.1"))
+            .dispatch();
+        assert_eq!(response.status(), Status::TooManyRequests);
+    }
+
+    #[test]
+    fn without_a_trusted_proxy_a_spoofed_x_real_ip_still_rotates_the_bucket() {
+        // Documents the residual gap the module docs warn about: with no
+        // `proxies.trusted` configured, `true_client_ip()` falls back to
+        // `client_ip()`, which trusts "X-Real-IP" from any peer.
+        let client = client(1, 60);
+
+        let response = client.get("/")
+            .header(Header::new("X-Real-IP", "203.0.113.1"))
+            .dispatch();
+        assert_eq!(response.status(), Status::Ok);
+
+        let response = client.get("/")
+            .header(Header::new("X-Real-IP", "203.0.113.2"))
+            .dispatch();
+        assert_eq!(response.status(), Status::Ok);
+    }
+
+    #[test]
+    fn requests_within_budget_succeed_with_remaining_header() {
+        let client = client(2, 60);
+
+        let response = client.get("/").remote("203.0.113.9:80".parse().unwrap()).dispatch();
+        assert_eq!(response.status(), Status::Ok);
+        assert_eq!(response.headers().get_one("X-RateLimit-Remaining"), Some("1"));
+
+        let response = client.get("/").remote("203.0.113.9:80".parse().unwrap()).dispatch();
+        assert_eq!(response.status(), Status::Ok);
+        assert_eq!(response.headers().get_one("X-RateLimit-Remaining"), Some("0"));
+    }
+
+    #[test]
+    fn the_request_past_the_limit_is_rejected_with_retry_after() {
+        let client = client(2, 60);
+
+        for _ in 0..2 {
+            let response = client.get("/").remote("203.0.113.9:80".parse().unwrap()).dispatch();
+            assert_eq!(response.status(), Status::Ok);
+        }
+
+        let response = client.get("/").remote("203.0.113.9:80".parse().unwrap()).dispatch();
+        assert_eq!(response.status(), Status::TooManyRequests);
+
+        let retry_after: u64 = response.headers().get_one("Retry-After")
+            .expect("Retry-After header present")
+            .parse()
+            .expect("Retry-After is a number of seconds");
+
+        assert!(retry_after <= 60);
+    }
+
+    #[test]
+    fn without_configured_limits_requests_are_never_rejected() {
+        let rocket = crate::ignite().attach(RateLimitFairing).mount("/", routes![index]);
+        let client = Client::new(rocket).expect("valid rocket");
+
+        for _ in 0..5 {
+            let response = client.get("/").dispatch();
+            assert_eq!(response.status(), Status::Ok);
+            assert_eq!(response.headers().get_one("X-RateLimit-Remaining"), None);
+        }
+    }
+}
@@ -0,0 +1,183 @@
+//! A [`Fairing`] and [`FromRequest`] guard that correlate log lines across a
+//! single request with a request ID.
+
+use std::time::{SystemTime, UNIX_EPOCH};
+
+use rand::Rng;
+
+use crate::{Request, Response, Data};
+use crate::request::{self, FromRequest};
+use crate::fairing::{Fairing, Info, Kind};
+use crate::outcome::Outcome::Success;
+
+/// Header carrying the request ID, both incoming (to allow a caller to
+/// supply its own) and outgoing (so that it can be read back by the caller
+/// or correlated in a log aggregator).
+const HEADER: &str = "X-Request-Id";
+
+/// The longest incoming `X-Request-Id` value `RequestIdFairing` will accept
+/// before generating a new ID instead.
+const MAX_LEN: usize = 128;
+
+/// A request ID, unique (for practical purposes) to a single request.
+///
+/// Retrieve it as a request guard to tag a handler's own log lines or
+/// include it in a response body:
+///
+/// ```rust
+/// use rocket::fairing::RequestId;
+///
+/// #[get("/")]
+/// fn index(id: RequestId) -> String {
+///     format!("your request id is {}", id.0)
+/// }
+/// # fn main() {}
+/// ```
+///
+/// Attach [`RequestIdFairing`] so that a request arriving without its own
+/// `X-Request-Id` header gets one generated for it, and so that every
+/// response echoes the ID back in the same header.
+#[derive(Debug, Clone)]
+pub struct RequestId(pub String);
+
+impl<'a, 'r> FromRequest<'a, 'r> for RequestId {
+    type Error = std::convert::Infallible;
+
+    fn from_request(request: &'a Request<'r>) -> request::Outcome<Self, Self::Error> {
+        Success(request.local_cache(|| RequestId(generate())).clone())
+    }
+}
+
+/// A [`Fairing`] that ensures every request carries a [`RequestId`]: it
+/// accepts a caller-supplied `X-Request-Id` header if it's valid, generates
+/// one otherwise, and echoes the result back on every response -- including
+/// those produced by error catchers.
+///
+/// # Example
+///
+/// ```rust
+/// use rocket::fairing::RequestIdFairing;
+///
+/// # let _ = || {
+/// let rocket = rocket::ignite().attach(RequestIdFairing);
+/// # };
+/// ```
+pub struct RequestIdFairing;
+
+impl Fairing for RequestIdFairing {
+    fn info(&self) -> Info {
+        Info {
+            name: "Request ID",
+            kind: Kind::Request | Kind::Response,
+        }
+    }
+
+    fn on_request(&self, request: &mut Request<'_>, _: &Data) {
+        let id = request.headers().get_one(HEADER)
+            .filter(|value| is_valid(value))
+            .map(str::to_string)
+            .unwrap_or_else(generate);
+
+        request.local_cache(|| RequestId(id));
+    }
+
+    fn on_response<'r>(&self, request: &'r Request<'_>, response: &mut Response<'r>) {
+        let id = request.local_cache(|| RequestId(generate()));
+        response.set_raw_header(HEADER, id.0.clone());
+    }
+}
+
+/// An incoming request ID is accepted as-is only if it's non-empty, no
+/// longer than [`MAX_LEN`], and made up entirely of visible (non-whitespace,
+/// non-control) ASCII characters -- the same character class HTTP tokens are
+/// typically restricted to, and narrow enough to rule out header-splitting
+/// shenanigans.
+fn is_valid(value: &str) -> bool {
+    !value.is_empty()
+        && value.len() <= MAX_LEN
+        && value.bytes().all(|b| b.is_ascii_graphic())
+}
+
+/// Generates a cheap, ULID-like identifier: a hex-encoded millisecond
+/// timestamp (so IDs sort roughly by creation time) followed by hex-encoded
+/// random bits (so concurrent requests in the same millisecond don't
+/// collide). This avoids pulling in a UUID implementation just to tag log
+/// lines.
+fn generate() -> String {
+    let millis = SystemTime::now().duration_since(UNIX_EPOCH)
+        .map(|d| d.as_millis())
+        .unwrap_or(0);
+
+    let random: u64 = rand::thread_rng().gen();
+    format!("{:011x}{:016x}", millis, random)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::local::Client;
+
+    #[get("/")]
+    fn index(id: RequestId) -> String { id.0 }
+
+    fn client() -> Client {
+        let rocket = crate::ignite().attach(RequestIdFairing).mount("/", routes![index]);
+        Client::new(rocket).expect("valid rocket")
+    }
+
+    #[test]
+    fn a_provided_id_is_echoed_back_and_seen_by_the_guard() {
+        let client = client();
+        let mut response = client.get("/")
+            .header(crate::http::Header::new(HEADER, "caller-supplied-id"))
+            .dispatch();
+
+        assert_eq!(response.headers().get_one(HEADER), Some("caller-supplied-id"));
+        assert_eq!(response.body_string(), Some("caller-supplied-id".into()));
+    }
+
+    #[test]
+    fn an_absent_id_is_generated() {
+        let client = client();
+        let mut response = client.get("/").dispatch();
+
+        let header = response.headers().get_one(HEADER).expect("id header present").to_string();
+        assert!(is_valid(&header));
+        assert_eq!(response.body_string(), Some(header));
+    }
+
+    #[test]
+    fn an_invalid_incoming_id_is_replaced_with_a_generated_one() {
+        let client = client();
+        let mut response = client.get("/")
+            .header(crate::http::Header::new(HEADER, "has a space"))
+            .dispatch();
+
+        let header = response.headers().get_one(HEADER).expect("id header present").to_string();
+        assert_ne!(header, "has a space");
+        assert!(is_valid(&header));
+        assert_eq!(response.body_string(), Some(header));
+    }
+
+    #[test]
+    fn an_overlong_incoming_id_is_replaced_with_a_generated_one() {
+        let client = client();
+        let too_long = "a".repeat(MAX_LEN + 1);
+        let mut response = client.get("/")
+            .header(crate::http::Header::new(HEADER, too_long.clone()))
+            .dispatch();
+
+        let header = response.headers().get_one(HEADER).expect("id header present").to_string();
+        assert_ne!(header, too_long);
+        assert!(is_valid(&header));
+    }
+
+    #[test]
+    fn the_id_is_present_on_error_catcher_responses() {
+        let client = client();
+        let response = client.get("/does-not-exist").dispatch();
+
+        let header = response.headers().get_one(HEADER).expect("id header present");
+        assert!(is_valid(header));
+    }
+}
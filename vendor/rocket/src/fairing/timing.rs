@@ -0,0 +1,260 @@
+//! A [`Fairing`] that times requests and exposes per-route percentiles.
+
+use std::collections::HashMap;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::{Arc, RwLock};
+use std::time::{Duration, Instant};
+
+use crate::{Request, Response, Data, Rocket};
+use crate::fairing::{Fairing, Info, Kind};
+use crate::http::{Method, Status};
+use crate::request::State;
+
+/// Upper bound, in microseconds, of each histogram bucket. A sample is sorted
+/// into the first bucket whose bound is greater than or equal to it; samples
+/// larger than every bound fall into an implicit overflow bucket.
+const BOUNDS_US: &[u64] = &[
+    1_000, 5_000, 10_000, 25_000, 50_000,
+    100_000, 250_000, 500_000, 1_000_000, 5_000_000,
+];
+
+/// A fixed-bucket histogram recording elapsed microseconds. Recording only
+/// increments an [`AtomicU64`] counter, so many requests can record
+/// concurrently without blocking each other.
+struct Histogram {
+    buckets: Vec<AtomicU64>,
+}
+
+impl Histogram {
+    fn new() -> Self {
+        // One counter per bound, plus one for the overflow bucket.
+        let buckets = (0..BOUNDS_US.len() + 1).map(|_| AtomicU64::new(0)).collect();
+        Histogram { buckets }
+    }
+
+    fn record(&self, micros: u64) {
+        let i = BOUNDS_US.iter().position(|&bound| micros <= bound)
+            .unwrap_or(BOUNDS_US.len());
+
+        self.buckets[i].fetch_add(1, Ordering::Relaxed);
+    }
+
+    fn counts(&self) -> Vec<u64> {
+        self.buckets.iter().map(|c| c.load(Ordering::Relaxed)).collect()
+    }
+}
+
+/// Estimates the `p`th percentile (`0.0..=1.0`) from per-bucket `counts`,
+/// assuming a sample landed in bucket `i` took `BOUNDS_US[i]` microseconds
+/// (or the last bound, for the overflow bucket).
+fn percentile(counts: &[u64], total: u64, p: f64) -> Duration {
+    if total == 0 {
+        return Duration::from_secs(0);
+    }
+
+    let target = (p * total as f64).ceil() as u64;
+    let mut seen = 0;
+    for (i, &count) in counts.iter().enumerate() {
+        seen += count;
+        if seen >= target {
+            let bound_us = BOUNDS_US.get(i).copied().unwrap_or_else(|| *BOUNDS_US.last().unwrap());
+            return Duration::from_micros(bound_us);
+        }
+    }
+
+    Duration::from_micros(*BOUNDS_US.last().unwrap())
+}
+
+/// A snapshot of the timing stats recorded for a single route, or for
+/// requests that no route handled (`route` is `"unrouted"` in that case).
+#[derive(Debug, Clone)]
+pub struct RouteTiming {
+    /// The method of the requests this entry summarizes.
+    pub method: Method,
+    /// The route's name (or URI, if unnamed), or `"unrouted"`.
+    pub route: String,
+    /// The number of requests recorded for this method/route pair.
+    pub count: u64,
+    /// An estimate of the 50th percentile response time.
+    pub p50: Duration,
+    /// An estimate of the 95th percentile response time.
+    pub p95: Duration,
+    /// An estimate of the 99th percentile response time.
+    pub p99: Duration,
+}
+
+/// Managed state recording request timings, bucketed by method and route.
+///
+/// Attach the [`Timing`] fairing to populate this automatically, then fetch
+/// it as you would any other managed state to inspect the results:
+///
+/// ```rust
+/// use rocket::fairing::{Timing, TimingStats};
+///
+/// # let _ = || {
+/// let rocket = rocket::ignite().attach(Timing);
+/// # };
+///
+/// #[get("/stats")]
+/// fn stats(stats: rocket::State<'_, TimingStats>) -> String {
+///     format!("{} routes timed", stats.snapshot().len())
+/// }
+/// # fn main() {}
+/// ```
+pub struct TimingStats {
+    histograms: RwLock<HashMap<(Method, String), Arc<Histogram>>>,
+}
+
+impl TimingStats {
+    fn new() -> Self {
+        TimingStats { histograms: RwLock::new(HashMap::new()) }
+    }
+
+    fn record(&self, method: Method, route: String, elapsed: Duration) {
+        let key = (method, route);
+        let micros = elapsed.as_micros().min(u64::max_value() as u128) as u64;
+
+        // Common case: the histogram already exists, so a read lock suffices.
+        if let Some(histogram) = self.histograms.read().expect("timing lock").get(&key) {
+            histogram.record(micros);
+            return;
+        }
+
+        // Rare case: this is the first request for this method/route pair.
+        let mut histograms = self.histograms.write().expect("timing lock");
+        let histogram = histograms.entry(key).or_insert_with(|| Arc::new(Histogram::new()));
+        histogram.record(micros);
+    }
+
+    /// Returns a snapshot of the timing stats recorded for every method and
+    /// route observed so far.
+    pub fn snapshot(&self) -> Vec<RouteTiming> {
+        let histograms = self.histograms.read().expect("timing lock");
+        histograms.iter().map(|((method, route), histogram)| {
+            let counts = histogram.counts();
+            let total: u64 = counts.iter().sum();
+            RouteTiming {
+                method: *method,
+                route: route.clone(),
+                count: total,
+                p50: percentile(&counts, total, 0.50),
+                p95: percentile(&counts, total, 0.95),
+                p99: percentile(&counts, total, 0.99),
+            }
+        }).collect()
+    }
+}
+
+#[derive(Copy, Clone)]
+struct TimingStart(Option<Instant>);
+
+/// A [`Fairing`] that times every request and records the elapsed duration
+/// into per-route histograms, exposed as managed [`TimingStats`].
+///
+/// Attaching `Timing` also sets an `X-Response-Time` header on every
+/// response, reporting the elapsed time in milliseconds.
+///
+/// # Example
+///
+/// ```rust
+/// use rocket::fairing::Timing;
+///
+/// # let _ = || {
+/// let rocket = rocket::ignite().attach(Timing);
+/// # };
+/// ```
+pub struct Timing;
+
+impl Fairing for Timing {
+    fn info(&self) -> Info {
+        Info {
+            name: "Request Timing",
+            kind: Kind::Attach | Kind::Request | Kind::Response,
+        }
+    }
+
+    fn on_attach(&self, rocket: Rocket) -> Result<Rocket, Rocket> {
+        Ok(rocket.manage(TimingStats::new()))
+    }
+
+    fn on_request(&self, request: &mut Request<'_>, _: &Data) {
+        request.local_cache(|| TimingStart(Some(Instant::now())));
+    }
+
+    fn on_response<'r>(&self, request: &'r Request<'_>, response: &mut Response<'r>) {
+        let start_time = request.local_cache(|| TimingStart(None));
+        let elapsed = match start_time.0 {
+            Some(start) => start.elapsed(),
+            None => return,
+        };
+
+        let millis = elapsed.as_secs_f64() * 1000.0;
+        response.set_raw_header("X-Response-Time", format!("{:.1}ms", millis));
+
+        let stats = match request.guard::<State<'_, TimingStats>>().succeeded() {
+            Some(stats) => stats,
+            None => return,
+        };
+
+        let route = if response.status() == Status::NotFound {
+            "unrouted".to_string()
+        } else {
+            match request.route() {
+                Some(route) => route.name.map(str::to_string)
+                    .unwrap_or_else(|| route.uri.to_string()),
+                None => "unrouted".to_string(),
+            }
+        };
+
+        stats.record(request.method(), route, elapsed);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::local::Client;
+
+    #[get("/hello")]
+    fn hello() -> &'static str { "hi" }
+
+    fn client() -> Client {
+        let rocket = crate::ignite().attach(Timing).mount("/", routes![hello]);
+        Client::new(rocket).expect("valid rocket")
+    }
+
+    #[test]
+    fn response_time_header_is_a_parseable_duration() {
+        let client = client();
+        let response = client.get("/hello").dispatch();
+
+        let header = response.headers().get_one("X-Response-Time").expect("header present");
+        let millis: f64 = header.trim_end_matches("ms").parse().expect("parses as a number");
+        assert!(millis >= 0.0);
+    }
+
+    #[test]
+    fn snapshot_counts_requests_per_route() {
+        let client = client();
+        for _ in 0..5 {
+            client.get("/hello").dispatch();
+        }
+
+        let stats = client.rocket().state::<TimingStats>().expect("managed state");
+        let snapshot = stats.snapshot();
+        let hello = snapshot.iter().find(|t| t.route == "hello").expect("hello route timed");
+        assert_eq!(hello.count, 5);
+        assert_eq!(hello.method, Method::Get);
+    }
+
+    #[test]
+    fn unmatched_routes_are_bucketed_separately() {
+        let client = client();
+        client.get("/does-not-exist").dispatch();
+
+        let stats = client.rocket().state::<TimingStats>().expect("managed state");
+        let snapshot = stats.snapshot();
+        let unrouted = snapshot.iter().find(|t| t.route == "unrouted").expect("unrouted bucket");
+        assert_eq!(unrouted.count, 1);
+    }
+}
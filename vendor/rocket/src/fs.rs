@@ -0,0 +1,253 @@
+//! A [`Handler`] that serves static files out of a directory.
+
+use std::path::{Path, PathBuf};
+
+use crate::{Request, Data, Route};
+use crate::handler::{Handler, Outcome};
+use crate::http::Method;
+use crate::request::UnsafePathBuf;
+use crate::response::{NamedFile, Redirect};
+
+bitflags::bitflags! {
+    /// Options that influence how a [`FileServer`] serves files from its
+    /// root, passed to [`FileServer::new()`].
+    pub struct Options: u8 {
+        /// If the requested path is a directory, serve its `index.html`
+        /// instead of forwarding. Without this option, a request for a
+        /// directory always forwards.
+        const Index = 0b0001;
+        /// Serve files whose name starts with a dot (other than `..`, which
+        /// is never served). Without this option, such requests forward, the
+        /// same way hidden files are typically kept out of reach since
+        /// they often hold secrets (`.env`, `.git`).
+        const DotFiles = 0b0010;
+        /// Redirect a request for a directory without a trailing slash
+        /// (`/dir`) to one with a trailing slash (`/dir/`), so relative
+        /// links within a served `index.html` resolve correctly. Only takes
+        /// effect together with `Index`.
+        const NormalizeDirs = 0b0100;
+        /// Give the generated route a low-priority default rank instead of
+        /// `Route::new()`'s usual dynamic-path default, so that more
+        /// specific, explicitly-ranked routes are tried before this one.
+        const Rank = 0b1000;
+    }
+}
+
+impl Default for Options {
+    /// The default set of options: just [`Options::Index`].
+    fn default() -> Self {
+        Options::Index
+    }
+}
+
+/// The rank [`FileServer`] uses for its route when [`Options::Rank`] is set.
+const DEFAULT_RANK: isize = 10;
+
+/// A [`Handler`] that serves static files from a directory, replacing the
+/// boilerplate `#[get("/<path..>")] fn files(path: PathBuf) -> Option<NamedFile>`
+/// route every application otherwise writes by hand.
+///
+/// # Example
+///
+/// ```rust
+/// use rocket::fs::{FileServer, Options};
+///
+/// # let _ = || {
+/// let rocket = rocket::ignite()
+///     .mount("/static", FileServer::from("public"))
+///     .mount("/assets", FileServer::new("public/assets", Options::Index | Options::DotFiles));
+/// # };
+/// ```
+///
+/// # Security
+///
+/// Requested paths are resolved against `root` using the same segment rules
+/// `PathBuf`'s [`FromSegments`] implementation applies to every `<param..>`
+/// route parameter: percent-decoded segments that are `..` pop the previous
+/// segment instead of escaping the root, and segments that could otherwise
+/// be used to traverse outside of `root` (or, unless [`Options::DotFiles`]
+/// is set, that start with a dot) are rejected outright. A request whose
+/// path fails these checks is forwarded, not served and not met with an
+/// error, so that a catcher or a lower-ranked route can still handle it.
+#[derive(Clone)]
+pub struct FileServer {
+    root: PathBuf,
+    options: Options,
+}
+
+impl FileServer {
+    /// Constructs a new `FileServer` that serves files from `root` according
+    /// to `options`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `root` doesn't exist or isn't a directory.
+    pub fn new<P: AsRef<Path>>(root: P, options: Options) -> Self {
+        let root = root.as_ref();
+        if !root.is_dir() {
+            error_!("FileServer path '{}' is not a directory.", root.display());
+            warn_!("Aborting early to prevent inevitable failure.");
+            panic!("invalid directory: refusing to continue");
+        }
+
+        FileServer { root: root.to_path_buf(), options }
+    }
+
+    /// Constructs a new `FileServer` that serves files from `root` with the
+    /// default options ([`Options::Index`]). Equivalent to
+    /// `FileServer::new(root, Options::default())`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `root` doesn't exist or isn't a directory.
+    pub fn from<P: AsRef<Path>>(root: P) -> Self {
+        FileServer::new(root, Options::default())
+    }
+}
+
+impl Handler for FileServer {
+    fn handle<'r>(&self, req: &'r Request<'_>, data: Data) -> Outcome<'r> {
+        // Resolve the requested path against `root`, respecting `DotFiles`.
+        // `None` means the request had no segments past the mount point,
+        // i.e. it's for the root of the served directory; `Some(Err(_))`
+        // means a segment failed the safety checks `FromSegments` enforces.
+        let path = if self.options.contains(Options::DotFiles) {
+            match req.get_segments::<UnsafePathBuf>(0) {
+                Some(Ok(path)) => Some(path.into_inner()),
+                Some(Err(_)) => return Outcome::forward(data),
+                None => None,
+            }
+        } else {
+            match req.get_segments::<PathBuf>(0) {
+                Some(Ok(path)) => Some(path),
+                Some(Err(_)) => return Outcome::forward(data),
+                None => None,
+            }
+        };
+
+        let path = match path {
+            Some(path) => self.root.join(path),
+            None => self.root.clone(),
+        };
+
+        if path.is_dir() {
+            if !self.options.contains(Options::Index) {
+                return Outcome::forward(data);
+            }
+
+            if self.options.contains(Options::NormalizeDirs) && !req.uri().path().ends_with('/') {
+                let redirect = match req.uri().query() {
+                    Some(query) => format!("{}/?{}", req.uri().path(), query),
+                    None => format!("{}/", req.uri().path()),
+                };
+
+                return Outcome::from(req, Redirect::to(redirect));
+            }
+
+            return Outcome::from_or_forward(req, data, NamedFile::open(path.join("index.html")).ok());
+        }
+
+        Outcome::from_or_forward(req, data, NamedFile::open(path).ok())
+    }
+}
+
+impl Into<Vec<Route>> for FileServer {
+    fn into(self) -> Vec<Route> {
+        let route = if self.options.contains(Options::Rank) {
+            Route::ranked(DEFAULT_RANK, Method::Get, "/<path..>", self)
+        } else {
+            Route::new(Method::Get, "/<path..>", self)
+        };
+
+        vec![route]
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::fs;
+
+    use crate::local::Client;
+    use crate::http::Status;
+
+    fn served_dir() -> tempfile::TempDir {
+        let dir = tempfile::tempdir().expect("temp dir");
+        fs::write(dir.path().join("index.html"), "home page").unwrap();
+        fs::write(dir.path().join("visible.txt"), "visible").unwrap();
+        fs::write(dir.path().join(".hidden"), "secret").unwrap();
+        fs::create_dir(dir.path().join("sub")).unwrap();
+        fs::write(dir.path().join("sub").join("index.html"), "sub page").unwrap();
+        dir
+    }
+
+    fn client_for(dir: &tempfile::TempDir, options: Options) -> Client {
+        let rocket = crate::ignite().mount("/static", FileServer::new(dir.path(), options));
+        Client::new(rocket).expect("valid rocket")
+    }
+
+    #[test]
+    fn serves_index_for_directory_requests() {
+        let dir = served_dir();
+        let client = client_for(&dir, Options::Index);
+
+        let mut response = client.get("/static/").dispatch();
+        assert_eq!(response.status(), Status::Ok);
+        assert_eq!(response.body_string(), Some("home page".into()));
+
+        let mut response = client.get("/static/sub/").dispatch();
+        assert_eq!(response.status(), Status::Ok);
+        assert_eq!(response.body_string(), Some("sub page".into()));
+    }
+
+    #[test]
+    fn normalize_dirs_redirects_to_trailing_slash() {
+        let dir = served_dir();
+        let client = client_for(&dir, Options::Index | Options::NormalizeDirs);
+
+        let response = client.get("/static/sub").dispatch();
+        assert_eq!(response.status(), Status::SeeOther);
+        assert_eq!(response.headers().get_one("Location"), Some("/static/sub/"));
+    }
+
+    #[test]
+    fn dotfiles_are_rejected_by_default() {
+        let dir = served_dir();
+        let client = client_for(&dir, Options::Index);
+
+        let response = client.get("/static/.hidden").dispatch();
+        assert_eq!(response.status(), Status::NotFound);
+    }
+
+    #[test]
+    fn dotfiles_option_allows_serving_them() {
+        let dir = served_dir();
+        let client = client_for(&dir, Options::Index | Options::DotFiles);
+
+        let mut response = client.get("/static/.hidden").dispatch();
+        assert_eq!(response.status(), Status::Ok);
+        assert_eq!(response.body_string(), Some("secret".into()));
+    }
+
+    #[test]
+    fn mount_point_prefix_is_stripped() {
+        let dir = served_dir();
+        let client = client_for(&dir, Options::Index);
+
+        let mut response = client.get("/static/visible.txt").dispatch();
+        assert_eq!(response.status(), Status::Ok);
+        assert_eq!(response.body_string(), Some("visible".into()));
+    }
+
+    #[test]
+    fn missing_file_forwards_to_the_default_404_catcher() {
+        let dir = served_dir();
+        let client = client_for(&dir, Options::Index);
+
+        // A missing file must forward rather than the handler responding
+        // with its own 404; the only way a 404 can surface here is via
+        // Rocket's default catcher kicking in after every route forwarded.
+        let response = client.get("/static/missing.txt").dispatch();
+        assert_eq!(response.status(), Status::NotFound);
+    }
+}
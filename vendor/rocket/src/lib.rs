@@ -108,6 +108,9 @@ pub mod data;
 pub mod handler;
 pub mod fairing;
 pub mod error;
+pub mod fs;
+#[cfg(feature = "websocket")] pub mod ws;
+#[cfg(feature = "templates")] pub mod templates;
 
 // Reexport of HTTP everything.
 pub mod http {
@@ -125,6 +128,7 @@ mod rocket;
 mod codegen;
 mod catcher;
 mod ext;
+mod shutdown;
 
 #[doc(inline)] pub use crate::response::Response;
 #[doc(inline)] pub use crate::handler::{Handler, ErrorHandler};
@@ -134,8 +138,9 @@ mod ext;
 #[doc(inline)] pub use crate::config::Config;
 pub use crate::router::Route;
 pub use crate::request::{Request, State};
-pub use crate::catcher::Catcher;
+pub use crate::catcher::{Catcher, CatcherUri};
 pub use crate::rocket::Rocket;
+pub use crate::shutdown::Shutdown;
 
 /// Alias to [`Rocket::ignite()`] Creates a new instance of `Rocket`.
 pub fn ignite() -> Rocket {
@@ -5,6 +5,7 @@ use crate::Rocket;
 use crate::local::LocalRequest;
 use crate::http::{Method, private::CookieJar};
 use crate::error::LaunchError;
+use crate::config::Value;
 
 /// A structure to construct requests for local dispatching.
 ///
@@ -144,6 +145,54 @@ impl Client {
         Client::_new(rocket, false)
     }
 
+    /// Like [`new()`](Client::new()), but first merges `extras` into
+    /// `rocket`'s configuration, as if each `(name, value)` pair were an
+    /// entry in a config file. Recognized names (`address`, `port`,
+    /// `limits`, and so on) update the corresponding `Config` field;
+    /// anything else becomes an extra. This is the easiest way to exercise
+    /// limit- or config-dependent behavior without hand-building a whole
+    /// `Config`.
+    ///
+    /// Note that fairings' `on_attach` callbacks run eagerly, as soon as
+    /// [`Rocket::attach()`] is called, so overrides applied here can't
+    /// retroactively change what an already-attached fairing saw; they only
+    /// affect the `Config` Rocket ignites with.
+    ///
+    /// # Errors
+    ///
+    /// If launching the `Rocket` instance would fail, excepting network errors,
+    /// the `LaunchError` is returned.
+    ///
+    /// # Panics
+    ///
+    /// Panics if any `(name, value)` pair isn't a valid override for `name`,
+    /// for instance a `limits` value that isn't a table of integers.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use rocket::local::Client;
+    /// use rocket::config::{Value, Table};
+    ///
+    /// let mut limits = Table::new();
+    /// limits.insert("forms".into(), 1024.into());
+    ///
+    /// let overrides = vec![("limits", Value::from(limits))];
+    /// let client = Client::tracked_with(rocket::ignite(), overrides);
+    /// assert!(client.is_ok());
+    /// ```
+    pub fn tracked_with<K, V, E>(mut rocket: Rocket, extras: E) -> Result<Client, LaunchError>
+        where K: AsRef<str>, V: Into<Value>, E: IntoIterator<Item = (K, V)>
+    {
+        for (name, value) in extras {
+            let name = name.as_ref();
+            rocket.config.set_raw(name, &value.into())
+                .unwrap_or_else(|e| panic!("invalid config override for `{}`: {}", name, e));
+        }
+
+        Client::new(rocket)
+    }
+
     /// Returns the instance of `Rocket` this client is creating requests for.
     ///
     /// # Example
@@ -349,3 +398,108 @@ mod test {
         assert_sync::<Client>();
     }
 }
+
+#[cfg(test)]
+mod tracked_with_test {
+    use super::Client;
+    use crate::{post, routes, FromForm};
+    use crate::request::Form;
+    use crate::http::ContentType;
+    use crate::config::{Value, Table};
+
+    #[derive(FromForm)]
+    struct Pad {
+        value: String,
+    }
+
+    #[post("/", data = "<form>")]
+    fn accept(form: Form<Pad>) -> String {
+        form.value.clone()
+    }
+
+    fn rocket() -> crate::Rocket {
+        crate::ignite().mount("/", routes![accept])
+    }
+
+    fn body() -> String {
+        // One field comfortably under the default 32 KiB forms limit, but
+        // over a 1 KiB override.
+        format!("value={}", "a".repeat(2000))
+    }
+
+    #[test]
+    fn default_limit_accepts_the_form() {
+        let client = Client::new(rocket()).expect("valid rocket");
+        let response = client.post("/")
+            .header(ContentType::Form)
+            .body(body())
+            .dispatch();
+
+        assert_eq!(response.status(), crate::http::Status::Ok);
+    }
+
+    #[test]
+    fn overridden_limit_rejects_the_form() {
+        let mut limits = Table::new();
+        limits.insert("forms".into(), 1024.into());
+
+        let overrides = vec![("limits", Value::from(limits))];
+        let client = Client::tracked_with(rocket(), overrides).expect("valid rocket");
+        let response = client.post("/")
+            .header(ContentType::Form)
+            .body(body())
+            .dispatch();
+
+        assert_ne!(response.status(), crate::http::Status::Ok);
+    }
+}
+
+#[cfg(test)]
+mod cached_guard_test {
+    use super::Client;
+    use std::sync::atomic::{AtomicUsize, Ordering};
+
+    use crate::{get, routes};
+    use crate::request::{Cached, FromRequest, Outcome};
+    use crate::outcome::Outcome::Success;
+    use crate::State;
+
+    #[derive(Clone)]
+    struct Counted;
+
+    impl<'a, 'r> FromRequest<'a, 'r> for Counted {
+        type Error = std::convert::Infallible;
+
+        fn from_request(request: &'a crate::Request<'r>) -> Outcome<Self, Self::Error> {
+            let counter = request.guard::<State<'_, AtomicUsize>>()
+                .expect("managed AtomicUsize");
+
+            counter.fetch_add(1, Ordering::SeqCst);
+            Success(Counted)
+        }
+    }
+
+    #[get("/")]
+    fn double_guard(_a: Cached<Counted>, _b: Cached<Counted>) -> String {
+        "ok".into()
+    }
+
+    fn rocket() -> crate::Rocket {
+        crate::ignite()
+            .manage(AtomicUsize::new(0))
+            .mount("/", routes![double_guard])
+    }
+
+    #[test]
+    fn cached_guard_runs_once_for_two_parameters() {
+        let client = Client::new(rocket()).expect("valid rocket");
+        let response = client.get("/").dispatch();
+        assert_eq!(response.status(), crate::http::Status::Ok);
+
+        let count = client.rocket().state::<AtomicUsize>()
+            .expect("managed AtomicUsize")
+            .load(Ordering::SeqCst);
+
+        assert_eq!(count, 1);
+    }
+}
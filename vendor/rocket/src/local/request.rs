@@ -219,6 +219,29 @@ impl<'c> LocalRequest<'c> {
         self
     }
 
+    /// Set the local address of this request: the address of the server
+    /// interface the request is considered to have been received on.
+    ///
+    /// This is useful for testing handlers whose behavior depends on which
+    /// interface or port they were hit on, such as in a multi-bind setup.
+    ///
+    /// # Examples
+    ///
+    /// Set the local address to "127.0.0.1:8000":
+    ///
+    /// ```rust
+    /// use rocket::local::Client;
+    ///
+    /// let client = Client::new(rocket::ignite()).unwrap();
+    /// let address = "127.0.0.1:8000".parse().unwrap();
+    /// let req = client.get("/").local(address);
+    /// ```
+    #[inline]
+    pub fn local(mut self, address: SocketAddr) -> Self {
+        self.request_mut().set_local_addr(address);
+        self
+    }
+
     /// Add a cookie to this request.
     ///
     /// # Examples
@@ -291,6 +314,32 @@ impl<'c> LocalRequest<'c> {
         self
     }
 
+    /// Add a [signed cookie] to this request.
+    ///
+    /// This method is only available when the `private-cookies` feature is
+    /// enabled.
+    ///
+    /// [signed cookie]: crate::http::Cookies::add_signed()
+    ///
+    /// # Examples
+    ///
+    /// Add `locale` as a signed cookie:
+    ///
+    /// ```rust
+    /// use rocket::local::Client;
+    /// use rocket::http::Cookie;
+    ///
+    /// let client = Client::new(rocket::ignite()).unwrap();
+    /// # #[allow(unused_variables)]
+    /// let req = client.get("/").signed_cookie(Cookie::new("locale", "en-US"));
+    /// ```
+    #[inline]
+    #[cfg(feature = "private-cookies")]
+    pub fn signed_cookie(self, cookie: Cookie<'static>) -> Self {
+        self.request.cookies().add_original_signed(cookie);
+        self
+    }
+
     // TODO: For CGI, we want to be able to set the body to be stdin without
     // actually reading everything into a vector. Can we allow that here while
     // keeping the simplicity? Looks like it would require us to reintroduce a
@@ -337,6 +386,146 @@ impl<'c> LocalRequest<'c> {
         self.data = body.as_ref().into();
     }
 
+    /// Appends `bytes` to the body (data) of the request without consuming
+    /// `self`, unlike [`set_body`], which replaces it.
+    ///
+    /// This is useful for assembling a body out of several fragments, as
+    /// when simulating a streamed upload.
+    ///
+    /// [`set_body`]: #method.set_body
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use rocket::local::Client;
+    ///
+    /// let client = Client::new(rocket::ignite()).unwrap();
+    /// let mut req = client.post("/");
+    /// req.append_body(b"hello, ");
+    /// req.append_body(b"world!");
+    /// ```
+    #[inline]
+    pub fn append_body(&mut self, bytes: &[u8]) {
+        self.data.extend_from_slice(bytes);
+    }
+
+    /// Appends `bytes` to the body (data) of the request, returning `self`.
+    ///
+    /// Unlike [`body`], which replaces the body, this extends it, so it can
+    /// be chained to assemble a body out of several fragments.
+    ///
+    /// [`body`]: #method.body
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use rocket::local::Client;
+    ///
+    /// let client = Client::new(rocket::ignite()).unwrap();
+    /// # #[allow(unused_variables)]
+    /// let req = client.post("/")
+    ///     .body_bytes(b"hello, ")
+    ///     .body_bytes(b"world!");
+    /// ```
+    #[inline]
+    pub fn body_bytes(mut self, bytes: &[u8]) -> Self {
+        self.append_body(bytes);
+        self
+    }
+
+    /// Serializes `value` to JSON and sets it as the body of the request,
+    /// also setting the `Content-Type` header to `ContentType::JSON`. Only
+    /// available when the `json` feature is enabled.
+    ///
+    /// # Panics
+    ///
+    /// Panics if serializing `value` fails. This is only appropriate for
+    /// tests, where a serialization failure is almost always a bug in the
+    /// test itself.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # #[cfg(feature = "json")] fn test() {
+    /// use rocket::local::Client;
+    /// use serde_json::json;
+    ///
+    /// let client = Client::new(rocket::ignite()).unwrap();
+    /// # #[allow(unused_variables)]
+    /// let req = client.post("/").json(&json!({ "key": "value" }));
+    /// # }
+    /// ```
+    #[cfg(feature = "json")]
+    pub fn json<T: serde::Serialize>(mut self, value: &T) -> Self {
+        self.data = serde_json::to_vec(value)
+            .unwrap_or_else(|e| panic!("LocalRequest::json(): failed to serialize value: {}", e));
+
+        self.header(crate::http::ContentType::JSON)
+    }
+
+    /// Renders `value` as a urlencoded form and sets it as the body of the
+    /// request, also setting the `Content-Type` header to
+    /// `ContentType::Form`.
+    ///
+    /// `value` is rendered via its [`UriDisplay<Query>`] implementation, the
+    /// same trait implemented by types deriving `UriDisplayQuery`.
+    ///
+    /// [`UriDisplay<Query>`]: crate::http::uri::UriDisplay
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use rocket::local::Client;
+    /// use rocket::http::uri::Query;
+    ///
+    /// #[derive(UriDisplayQuery)]
+    /// struct Login<'a> {
+    ///     username: &'a str,
+    ///     password: &'a str,
+    /// }
+    ///
+    /// let client = Client::new(rocket::ignite()).unwrap();
+    /// # #[allow(unused_variables)]
+    /// let req = client.post("/login")
+    ///     .form(&Login { username: "sb", password: "hunter2" });
+    /// ```
+    pub fn form<T: crate::http::uri::UriDisplay<crate::http::uri::Query>>(
+        mut self,
+        value: &T
+    ) -> Self {
+        let value = value as &dyn crate::http::uri::UriDisplay<crate::http::uri::Query>;
+        self.data = value.to_string().into_bytes();
+        self.header(crate::http::ContentType::Form)
+    }
+
+    /// Begins building a `multipart/form-data` body for this request. Add
+    /// text and file parts via [`MultipartRequest::text()`] and
+    /// [`MultipartRequest::file()`], then call
+    /// [`MultipartRequest::finish()`] to serialize the accumulated parts into
+    /// the body, set the `Content-Type` header, and get the `LocalRequest`
+    /// back. Because parts can only be added to the returned
+    /// `MultipartRequest`, not to `self` directly, it's not possible to add
+    /// a part after the request has been dispatched.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use rocket::local::Client;
+    /// use rocket::http::ContentType;
+    ///
+    /// let client = Client::new(rocket::ignite()).unwrap();
+    /// # #[allow(unused_variables)]
+    /// let req = client.post("/upload")
+    ///     .multipart()
+    ///     .text("title", "my photo")
+    ///     .file("photo", "me.png", ContentType::PNG, vec![0u8; 4])
+    ///     .finish();
+    /// ```
+    #[inline]
+    pub fn multipart(self) -> MultipartRequest<'c> {
+        MultipartRequest { request: self, boundary: random_boundary(), parts: vec![] }
+    }
+
     /// Dispatches the request, returning the response.
     ///
     /// This method consumes `self` and is the preferred mechanism for
@@ -389,6 +578,62 @@ impl<'c> LocalRequest<'c> {
         LocalRequest::_dispatch(self.client, req, rc_req, &self.uri, data)
     }
 
+    /// Dispatches the request, then continues to dispatch new requests
+    /// against any `3xx` response's `Location` header, up to `max` times.
+    ///
+    /// A `303 See Other` switches the method of the next request to `GET`;
+    /// any other redirection status repeats the request with the same
+    /// method. Each followed request is freshly built, so neither carries a
+    /// body nor copies headers set on the original request; cookies are
+    /// still replayed, since [`Client`] tracks those independently. A
+    /// `Location` that isn't a relative origin URI (for instance, an
+    /// absolute URI pointing at an external host) is not followed; the
+    /// response containing it is returned as-is. The chain of URIs visited
+    /// before the final response is available via
+    /// [`LocalResponse::previous_uris()`].
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use rocket::local::Client;
+    ///
+    /// let client = Client::new(rocket::ignite()).expect("valid rocket");
+    /// let response = client.get("/redirecting/path").follow_redirects(5);
+    /// ```
+    pub fn follow_redirects(self, max: usize) -> LocalResponse<'c> {
+        let client = self.client;
+        let mut method = self.request.method();
+        let mut response = self.dispatch();
+        let mut previous = vec![];
+
+        for _ in 0..max {
+            if !response.status().class().is_redirection() {
+                break;
+            }
+
+            let location = match response.headers().get_one("Location") {
+                Some(location) => location.to_string(),
+                None => break,
+            };
+
+            let next_uri = match crate::http::uri::Uri::parse(&location) {
+                Ok(crate::http::uri::Uri::Origin(origin)) => origin.into_owned(),
+                _ => break,
+            };
+
+            previous.push(response.uri().clone().into_owned());
+
+            if response.status() == Status::SeeOther {
+                method = Method::Get;
+            }
+
+            response = client.req(method, next_uri.to_string()).dispatch();
+        }
+
+        response.previous = previous;
+        response
+    }
+
     // Performs the actual dispatch.
     fn _dispatch(
         client: &'c Client,
@@ -404,11 +649,12 @@ impl<'c> LocalRequest<'c> {
         } else {
             error!("Malformed request URI: {}", uri);
             let res = client.rocket().handle_error(Status::BadRequest, request);
-            return LocalResponse { _request: owned_request, response: res };
+            return LocalResponse { _request: owned_request, response: res, previous: vec![] };
         }
 
         // Actually dispatch the request.
-        let response = client.rocket().dispatch(request, Data::local(data));
+        let peek_cap = crate::data::peek_cap(&client.rocket().config.limits);
+        let response = client.rocket().dispatch(request, Data::local(data, peek_cap));
 
         // If the client is tracking cookies, updates the internal cookie jar
         // with the changes reflected by `response`.
@@ -429,7 +675,8 @@ impl<'c> LocalRequest<'c> {
 
         LocalResponse {
             _request: owned_request,
-            response: response
+            response: response,
+            previous: vec![]
         }
     }
 }
@@ -450,6 +697,7 @@ impl fmt::Debug for LocalRequest<'_> {
 pub struct LocalResponse<'c> {
     _request: Rc<Request<'c>>,
     response: Response<'c>,
+    previous: Vec<Origin<'static>>,
 }
 
 impl<'c> Deref for LocalResponse<'c> {
@@ -468,6 +716,108 @@ impl<'c> DerefMut for LocalResponse<'c> {
     }
 }
 
+impl<'c> LocalResponse<'c> {
+    /// Returns the URI of the request that produced this response, exactly
+    /// as Rocket saw it when routing, including any mutations (such as a
+    /// `_method` form field overriding the HTTP method) made during
+    /// preprocessing.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use rocket::local::Client;
+    ///
+    /// let client = Client::new(rocket::ignite()).expect("valid rocket");
+    /// let response = client.get("/hello%20world").dispatch();
+    /// assert_eq!(response.uri().path(), "/hello%20world");
+    /// ```
+    #[inline]
+    pub fn uri(&self) -> &Origin<'c> {
+        self._request.uri()
+    }
+
+    /// Reads and deserializes the response body as JSON into a `T`. Returns
+    /// `None` if reading the body or deserializing it as a `T` fails. Only
+    /// available when the `json` feature is enabled.
+    ///
+    /// The entire body is buffered with no cap; for a response that might
+    /// stream an unbounded amount of data, use
+    /// [`into_json_with_limit()`](LocalResponse::into_json_with_limit())
+    /// instead.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// # #[cfg(feature = "json")] fn test() {
+    /// use rocket::local::Client;
+    /// use serde::Deserialize;
+    ///
+    /// #[derive(Deserialize)]
+    /// struct Message {
+    ///     key: String,
+    /// }
+    ///
+    /// let client = Client::new(rocket::ignite()).unwrap();
+    /// let response = client.get("/").dispatch();
+    /// let message: Option<Message> = response.into_json();
+    /// # }
+    /// ```
+    #[cfg(feature = "json")]
+    #[inline]
+    pub fn into_json<T: serde::de::DeserializeOwned>(self) -> Option<T> {
+        self.into_json_with_limit(u64::max_value())
+    }
+
+    /// Like [`into_json()`](LocalResponse::into_json()), but reads at most
+    /// `limit` bytes of the body, returning `None` rather than buffering the
+    /// rest if the body is longer. Useful in tests that dispatch against a
+    /// route that streams data, where buffering the whole body would hang or
+    /// exhaust memory.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// # #[cfg(feature = "json")] fn test() {
+    /// use rocket::local::Client;
+    /// use serde::Deserialize;
+    ///
+    /// #[derive(Deserialize)]
+    /// struct Message {
+    ///     key: String,
+    /// }
+    ///
+    /// let client = Client::new(rocket::ignite()).unwrap();
+    /// let response = client.get("/").dispatch();
+    /// let message: Option<Message> = response.into_json_with_limit(1024);
+    /// # }
+    /// ```
+    #[cfg(feature = "json")]
+    pub fn into_json_with_limit<T: serde::de::DeserializeOwned>(mut self, limit: u64) -> Option<T> {
+        let body = self.response.take_bytes_with_limit(limit)?;
+        serde_json::from_slice(&body).ok()
+    }
+
+    /// Returns the URIs visited before this response, in the order they
+    /// were visited, when this response was produced by
+    /// [`LocalRequest::follow_redirects()`]. Empty otherwise.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use rocket::local::Client;
+    ///
+    /// let client = Client::new(rocket::ignite()).expect("valid rocket");
+    /// let response = client.get("/redirecting/path").follow_redirects(5);
+    /// for uri in response.previous_uris() {
+    ///     println!("visited {}", uri);
+    /// }
+    /// ```
+    #[inline]
+    pub fn previous_uris(&self) -> &[Origin<'static>] {
+        &self.previous
+    }
+}
+
 impl fmt::Debug for LocalResponse<'_> {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         fmt::Debug::fmt(&self.response, f)
@@ -486,6 +836,232 @@ impl<'c> Clone for LocalRequest<'c> {
     }
 }
 
+enum MultipartPart {
+    Text { name: String, value: String },
+    File { name: String, filename: String, content_type: crate::http::ContentType, data: Vec<u8> },
+}
+
+/// A builder for a `multipart/form-data` request body, created via
+/// [`LocalRequest::multipart()`].
+pub struct MultipartRequest<'c> {
+    request: LocalRequest<'c>,
+    boundary: String,
+    parts: Vec<MultipartPart>,
+}
+
+impl<'c> MultipartRequest<'c> {
+    /// Adds a text field named `name` with value `value`.
+    #[inline]
+    pub fn text<N: Into<String>, V: Into<String>>(mut self, name: N, value: V) -> Self {
+        self.parts.push(MultipartPart::Text { name: name.into(), value: value.into() });
+        self
+    }
+
+    /// Adds a file field named `name`, reported to the server with file name
+    /// `filename` and content type `content_type`, with contents `data`.
+    #[inline]
+    pub fn file<N, F, D>(
+        mut self,
+        name: N,
+        filename: F,
+        content_type: crate::http::ContentType,
+        data: D
+    ) -> Self
+        where N: Into<String>, F: Into<String>, D: Into<Vec<u8>>
+    {
+        self.parts.push(MultipartPart::File {
+            name: name.into(),
+            filename: filename.into(),
+            content_type,
+            data: data.into(),
+        });
+
+        self
+    }
+
+    /// Serializes the accumulated parts into a `multipart/form-data` body,
+    /// sets the request's `Content-Type` header to match, and returns the
+    /// underlying [`LocalRequest`], ready to dispatch.
+    pub fn finish(self) -> LocalRequest<'c> {
+        let mut body = Vec::new();
+        for part in &self.parts {
+            body.extend_from_slice(b"--");
+            body.extend_from_slice(self.boundary.as_bytes());
+            body.extend_from_slice(b"\r\n");
+
+            match part {
+                MultipartPart::Text { name, value } => {
+                    let disposition = format!(
+                        "Content-Disposition: form-data; name=\"{}\"\r\n\r\n",
+                        quote_escape(name)
+                    );
+
+                    body.extend_from_slice(disposition.as_bytes());
+                    body.extend_from_slice(value.as_bytes());
+                }
+                MultipartPart::File { name, filename, content_type, data } => {
+                    let disposition = format!(
+                        "Content-Disposition: form-data; name=\"{}\"; filename=\"{}\"\r\n",
+                        quote_escape(name), quote_escape(filename)
+                    );
+
+                    body.extend_from_slice(disposition.as_bytes());
+                    body.extend_from_slice(format!("Content-Type: {}\r\n\r\n", content_type).as_bytes());
+                    body.extend_from_slice(data);
+                }
+            }
+
+            body.extend_from_slice(b"\r\n");
+        }
+
+        body.extend_from_slice(b"--");
+        body.extend_from_slice(self.boundary.as_bytes());
+        body.extend_from_slice(b"--\r\n");
+
+        let content_type = crate::http::ContentType::with_params(
+            "multipart", "form-data", ("boundary", self.boundary)
+        );
+
+        self.request.body_bytes(&body).header(content_type)
+    }
+}
+
+/// Escapes `value` for use inside a `Content-Disposition` quoted-string, per
+/// RFC 7578 §4.2: backslashes and double quotes are backslash-escaped, and
+/// the otherwise-disallowed CR/LF are percent-encoded so a crafted filename
+/// can't break out of the quoted string or inject extra header lines.
+fn quote_escape(value: &str) -> String {
+    value.replace('\\', "\\\\")
+        .replace('"', "\\\"")
+        .replace('\r', "%0D")
+        .replace('\n', "%0A")
+}
+
+/// Generates a boundary that's exceedingly unlikely to collide with any
+/// multipart part's contents. This doesn't need to be cryptographically
+/// random, just unique enough across calls within a test process.
+fn random_boundary() -> String {
+    use std::collections::hash_map::DefaultHasher;
+    use std::hash::{Hash, Hasher};
+    use std::sync::atomic::{AtomicU64, Ordering};
+    use std::time::{SystemTime, UNIX_EPOCH};
+
+    static COUNTER: AtomicU64 = AtomicU64::new(0);
+
+    let mut hasher = DefaultHasher::new();
+    SystemTime::now().duration_since(UNIX_EPOCH).unwrap().as_nanos().hash(&mut hasher);
+    COUNTER.fetch_add(1, Ordering::Relaxed).hash(&mut hasher);
+    std::thread::current().id().hash(&mut hasher);
+
+    format!("--------------------------RocketFormBoundary{:016x}", hasher.finish())
+}
+
+#[cfg(test)]
+mod multipart_tests {
+    use super::*;
+    use crate::{Route, Rocket};
+    use crate::http::{ContentType, Method};
+    use crate::handler::{self, Handler};
+
+    #[derive(Clone)]
+    struct Peek;
+
+    impl Handler for Peek {
+        fn handle<'r>(&self, req: &'r Request<'_>, data: Data) -> handler::Outcome<'r> {
+            handler::Outcome::from(req, data.peek().to_vec())
+        }
+    }
+
+    fn rocket() -> Rocket {
+        crate::ignite().mount("/", vec![Route::new(Method::Post, "/", Peek)])
+    }
+
+    #[test]
+    fn multipart_body_has_expected_boundary_structure() {
+        let client = Client::new(rocket()).expect("valid rocket");
+        let mut response = client.post("/")
+            .multipart()
+            .text("title", "a summer day")
+            .file("photo", "me.png", ContentType::PNG, vec![1, 2, 3, 4])
+            .finish()
+            .dispatch();
+
+        let body = response.body_string().expect("response body");
+        let boundary = body.lines().next().expect("boundary line");
+        assert!(boundary.starts_with("--"));
+
+        assert!(body.contains(&format!("{}\r\nContent-Disposition: form-data; name=\"title\"\r\n\r\na summer day", boundary)));
+        assert!(body.contains("Content-Disposition: form-data; name=\"photo\"; filename=\"me.png\""));
+        assert!(body.contains("Content-Type: image/png"));
+        assert!(body.ends_with(&format!("{}--\r\n", boundary)));
+    }
+
+    #[test]
+    fn quotes_and_newlines_in_filenames_are_escaped() {
+        let client = Client::new(rocket()).expect("valid rocket");
+        let mut response = client.post("/")
+            .multipart()
+            .file("photo", "quote\".txt", ContentType::Plain, vec![])
+            .finish()
+            .dispatch();
+
+        let body = response.body_string().expect("response body");
+        assert!(body.contains("filename=\"quote\\\".txt\""));
+        assert!(!body.contains("filename=\"quote\".txt\""));
+    }
+}
+
+#[cfg(all(test, feature = "json"))]
+mod json_tests {
+    use super::*;
+    use crate::{Route, Rocket};
+    use crate::http::Method;
+    use crate::handler::{self, Handler};
+    use serde::Deserialize;
+
+    #[derive(Deserialize, Debug, PartialEq)]
+    struct Message {
+        key: String,
+    }
+
+    #[derive(Clone)]
+    struct Respond(&'static str);
+
+    impl Handler for Respond {
+        fn handle<'r>(&self, req: &'r Request<'_>, _: Data) -> handler::Outcome<'r> {
+            handler::Outcome::from(req, self.0)
+        }
+    }
+
+    fn rocket(body: &'static str) -> Rocket {
+        crate::ignite().mount("/", vec![Route::new(Method::Get, "/", Respond(body))])
+    }
+
+    #[test]
+    fn into_json_round_trips_a_struct() {
+        let client = Client::new(rocket(r#"{"key":"value"}"#)).expect("valid rocket");
+        let response = client.get("/").dispatch();
+        let message: Option<Message> = response.into_json();
+        assert_eq!(message, Some(Message { key: "value".into() }));
+    }
+
+    #[test]
+    fn into_json_is_none_on_parse_failure() {
+        let client = Client::new(rocket("not json")).expect("valid rocket");
+        let response = client.get("/").dispatch();
+        let message: Option<Message> = response.into_json();
+        assert_eq!(message, None);
+    }
+
+    #[test]
+    fn into_json_with_limit_is_none_when_body_exceeds_limit() {
+        let client = Client::new(rocket(r#"{"key":"value"}"#)).expect("valid rocket");
+        let response = client.get("/").dispatch();
+        let message: Option<Message> = response.into_json_with_limit(5);
+        assert_eq!(message, None);
+    }
+}
+
 // #[cfg(test)]
 mod tests {
     // Someday...
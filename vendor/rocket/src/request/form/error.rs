@@ -7,17 +7,27 @@ use crate::http::RawStr;
 /// If multiple errors occur while parsing a form, the first error in the
 /// following precedence, from highest to lowest, is returned:
 ///
-///   * `BadValue` or `Unknown` in incoming form string field order
+///   * `BadValue`, `Unknown`, or `UnknownFields` in incoming form string
+///     field order
 ///   * `Missing` in lexical field order
-#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
 pub enum FormParseError<'f> {
     /// The field named `.0` with value `.1` failed to parse or validate.
     BadValue(&'f RawStr, &'f RawStr),
     /// The parse was strict and the field named `.0` with value `.1` appeared
     /// in the incoming form string but was unexpected.
     ///
-    /// This error cannot occur when parsing is lenient.
+    /// This error cannot occur when parsing is lenient, or when the deriving
+    /// struct or enum is annotated with `#[form(collect_unknown)]`.
     Unknown(&'f RawStr, &'f RawStr),
+    /// The parse was strict, the deriving struct or enum was annotated with
+    /// `#[form(collect_unknown)]`, and the name/value pairs in `.0` appeared
+    /// in the incoming form string but were unexpected.
+    ///
+    /// This error cannot occur when parsing is lenient, or without
+    /// `#[form(collect_unknown)]`, in which case the first unexpected field
+    /// is reported via `Unknown` instead.
+    UnknownFields(Vec<(&'f RawStr, &'f RawStr)>),
     /// The field named `.0` was expected but is missing in the incoming form.
     Missing(&'f RawStr),
 }
@@ -29,9 +39,17 @@ pub enum FormDataError<'f, E> {
     /// An I/O error occurred while reading reading the data stream. This can
     /// also mean that the form contained invalid UTF-8.
     Io(io::Error),
+    /// The form data exceeded the configured `limits.forms` (or, for a
+    /// multipart body, `limits.data-form`) size and was truncated rather
+    /// than fully read.
+    TooLarge,
     /// The form string (in `.0`) is malformed and was unable to be parsed as
-    /// HTTP `application/x-www-form-urlencoded` data.
-    Malformed(&'f str),
+    /// HTTP `application/x-www-form-urlencoded` data. The byte offset in
+    /// `.0` at which tokenization broke down is in `.1`.
+    Malformed(&'f str, usize),
+    /// A `multipart/form-data` part named `.0` included a `filename`,
+    /// indicating a file upload, which is not currently supported.
+    MultipartFile(String),
     /// The form string (in `.1`) failed to parse as the intended structure. The
     /// error type in `.0` contains further details.
     Parse(E, &'f str)
@@ -64,7 +82,12 @@ pub enum FormDataError<'f, E> {
 ///     match sink {
 ///         Ok(form) => form.into_inner().value,
 ///         Err(FormDataError::Io(_)) => "I/O error".into(),
-///         Err(FormDataError::Malformed(f)) | Err(FormDataError::Parse(_, f)) => {
+///         Err(FormDataError::TooLarge) => "form data too large".into(),
+///         Err(FormDataError::MultipartFile(field)) => format!("file upload in '{}'", field),
+///         Err(FormDataError::Malformed(f, i)) => {
+///             format!("invalid form input at byte {}: {}", i, f)
+///         }
+///         Err(FormDataError::Parse(_, f)) => {
 ///             format!("invalid form input: {}", f)
 ///         }
 ///     }
@@ -1,7 +1,7 @@
 use std::ops::Deref;
 
 use crate::outcome::Outcome::*;
-use crate::request::{Request, form::{FromForm, FormItems, FormDataError}};
+use crate::request::{Request, form::{FromForm, FormItems, FormDataError, multipart}};
 use crate::data::{Outcome, Transform, Transformed, Data, FromData};
 use crate::http::{Status, uri::{Query, FromUriParam}};
 
@@ -110,6 +110,18 @@ use crate::http::{Status, uri::{Query, FromUriParam}};
 /// [global.limits]
 /// forms = 524288
 /// ```
+///
+/// ## Multipart Forms
+///
+/// A `Form<T>` will also accept a `multipart/form-data` body whose parts are
+/// all simple text fields; the fields are decoded and handled exactly as
+/// they would be for an `application/x-www-form-urlencoded` body. A part
+/// that includes a `filename` in its `Content-Disposition` header, meaning
+/// it's a file upload, results in a
+/// [`FormDataError::MultipartFile`](crate::request::FormDataError::MultipartFile)
+/// error; file uploads are not supported by `Form`. The size limit for
+/// multipart bodies defaults to the same limit as `limits.forms` but can be
+/// set independently via the `limits.data-form` configuration parameter.
 #[derive(Debug)]
 pub struct Form<T>(pub T);
 
@@ -158,7 +170,7 @@ impl<'f, T: FromForm<'f>> Form<T> {
         let result = T::from_form(&mut items, strict);
         if !items.exhaust() {
             error_!("The request's form string was malformed.");
-            return Failure((Status::BadRequest, Malformed(form_str)));
+            return Failure((Status::BadRequest, Malformed(form_str, items.index())));
         }
 
         match result {
@@ -169,6 +181,72 @@ impl<'f, T: FromForm<'f>> Form<T> {
             }
         }
     }
+
+    /// Parses an already-obtained form string into a `T` strictly, without
+    /// requiring a `Request` or `Data`.
+    ///
+    /// This is identical to the parsing performed by the `Form<T>` data
+    /// guard, minus the HTTP-specific content type check and size limiting,
+    /// and is useful for unit-testing `FromForm` implementations or for
+    /// parsing form data obtained through a non-HTTP channel.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// # #![feature(proc_macro_hygiene)]
+    /// # #[macro_use] extern crate rocket;
+    /// use rocket::request::Form;
+    ///
+    /// #[derive(FromForm)]
+    /// struct MyForm {
+    ///     field: String,
+    /// }
+    ///
+    /// # fn main() {
+    /// let form = Form::<MyForm>::parse_str("field=hello").unwrap();
+    /// assert_eq!(form.field, "hello");
+    /// # }
+    /// ```
+    pub fn parse_str(form_str: &'f str) -> Result<T, FormDataError<'f, T::Error>> {
+        match Self::from_data(form_str, true) {
+            Success(v) => Ok(v),
+            Failure((_, e)) => Err(e),
+            Forward(_) => unreachable!("Form::from_data() never forwards"),
+        }
+    }
+
+    /// Parses an already-obtained form string into a `T` leniently, without
+    /// requiring a `Request` or `Data`.
+    ///
+    /// This is identical to [`parse_str()`](Form::parse_str()) except that
+    /// unexpected, extra fields in `form_str` are ignored rather than
+    /// resulting in an error. See [`LenientForm`](crate::request::LenientForm)
+    /// for further details on lenient parsing.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// # #![feature(proc_macro_hygiene)]
+    /// # #[macro_use] extern crate rocket;
+    /// use rocket::request::Form;
+    ///
+    /// #[derive(FromForm)]
+    /// struct MyForm {
+    ///     field: String,
+    /// }
+    ///
+    /// # fn main() {
+    /// let form = Form::<MyForm>::parse_str_lenient("field=hello&extra=1").unwrap();
+    /// assert_eq!(form.field, "hello");
+    /// # }
+    /// ```
+    pub fn parse_str_lenient(form_str: &'f str) -> Result<T, FormDataError<'f, T::Error>> {
+        match Self::from_data(form_str, false) {
+            Success(v) => Ok(v),
+            Failure((_, e)) => Err(e),
+            Forward(_) => unreachable!("Form::from_data() never forwards"),
+        }
+    }
 }
 
 /// Parses a `Form` from incoming form data.
@@ -195,18 +273,63 @@ impl<'f, T: FromForm<'f>> FromData<'f> for Form<T> {
     ) -> Transform<Outcome<Self::Owned, Self::Error>> {
         use std::{cmp::min, io::Read};
 
-        if !request.content_type().map_or(false, |ct| ct.is_form()) {
+        let content_type = request.content_type();
+        let is_multipart = content_type.map_or(false, |ct| ct.is_form_data());
+        if !is_multipart && !content_type.map_or(false, |ct| ct.is_form()) {
             warn_!("Form data does not have form content type.");
             return Transform::Borrowed(Forward(data))
         }
 
+        if is_multipart {
+            let boundary = content_type.and_then(|ct| {
+                ct.media_type().params().find(|(k, _)| k.eq_ignore_ascii_case("boundary"))
+            });
+
+            let boundary = match boundary {
+                Some((_, boundary)) => boundary.to_string(),
+                None => {
+                    error_!("Multipart form data is missing a boundary parameter.");
+                    return Transform::Borrowed(Failure((Status::BadRequest, FormDataError::Malformed("", 0))))
+                }
+            };
+
+            let limit = request.limits().get("data-form").unwrap_or(request.limits().forms);
+            let mut stream = data.open_limited(limit);
+            let mut body = Vec::new();
+            if let Err(e) = stream.read_to_end(&mut body) {
+                return Transform::Borrowed(Failure((Status::InternalServerError, FormDataError::Io(e))))
+            }
+
+            if stream.was_truncated() {
+                warn_!("Multipart form data exceeded the `limits.data-form` limit of {} bytes.", limit);
+                return Transform::Borrowed(Failure((Status::PayloadTooLarge, FormDataError::TooLarge)))
+            }
+
+            return match multipart::to_form_string(&body, &boundary) {
+                Ok(form_string) => Transform::Borrowed(Success(form_string)),
+                Err(multipart::MultipartError::FilePart(name)) => {
+                    warn_!("Multipart form field '{}' is a file upload, which is unsupported.", name);
+                    Transform::Borrowed(Failure((Status::UnprocessableEntity, FormDataError::MultipartFile(name))))
+                }
+                Err(multipart::MultipartError::Malformed) => {
+                    error_!("The multipart form data was malformed.");
+                    Transform::Borrowed(Failure((Status::BadRequest, FormDataError::Malformed("", 0))))
+                }
+            };
+        }
+
         let limit = request.limits().forms;
-        let mut stream = data.open().take(limit);
+        let mut stream = data.open_limited(limit);
         let mut form_string = String::with_capacity(min(4096, limit) as usize);
         if let Err(e) = stream.read_to_string(&mut form_string) {
             return Transform::Borrowed(Failure((Status::InternalServerError, FormDataError::Io(e))))
         }
 
+        if stream.was_truncated() {
+            warn_!("Form data exceeded the `limits.forms` limit of {} bytes.", limit);
+            return Transform::Borrowed(Failure((Status::PayloadTooLarge, FormDataError::TooLarge)))
+        }
+
         Transform::Borrowed(Success(form_string))
     }
 
@@ -223,3 +346,86 @@ impl<'f, A, T: FromUriParam<Query, A> + FromForm<'f>> FromUriParam<Query, A> for
         T::from_uri_param(param)
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::request::FormItems;
+
+    #[derive(Debug, PartialEq)]
+    struct Named {
+        a: String,
+        b: String,
+    }
+
+    impl<'f> FromForm<'f> for Named {
+        type Error = &'f str;
+
+        fn from_form(items: &mut FormItems<'f>, strict: bool) -> Result<Named, &'f str> {
+            let mut a = None;
+            let mut b = None;
+
+            for item in items {
+                match item.key.as_str() {
+                    "a" => a = Some(item.value.url_decode_lossy()),
+                    "b" => b = Some(item.value.url_decode_lossy()),
+                    _ if strict => return Err("unknown"),
+                    _ => continue,
+                }
+            }
+
+            match (a, b) {
+                (Some(a), Some(b)) => Ok(Named { a, b }),
+                _ => Err("missing"),
+            }
+        }
+    }
+
+    #[test]
+    fn parse_str_accepts_exact_fields() {
+        let form = Form::<Named>::parse_str("a=hello&b=world").unwrap();
+        assert_eq!(form, Named { a: "hello".into(), b: "world".into() });
+    }
+
+    #[test]
+    fn parse_str_rejects_extra_field_when_strict() {
+        let err = Form::<Named>::parse_str("a=hello&b=world&c=extra").unwrap_err();
+        match err {
+            FormDataError::Parse("unknown", _) => {}
+            _ => panic!("expected a strict Parse(\"unknown\") error, got {:?}", err),
+        }
+    }
+
+    #[test]
+    fn parse_str_lenient_ignores_extra_field() {
+        let form = Form::<Named>::parse_str_lenient("a=hello&b=world&c=extra").unwrap();
+        assert_eq!(form, Named { a: "hello".into(), b: "world".into() });
+    }
+
+    #[test]
+    fn parse_str_reports_missing_field() {
+        let err = Form::<Named>::parse_str("a=hello").unwrap_err();
+        match err {
+            FormDataError::Parse("missing", _) => {}
+            _ => panic!("expected a Parse(\"missing\") error, got {:?}", err),
+        }
+    }
+
+    #[test]
+    fn parse_str_lenient_also_reports_missing_field() {
+        let err = Form::<Named>::parse_str_lenient("a=hello").unwrap_err();
+        match err {
+            FormDataError::Parse("missing", _) => {}
+            _ => panic!("expected a Parse(\"missing\") error, got {:?}", err),
+        }
+    }
+
+    #[test]
+    fn parse_str_reports_malformed_string() {
+        let err = Form::<Named>::parse_str("a=b&==d").unwrap_err();
+        match err {
+            FormDataError::Malformed(_, 4) => {}
+            _ => panic!("expected a Malformed(_, 4) error, got {:?}", err),
+        }
+    }
+}
@@ -287,6 +287,30 @@ impl FormItems<'_> {
         self.completed()
     }
 
+    /// Returns the byte offset into the form string at which the next item
+    /// would be read. When the iterator has stopped short of `completed()`
+    /// returning `true`, this is the offset at which tokenization broke
+    /// down, and is useful for pinpointing why a form string was malformed.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use rocket::request::FormItems;
+    ///
+    /// let mut items = FormItems::from("a=b&==d");
+    /// let key_values: Vec<_> = items.by_ref().collect();
+    ///
+    /// assert_eq!(items.completed(), false);
+    /// assert_eq!(items.index(), 4);
+    /// ```
+    #[inline]
+    pub fn index(&self) -> usize {
+        match self {
+            FormItems::Raw { next_index, .. } => *next_index,
+            FormItems::Cooked { next_index, .. } => *next_index,
+        }
+    }
+
     #[inline]
     #[doc(hidden)]
     pub fn mark_complete(&mut self) {
@@ -297,6 +321,70 @@ impl FormItems<'_> {
     }
 }
 
+impl<'f> FormItems<'f> {
+    /// Converts `self` into an iterator adapter that yields [`RawFormItem`]s
+    /// rather than [`FormItem`]s.
+    ///
+    /// This is intended for advanced users writing manual `FromForm`
+    /// implementations who need to know, for each item, whether `key` or
+    /// `value` contains characters that URL decoding would change; see
+    /// [`RawFormItem::was_encoded`] for exactly what that means.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use rocket::request::FormItems;
+    ///
+    /// let form_string = "plain=hello&escaped=Hello%2C+Mark%21";
+    /// let mut items = FormItems::from(form_string).raw_items();
+    ///
+    /// let item = items.next().unwrap();
+    /// assert_eq!(item.key, "plain");
+    /// assert_eq!(item.was_encoded, false);
+    ///
+    /// let item = items.next().unwrap();
+    /// assert_eq!(item.key, "escaped");
+    /// assert_eq!(item.was_encoded, true);
+    /// ```
+    #[inline]
+    pub fn raw_items(self) -> RawItems<'f> {
+        RawItems(self)
+    }
+}
+
+/// A form item as returned by [`FormItems::raw_items()`].
+///
+/// This mirrors [`FormItem`], additionally reporting whether `raw` contains
+/// a `%` or `+` byte, either of which would be consumed by URL decoding.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub struct RawFormItem<'f> {
+    /// The full, nonempty string for the item, not including `&` delimiters.
+    pub raw: &'f RawStr,
+    /// The raw, not URL decoded key for the item.
+    pub key: &'f RawStr,
+    /// The raw, not URL decoded value for the item.
+    pub value: &'f RawStr,
+    /// Whether `raw` contains a `%` or `+` byte, meaning URL decoding `key`
+    /// or `value` would actually change one of them.
+    pub was_encoded: bool,
+}
+
+/// Iterator adapter, created by [`FormItems::raw_items()`], that yields
+/// [`RawFormItem`]s.
+#[derive(Debug)]
+pub struct RawItems<'f>(FormItems<'f>);
+
+impl<'f> Iterator for RawItems<'f> {
+    type Item = RawFormItem<'f>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.0.next().map(|item| {
+            let was_encoded = item.raw.as_bytes().iter().any(|&b| b == b'%' || b == b'+');
+            RawFormItem { raw: item.raw, key: item.key, value: item.value, was_encoded }
+        })
+    }
+}
+
 impl<'f> From<&'f RawStr> for FormItems<'f> {
     #[inline(always)]
     fn from(string: &'f RawStr) -> FormItems<'f> {
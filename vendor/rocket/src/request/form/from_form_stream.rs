@@ -0,0 +1,158 @@
+use std::io::{self, Read};
+
+use memchr::memchr;
+
+use crate::http::RawStr;
+use crate::data::LimitedDataStream;
+
+const CHUNK_SIZE: usize = 4096;
+
+/// A trait for incrementally parsing a type directly off the incoming form
+/// data stream, without ever buffering the entire body into memory.
+///
+/// This is the building block behind
+/// [`StreamedForm<T>`](crate::request::StreamedForm). Unlike
+/// [`FromForm`](crate::request::FromForm), whose `from_form()` is handed a
+/// [`FormItems`](crate::request::FormItems) over an already fully-read form
+/// string, `FromFormStream::push()` is called once per `key=value` pair as
+/// soon as it's been tokenized off the wire. This trades the zero-copy
+/// `&RawStr` borrows `FormItems` enables for the ability to parse forms far
+/// larger than you'd want to hold in memory at once.
+///
+/// `key` and `value` are the raw, percent-encoded bytes of the pair; neither
+/// is URL decoded. Use [`RawStr::url_decode()`] if you need decoded values.
+///
+/// # Example
+///
+/// ```rust
+/// use rocket::http::RawStr;
+/// use rocket::request::FromFormStream;
+///
+/// #[derive(Default)]
+/// struct RowCount(usize);
+///
+/// impl FromFormStream for RowCount {
+///     type Error = &'static str;
+///
+///     fn push(&mut self, key: &RawStr, _value: &RawStr) -> Result<(), Self::Error> {
+///         if key.is_empty() {
+///             return Err("expected a key for every row");
+///         }
+///
+///         self.0 += 1;
+///         Ok(())
+///     }
+///
+///     fn finalize(self) -> Result<Self, Self::Error> {
+///         Ok(self)
+///     }
+/// }
+/// ```
+pub trait FromFormStream: Sized {
+    /// The error returned by `push()` and `finalize()` on failure.
+    type Error;
+
+    /// Called once for each `key=value` pair read off the stream, in order.
+    fn push(&mut self, key: &RawStr, value: &RawStr) -> Result<(), Self::Error>;
+
+    /// Called once the stream has been fully read, so that implementors can
+    /// validate that everything required was seen before producing `self`.
+    fn finalize(self) -> Result<Self, Self::Error>;
+}
+
+/// The error type returned when parsing a
+/// [`StreamedForm<T>`](crate::request::StreamedForm).
+#[derive(Debug)]
+pub enum FormStreamError<E> {
+    /// The form data exceeded the `limits.forms` configuration parameter.
+    TooLarge,
+    /// An I/O error occurred while reading the incoming data stream.
+    Io(io::Error),
+    /// `T::push()` or `T::finalize()` returned an error while parsing.
+    Parse(E),
+}
+
+/// Splits a [`LimitedDataStream`] into `&`-delimited raw items, reading and
+/// discarding only as much of the stream as is needed to find the next
+/// delimiter, rather than ever holding the full body at once.
+struct Tokenizer {
+    stream: LimitedDataStream,
+    buf: Vec<u8>,
+    pos: usize,
+    done: bool,
+}
+
+impl Tokenizer {
+    fn new(stream: LimitedDataStream) -> Self {
+        Tokenizer { stream, buf: Vec::new(), pos: 0, done: false }
+    }
+
+    fn next_raw_item(&mut self) -> io::Result<Option<String>> {
+        loop {
+            if let Some(rel) = memchr(b'&', &self.buf[self.pos..]) {
+                let end = self.pos + rel;
+                let item = String::from_utf8_lossy(&self.buf[self.pos..end]).into_owned();
+                self.pos = end + 1;
+                return Ok(Some(item));
+            }
+
+            if self.done {
+                if self.pos < self.buf.len() {
+                    let item = String::from_utf8_lossy(&self.buf[self.pos..]).into_owned();
+                    self.pos = self.buf.len();
+                    return Ok(Some(item));
+                }
+
+                return Ok(None);
+            }
+
+            if self.pos > 0 {
+                self.buf.drain(..self.pos);
+                self.pos = 0;
+            }
+
+            let mut chunk = [0u8; CHUNK_SIZE];
+            let n = self.stream.read(&mut chunk)?;
+            if n == 0 {
+                self.done = true;
+            } else {
+                self.buf.extend_from_slice(&chunk[..n]);
+            }
+        }
+    }
+}
+
+pub(crate) fn parse_form_stream<T: FromFormStream>(
+    stream: LimitedDataStream,
+    mut builder: T,
+) -> Result<T, FormStreamError<T::Error>> {
+    let mut tokenizer = Tokenizer::new(stream);
+    loop {
+        let raw_item = match tokenizer.next_raw_item().map_err(FormStreamError::Io)? {
+            Some(item) => item,
+            None => break,
+        };
+
+        if raw_item.is_empty() {
+            continue;
+        }
+
+        let (key, value) = match raw_item.find('=') {
+            Some(i) => (&raw_item[..i], &raw_item[(i + 1)..]),
+            None => (raw_item.as_str(), ""),
+        };
+
+        if key.is_empty() && value.is_empty() {
+            continue;
+        }
+
+        builder.push(RawStr::from_str(key), RawStr::from_str(value))
+            .map_err(FormStreamError::Parse)?;
+    }
+
+    if tokenizer.stream.was_truncated() {
+        return Err(FormStreamError::TooLarge);
+    }
+
+    builder.finalize().map_err(FormStreamError::Parse)
+}
@@ -264,6 +264,18 @@ impl<'v, T: FromFormValue<'v>> FromFormValue<'v> for Option<T> {
     }
 }
 
+/// Accepts both the hyphenated and simple `Uuid` string forms. On failure,
+/// the error is the raw value, unchanged, for forwarding/logging purposes.
+#[cfg(feature = "uuid")]
+impl<'v> FromFormValue<'v> for uuid_::Uuid {
+    type Error = &'v RawStr;
+
+    #[inline(always)]
+    fn from_form_value(v: &'v RawStr) -> Result<Self, Self::Error> {
+        uuid_::Uuid::parse_str(v.as_str()).map_err(|_| v)
+    }
+}
+
 // // TODO: Add more useful implementations (range, regex, etc.).
 impl<'v, T: FromFormValue<'v>> FromFormValue<'v> for Result<T, T::Error> {
     type Error = std::convert::Infallible;
@@ -3,13 +3,18 @@
 mod form_items;
 mod from_form;
 mod from_form_value;
+mod from_form_stream;
 mod lenient;
-mod error;
 mod form;
+mod streamed;
+mod error;
+mod multipart;
 
-pub use self::form_items::{FormItems, FormItem};
+pub use self::form_items::{FormItems, FormItem, RawFormItem, RawItems};
 pub use self::from_form::FromForm;
 pub use self::from_form_value::FromFormValue;
+pub use self::from_form_stream::{FromFormStream, FormStreamError};
 pub use self::form::Form;
 pub use self::lenient::LenientForm;
+pub use self::streamed::StreamedForm;
 pub use self::error::{FormError, FormParseError, FormDataError};
@@ -0,0 +1,182 @@
+//! Minimal decoding of the text fields of a `multipart/form-data` body.
+//!
+//! This does not attempt to be a general-purpose MIME multipart parser. It
+//! exists so that [`Form`](super::Form) can accept simple, all-text
+//! multipart bodies in addition to `application/x-www-form-urlencoded`
+//! ones, by rewriting the text parts into an equivalent urlencoded string
+//! and handing that off to the existing [`FormItems`](super::FormItems)
+//! parsing path. Parts that look like file uploads (those with a
+//! `filename` in their `Content-Disposition` header) are rejected.
+
+/// An error encountered while decoding a `multipart/form-data` body.
+#[derive(Debug)]
+pub enum MultipartError {
+    /// The body was not validly structured `multipart/form-data`.
+    Malformed,
+    /// The part named `.0` included a `filename`, indicating a file upload,
+    /// which this parser does not support.
+    FilePart(String),
+}
+
+/// Decodes the text fields of `body`, a `multipart/form-data` body
+/// delimited by `boundary`, into an `application/x-www-form-urlencoded`
+/// equivalent string.
+pub fn to_form_string(body: &[u8], boundary: &str) -> Result<String, MultipartError> {
+    let delimiter = format!("--{}", boundary);
+    let chunks = split_on(body, delimiter.as_bytes());
+
+    // The first chunk is the preamble before the first boundary and the
+    // last is the "--" epilogue after the final boundary; neither is a part.
+    if chunks.len() < 2 {
+        return Err(MultipartError::Malformed);
+    }
+    let parts = &chunks[1..chunks.len() - 1];
+
+    let mut fields = Vec::new();
+    for part in parts {
+        let part = trim_crlf(part);
+        if part.is_empty() {
+            continue;
+        }
+
+        let header_end = find(part, b"\r\n\r\n").ok_or(MultipartError::Malformed)?;
+        let headers = std::str::from_utf8(&part[..header_end])
+            .map_err(|_| MultipartError::Malformed)?;
+        let content = &part[header_end + 4..];
+
+        let disposition = headers.lines()
+            .find(|line| line.to_ascii_lowercase().starts_with("content-disposition:"))
+            .ok_or(MultipartError::Malformed)?;
+
+        let name = find_param(disposition, "name").ok_or(MultipartError::Malformed)?;
+        if find_param(disposition, "filename").is_some() {
+            return Err(MultipartError::FilePart(name));
+        }
+
+        let value = std::str::from_utf8(content).map_err(|_| MultipartError::Malformed)?;
+        fields.push(format!("{}={}", percent_encode(&name), percent_encode(value)));
+    }
+
+    Ok(fields.join("&"))
+}
+
+/// Finds `param="value"` in a `;`-separated header like `Content-Disposition:
+/// form-data; name="field"; filename="thing.txt"` and returns `value`.
+fn find_param(header: &str, param: &str) -> Option<String> {
+    let prefix = format!("{}=\"", param);
+    for segment in header.split(';') {
+        let segment = segment.trim();
+        if segment.len() > prefix.len() && segment[..prefix.len()].eq_ignore_ascii_case(&prefix) {
+            let rest = &segment[prefix.len()..];
+            if let Some(end) = rest.find('"') {
+                return Some(rest[..end].to_string());
+            }
+        }
+    }
+
+    None
+}
+
+/// Percent-encodes `value` as an `application/x-www-form-urlencoded` value.
+fn percent_encode(value: &str) -> String {
+    let mut out = String::with_capacity(value.len());
+    for &byte in value.as_bytes() {
+        match byte {
+            b'A'..=b'Z' | b'a'..=b'z' | b'0'..=b'9' | b'-' | b'_' | b'.' | b'~' => {
+                out.push(byte as char);
+            }
+            _ => out.push_str(&format!("%{:02X}", byte)),
+        }
+    }
+
+    out
+}
+
+fn trim_crlf(mut bytes: &[u8]) -> &[u8] {
+    while bytes.starts_with(b"\r\n") {
+        bytes = &bytes[2..];
+    }
+
+    while bytes.ends_with(b"\r\n") {
+        bytes = &bytes[..bytes.len() - 2];
+    }
+
+    bytes
+}
+
+fn find(haystack: &[u8], needle: &[u8]) -> Option<usize> {
+    if needle.is_empty() || haystack.len() < needle.len() {
+        return None;
+    }
+
+    (0..=haystack.len() - needle.len()).find(|&i| &haystack[i..i + needle.len()] == needle)
+}
+
+fn split_on<'a>(haystack: &'a [u8], needle: &[u8]) -> Vec<&'a [u8]> {
+    let mut parts = vec![];
+    let mut rest = haystack;
+    while let Some(pos) = find(rest, needle) {
+        parts.push(&rest[..pos]);
+        rest = &rest[pos + needle.len()..];
+    }
+
+    parts.push(rest);
+    parts
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn decodes_simple_text_fields() {
+        let body = [
+            "--XYZ",
+            "Content-Disposition: form-data; name=\"a\"",
+            "",
+            "hello",
+            "--XYZ",
+            "Content-Disposition: form-data; name=\"b\"",
+            "",
+            "world",
+            "--XYZ--",
+            "",
+        ].join("\r\n");
+
+        let form_string = to_form_string(body.as_bytes(), "XYZ").unwrap();
+        assert_eq!(form_string, "a=hello&b=world");
+    }
+
+    #[test]
+    fn rejects_file_parts() {
+        let body = [
+            "--XYZ",
+            "Content-Disposition: form-data; name=\"upload\"; filename=\"a.txt\"",
+            "Content-Type: text/plain",
+            "",
+            "file contents",
+            "--XYZ--",
+            "",
+        ].join("\r\n");
+
+        match to_form_string(body.as_bytes(), "XYZ") {
+            Err(MultipartError::FilePart(name)) => assert_eq!(name, "upload"),
+            other => panic!("expected FilePart error, got {:?}", other.map(drop)),
+        }
+    }
+
+    #[test]
+    fn percent_encodes_special_characters() {
+        let body = [
+            "--XYZ",
+            "Content-Disposition: form-data; name=\"a\"",
+            "",
+            "x&y=z",
+            "--XYZ--",
+            "",
+        ].join("\r\n");
+
+        let form_string = to_form_string(body.as_bytes(), "XYZ").unwrap();
+        assert_eq!(form_string, "a=x%26y%3Dz");
+    }
+}
@@ -0,0 +1,205 @@
+use std::ops::Deref;
+
+use crate::outcome::Outcome::*;
+use crate::request::{Request, form::{FromFormStream, FormStreamError, from_form_stream::parse_form_stream}};
+use crate::data::{Data, FromDataSimple, Outcome};
+use crate::http::Status;
+
+/// A data guard for incrementally parsing [`FromFormStream`] types.
+///
+/// Unlike [`Form<T>`](crate::request::Form), which reads the entire incoming
+/// form body into a `String` before parsing it, `StreamedForm<T>` tokenizes
+/// the body as it arrives and feeds each `key=value` pair to `T` via
+/// [`FromFormStream::push()`], so the full form is never buffered in memory
+/// at once. This is intended for unusually large forms (bulk imports, long
+/// file metadata lists); for everything else, prefer `Form<T>`, which is
+/// simpler and gives you `#[derive(FromForm)]`.
+///
+/// # Usage
+///
+/// `T` must implement [`FromFormStream`] and [`Default`]; there is no derive
+/// for `FromFormStream`.
+///
+/// ```rust
+/// # #![feature(proc_macro_hygiene)]
+/// # #[macro_use] extern crate rocket;
+/// use rocket::http::RawStr;
+/// use rocket::request::{StreamedForm, FromFormStream};
+///
+/// #[derive(Default)]
+/// struct RowCount(usize);
+///
+/// impl FromFormStream for RowCount {
+///     type Error = &'static str;
+///
+///     fn push(&mut self, _key: &RawStr, _value: &RawStr) -> Result<(), Self::Error> {
+///         self.0 += 1;
+///         Ok(())
+///     }
+///
+///     fn finalize(self) -> Result<Self, Self::Error> {
+///         Ok(self)
+///     }
+/// }
+///
+/// #[post("/submit", data = "<form>")]
+/// fn submit(form: StreamedForm<RowCount>) -> String {
+///     form.into_inner().0.to_string()
+/// }
+/// # fn main() { }
+/// ```
+///
+/// ## Incoming Data Limits
+///
+/// A `StreamedForm` obeys the same `limits.forms` configuration parameter as
+/// `Form`, defaulting to 32KiB. Because the body is never fully buffered,
+/// raising this limit for a `StreamedForm<T>` route doesn't cost any extra
+/// memory the way it would for `Form<T>`.
+#[derive(Debug)]
+pub struct StreamedForm<T>(pub T);
+
+impl<T> StreamedForm<T> {
+    /// Consumes `self` and returns the parsed value.
+    #[inline(always)]
+    pub fn into_inner(self) -> T {
+        self.0
+    }
+}
+
+impl<T> Deref for StreamedForm<T> {
+    type Target = T;
+
+    fn deref(&self) -> &T {
+        &self.0
+    }
+}
+
+/// Parses a `StreamedForm` from incoming form data.
+///
+/// If the content type of the request data is not
+/// `application/x-www-form-urlencoded`, `Forward`s the request. Multipart
+/// forms are not supported; use [`Form`](crate::request::Form) for those. If
+/// `T::push()` or `T::finalize()` fails, a `Failure` with status
+/// `UnprocessableEntity` is returned. If the incoming stream exceeds
+/// `limits.forms`, a `Failure` with status `PayloadTooLarge` is returned.
+impl<T: FromFormStream + Default> FromDataSimple for StreamedForm<T> {
+    type Error = FormStreamError<T::Error>;
+
+    fn from_data(request: &Request<'_>, data: Data) -> Outcome<Self, Self::Error> {
+        let content_type = request.content_type();
+        if !content_type.map_or(false, |ct| ct.is_form()) {
+            warn_!("Form data does not have form content type.");
+            return Forward(data);
+        }
+
+        let limit = request.limits().forms;
+        let stream = data.open_limited(limit);
+        match parse_form_stream(stream, T::default()) {
+            Ok(value) => Success(StreamedForm(value)),
+            Err(FormStreamError::TooLarge) => {
+                warn_!("Form data exceeded the `limits.forms` limit of {} bytes.", limit);
+                Failure((Status::PayloadTooLarge, FormStreamError::TooLarge))
+            }
+            Err(FormStreamError::Io(e)) => {
+                Failure((Status::InternalServerError, FormStreamError::Io(e)))
+            }
+            Err(FormStreamError::Parse(e)) => {
+                error_!("The incoming form failed to parse.");
+                Failure((Status::UnprocessableEntity, FormStreamError::Parse(e)))
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::config::{Config, Environment, Limits};
+    use crate::http::{Method, ContentType, RawStr};
+    use crate::http::uri::Origin;
+
+    #[derive(Default, Debug, PartialEq)]
+    struct Pairs(Vec<(String, String)>);
+
+    impl FromFormStream for Pairs {
+        type Error = &'static str;
+
+        fn push(&mut self, key: &RawStr, value: &RawStr) -> Result<(), Self::Error> {
+            if key.is_empty() {
+                return Err("empty key");
+            }
+
+            self.0.push((key.url_decode_lossy(), value.url_decode_lossy()));
+            Ok(())
+        }
+
+        fn finalize(self) -> Result<Self, Self::Error> {
+            if self.0.is_empty() {
+                return Err("no fields");
+            }
+
+            Ok(self)
+        }
+    }
+
+    fn parse(body: &[u8], limits: Limits) -> Outcome<StreamedForm<Pairs>, FormStreamError<&'static str>> {
+        let config = Config::build(Environment::Development)
+            .limits(limits)
+            .finalize()
+            .expect("valid config");
+
+        let rocket = crate::custom(config);
+        let mut request = Request::new(&rocket, Method::Post, Origin::dummy());
+        request.add_header(ContentType::Form);
+        let data = Data::local(body.to_vec(), 512);
+        StreamedForm::<Pairs>::from_data(&request, data)
+    }
+
+    #[test]
+    fn parses_simple_pairs() {
+        match parse(b"a=hello&b=world", Limits::default()) {
+            Success(form) => assert_eq!(form.into_inner().0, vec![
+                ("a".into(), "hello".into()),
+                ("b".into(), "world".into()),
+            ]),
+            other => panic!("expected Success, got {:?}", other),
+        }
+    }
+
+    #[test]
+    fn decodes_percent_encoded_values_across_chunk_boundaries() {
+        // Build a body much larger than the tokenizer's internal read-chunk
+        // size to exercise carrying a partial item across chunk boundaries.
+        let mut body = String::new();
+        for i in 0..400 {
+            body.push_str(&format!("field{}=value%2C{}&", i, i));
+        }
+        body.push_str("greeting=Hello%2C+Mark%21");
+
+        match parse(body.as_bytes(), Limits::default()) {
+            Success(form) => {
+                let pairs = form.into_inner().0;
+                assert_eq!(pairs.len(), 401);
+                assert_eq!(pairs.last().unwrap(), &("greeting".into(), "Hello, Mark!".into()));
+            }
+            other => panic!("expected Success, got {:?}", other),
+        }
+    }
+
+    #[test]
+    fn reports_parse_error_from_push() {
+        match parse(b"=novalue&a=b", Limits::default()) {
+            Failure((Status::UnprocessableEntity, FormStreamError::Parse("empty key"))) => {}
+            other => panic!("expected a Parse(\"empty key\") failure, got {:?}", other),
+        }
+    }
+
+    #[test]
+    fn reports_too_large_when_limit_exceeded() {
+        let limits = Limits::default().limit("forms", 4);
+        match parse(b"a=hello&b=world", limits) {
+            Failure((Status::PayloadTooLarge, FormStreamError::TooLarge)) => {}
+            other => panic!("expected a TooLarge failure, got {:?}", other),
+        }
+    }
+}
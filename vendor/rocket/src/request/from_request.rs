@@ -447,3 +447,64 @@ impl<'a, 'r, T: FromRequest<'a, 'r>> FromRequest<'a, 'r> for Option<T> {
     }
 }
 
+/// A request guard that wraps another request guard `T`, memoizing its
+/// outcome for the duration of the request via
+/// [`Request::guard_cached()`](crate::Request::guard_cached()). Mounting the
+/// same `Cached<T>` across multiple routes, or requesting it more than once
+/// on the same route, runs `T`'s guard at most once per request. A bare `T`
+/// guard isn't memoized and runs `T`'s guard each time it's requested.
+///
+/// `Cached<T>` derefs to `T`.
+///
+/// # Example
+///
+/// ```rust
+/// # #![feature(proc_macro_hygiene)]
+/// # #[macro_use] extern crate rocket;
+/// use rocket::request::Cached;
+/// # #[derive(Clone)] struct AdminUser;
+/// # impl<'a, 'r> rocket::request::FromRequest<'a, 'r> for AdminUser {
+/// #     type Error = std::convert::Infallible;
+/// #     fn from_request(_: &'a rocket::Request<'r>) -> rocket::request::Outcome<Self, Self::Error> {
+/// #         rocket::outcome::Outcome::Success(AdminUser)
+/// #     }
+/// # }
+///
+/// #[get("/")]
+/// fn admin_panel(user: Cached<AdminUser>, _also_user: Cached<AdminUser>) { }
+/// # fn main() {}
+/// ```
+pub struct Cached<T>(T);
+
+impl<T> Cached<T> {
+    /// Consumes `self` and returns the inner `T`.
+    #[inline(always)]
+    pub fn into_inner(self) -> T {
+        self.0
+    }
+}
+
+impl<T> std::ops::Deref for Cached<T> {
+    type Target = T;
+
+    #[inline(always)]
+    fn deref(&self) -> &T {
+        &self.0
+    }
+}
+
+impl<'a, 'r, T> FromRequest<'a, 'r> for Cached<T>
+    where T: FromRequest<'a, 'r> + Clone + Send + Sync + 'static,
+          T::Error: Clone + Send + Sync + 'static
+{
+    type Error = T::Error;
+
+    fn from_request(request: &'a Request<'r>) -> Outcome<Self, Self::Error> {
+        match request.guard_cached::<T>() {
+            Success(val) => Success(Cached(val.clone())),
+            Failure((status, err)) => Failure((status, err.clone())),
+            Forward(()) => Forward(()),
+        }
+    }
+}
+
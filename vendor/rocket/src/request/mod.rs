@@ -10,16 +10,16 @@ mod query;
 #[cfg(test)]
 mod tests;
 
-#[doc(hidden)] pub use rocket_codegen::{FromForm, FromFormValue};
+#[doc(hidden)] pub use rocket_codegen::{FromForm, FromFormValue, FromRequest};
 
 pub use self::request::Request;
-pub use self::from_request::{FromRequest, Outcome};
-pub use self::param::{FromParam, FromSegments};
-pub use self::form::{FromForm, FromFormValue};
-pub use self::form::{Form, LenientForm, FormItems, FormItem};
+pub use self::from_request::{FromRequest, Outcome, Cached};
+pub use self::param::{FromParam, FromSegments, UnsafePathBuf};
+pub use self::form::{FromForm, FromFormValue, FromFormStream, FormStreamError};
+pub use self::form::{Form, LenientForm, StreamedForm, FormItems, FormItem, RawFormItem, RawItems};
 pub use self::form::{FormError, FormParseError, FormDataError};
 pub use self::state::State;
-pub use self::query::{Query, FromQuery};
+pub use self::query::{Query, FromQuery, QueryParamFailures};
 
 #[doc(inline)]
 pub use crate::response::flash::FlashMessage;
@@ -2,6 +2,7 @@ use std::str::FromStr;
 use std::path::PathBuf;
 use std::fmt::Debug;
 use std::borrow::Cow;
+use std::ops::Deref;
 
 use crate::http::{RawStr, uri::{Segments, SegmentError}};
 
@@ -281,6 +282,18 @@ impl<'a, T: FromParam<'a>> FromParam<'a> for Option<T> {
     }
 }
 
+/// Accepts both the hyphenated and simple `Uuid` string forms. On failure,
+/// the error is the raw string, unchanged, for forwarding/logging purposes.
+#[cfg(feature = "uuid")]
+impl<'a> FromParam<'a> for uuid_::Uuid {
+    type Error = &'a RawStr;
+
+    #[inline(always)]
+    fn from_param(param: &'a RawStr) -> Result<Self, Self::Error> {
+        uuid_::Uuid::parse_str(param.as_str()).map_err(|_| param)
+    }
+}
+
 /// Trait to convert _many_ dynamic path segment strings to a concrete value.
 ///
 /// This is the `..` analog to [`FromParam`], and its functionality is identical
@@ -299,6 +312,12 @@ impl<'a, T: FromParam<'a>> FromParam<'a> for Option<T> {
 /// any other segments that begin with "*" or "." are ignored.  If a
 /// percent-decoded segment results in invalid UTF8, an `Err` is returned with
 /// the `Utf8Error`.
+///
+/// On failure, `PathBuf`'s `Error` is a [`SegmentError`] identifying both the
+/// offending segment's index and the condition that was violated; route
+/// codegen logs this when the parameter fails to parse and the request is
+/// forwarded. See [`UnsafePathBuf`] for a `PathBuf`-like type that permits
+/// dotfiles while still rejecting `..`.
 pub trait FromSegments<'a>: Sized {
     /// The associated error to be returned when parsing fails.
     type Error: Debug;
@@ -337,7 +356,47 @@ impl FromSegments<'_> for PathBuf {
     type Error = SegmentError;
 
     fn from_segments(segments: Segments<'_>) -> Result<PathBuf, SegmentError> {
-        segments.into_path_buf(false)
+        segments.into_path_buf(false, false)
+    }
+}
+
+/// A [`PathBuf`] that additionally allows dotfiles (path segments starting
+/// with `.`, other than `..`) in its matched segments, for routes that
+/// intentionally serve paths such as `.well-known/acme-challenge/<token>`.
+///
+/// Unlike `PathBuf`, a `..` segment is rejected with
+/// [`SegmentError::DotDot`] rather than used to pop the previous segment:
+/// since `UnsafePathBuf` is already more permissive about dotfiles, it holds
+/// the line on traversal more strictly. All of the other safety checks
+/// `PathBuf` performs still apply.
+///
+/// As the name suggests, prefer `PathBuf` unless a route genuinely needs to
+/// serve dotfiles.
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub struct UnsafePathBuf(PathBuf);
+
+impl UnsafePathBuf {
+    /// Consumes `self` and returns the inner `PathBuf`.
+    #[inline(always)]
+    pub fn into_inner(self) -> PathBuf {
+        self.0
+    }
+}
+
+impl Deref for UnsafePathBuf {
+    type Target = PathBuf;
+
+    #[inline(always)]
+    fn deref(&self) -> &PathBuf {
+        &self.0
+    }
+}
+
+impl FromSegments<'_> for UnsafePathBuf {
+    type Error = SegmentError;
+
+    fn from_segments(segments: Segments<'_>) -> Result<UnsafePathBuf, SegmentError> {
+        segments.into_path_buf(true, true).map(UnsafePathBuf)
     }
 }
 
@@ -26,6 +26,13 @@ use crate::request::{FormItems, FormItem, Form, LenientForm, FromForm};
 /// [`FormItem`]. As such, its usage is equivalent to that of [`FormItems`], and
 /// we refer you to its documentation for further details.
 ///
+/// Items are yielded in the same order they appeared in the raw query
+/// string, skipping only those consumed by an earlier, statically-named
+/// query parameter; duplicate keys are preserved as separate items. This
+/// matters for a `FromQuery` implementation that collects into an ordered
+/// structure, such as a `Vec`, to represent a repeated, array-valued query
+/// parameter.
+///
 /// ## Example
 ///
 /// ```rust
@@ -235,3 +242,28 @@ impl<'q, T: FromQuery<'q>> FromQuery<'q> for Result<T, T::Error> {
         Ok(T::from_query(q))
     }
 }
+
+/// The `(name, raw value)` pairs of the declared query parameters that
+/// failed to parse on a request that was forwarded because of it.
+///
+/// Rocket's code generation records every failed query parameter here,
+/// via [`Request::local_cache()`](crate::Request::local_cache()), before
+/// forwarding a request whose query parameters didn't all parse
+/// successfully. A catcher or a lower-ranked route can inspect this to
+/// find out why the forward happened:
+///
+/// ```rust
+/// # #![feature(proc_macro_hygiene)]
+/// # #[macro_use] extern crate rocket;
+/// use rocket::Request;
+/// use rocket::request::QueryParamFailures;
+///
+/// #[catch(404)]
+/// fn not_found(req: &Request) -> String {
+///     let failures = req.local_cache(|| QueryParamFailures(vec![]));
+///     format!("bad query parameters: {:?}", failures.0)
+/// }
+/// # fn main() { }
+/// ```
+#[derive(Debug, Clone, Default)]
+pub struct QueryParamFailures(pub Vec<(String, String)>);
@@ -16,6 +16,8 @@ use crate::config::{Config, Limits};
 use crate::http::{hyper, uri::{Origin, Segments}};
 use crate::http::{Method, Header, HeaderMap, Cookies};
 use crate::http::{RawStr, ContentType, Accept, MediaType};
+use crate::http::{LanguageTag, Authorization, ForwardedElement};
+use crate::http::{parse_accept_language, parse_authorization, parse_forwarded};
 use crate::http::private::{Indexed, SmallVec, CookieJar};
 
 type Indices = (usize, usize);
@@ -32,6 +34,7 @@ pub struct Request<'r> {
     uri: Origin<'r>,
     headers: HeaderMap<'r>,
     remote: Option<SocketAddr>,
+    local: Option<SocketAddr>,
     pub(crate) state: RequestState<'r>,
 }
 
@@ -45,6 +48,11 @@ pub(crate) struct RequestState<'r> {
     pub cookies: RefCell<CookieJar>,
     pub accept: Storage<Option<Accept>>,
     pub content_type: Storage<Option<ContentType>>,
+    pub negotiated_format: Storage<Option<MediaType>>,
+    pub limits: Storage<Limits>,
+    pub accept_language: Storage<Vec<(LanguageTag, f32)>>,
+    pub authorization: Storage<Option<Authorization>>,
+    pub forwarded: Storage<Vec<ForwardedElement>>,
     pub cache: Rc<Container>,
 }
 
@@ -68,6 +76,7 @@ impl<'r> Request<'r> {
             uri: uri,
             headers: HeaderMap::new(),
             remote: None,
+            local: None,
             state: RequestState {
                 path_segments: SmallVec::new(),
                 query_items: None,
@@ -77,6 +86,11 @@ impl<'r> Request<'r> {
                 cookies: RefCell::new(CookieJar::new()),
                 accept: Storage::new(),
                 content_type: Storage::new(),
+                negotiated_format: Storage::new(),
+                limits: Storage::new(),
+                accept_language: Storage::new(),
+                authorization: Storage::new(),
+                forwarded: Storage::new(),
                 cache: Rc::new(Container::new()),
             }
         };
@@ -211,6 +225,52 @@ impl<'r> Request<'r> {
         self.remote = Some(address);
     }
 
+    /// Returns the local address of the server interface that received this
+    /// request if the address is known. If the address is not known, `None`
+    /// is returned.
+    ///
+    /// This is most useful for applications that bind to more than one
+    /// interface or port, where a handler's behavior depends on which one was
+    /// hit.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// # use rocket::Request;
+    /// # use rocket::http::Method;
+    /// # Request::example(Method::Get, "/uri", |request| {
+    /// assert!(request.local_addr().is_none());
+    /// # });
+    /// ```
+    #[inline(always)]
+    pub fn local_addr(&self) -> Option<SocketAddr> {
+        self.local
+    }
+
+    /// Sets the local address of `self` to `address`.
+    ///
+    /// # Example
+    ///
+    /// Set the local address to be 127.0.0.1:8000:
+    ///
+    /// ```rust
+    /// # use rocket::Request;
+    /// # use rocket::http::Method;
+    /// use std::net::{SocketAddr, IpAddr, Ipv4Addr};
+    ///
+    /// # Request::example(Method::Get, "/uri", |mut request| {
+    /// let (ip, port) = (IpAddr::V4(Ipv4Addr::new(127, 0, 0, 1)), 8000);
+    /// let localhost = SocketAddr::new(ip, port);
+    /// request.set_local_addr(localhost);
+    ///
+    /// assert_eq!(request.local_addr(), Some(localhost));
+    /// # });
+    /// ```
+    #[inline(always)]
+    pub fn set_local_addr(&mut self, address: SocketAddr) {
+        self.local = Some(address);
+    }
+
     /// Returns the IP address in the "X-Real-IP" header of the request if such
     /// a header exists and contains a valid IP address.
     ///
@@ -268,6 +328,69 @@ impl<'r> Request<'r> {
         self.real_ip().or_else(|| self.remote().map(|r| r.ip()))
     }
 
+    /// Attempts to return the client's true IP address by walking the
+    /// "X-Forwarded-For" header from its rightmost entry, skipping any
+    /// entries that fall within the [`proxies.trusted`] config ranges.
+    ///
+    /// If the immediate peer (the remote address of the TCP connection)
+    /// isn't itself a trusted proxy, or the header is absent, this falls
+    /// back to [`client_ip()`](Request::client_ip()), since an untrusted
+    /// peer can set "X-Forwarded-For" to whatever it likes.
+    ///
+    /// If an entry in the header can't be parsed as an IP address, the walk
+    /// stops there and falls back to [`client_ip()`](Request::client_ip())
+    /// rather than guessing at anything further left in the header.
+    ///
+    /// [`proxies.trusted`]: crate::config::TrustedProxies
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// # use rocket::Request;
+    /// # use rocket::http::Method;
+    /// use rocket::http::Header;
+    ///
+    /// # Request::example(Method::Get, "/uri", |mut request| {
+    /// // With no trusted proxies configured, the peer is never trusted, so
+    /// // this falls back to `client_ip()` regardless of the header.
+    /// request.set_remote("127.0.0.1:8000".parse().unwrap());
+    /// request.add_header(Header::new("X-Forwarded-For", "203.0.113.5"));
+    /// assert_eq!(request.true_client_ip(), Some("127.0.0.1".parse().unwrap()));
+    /// # });
+    /// ```
+    pub fn true_client_ip(&self) -> Option<IpAddr> {
+        let trusted = &self.state.config.proxies;
+        let peer_is_trusted = self.remote()
+            .map(|addr| trusted.contains(&addr.ip()))
+            .unwrap_or(false);
+
+        if !peer_is_trusted {
+            return self.client_ip();
+        }
+
+        let header = match self.headers().get_one("X-Forwarded-For") {
+            Some(header) => header,
+            None => return self.client_ip(),
+        };
+
+        let mut found = None;
+        for entry in header.split(',').rev() {
+            let ip = match parse_forwarded_for_entry(entry) {
+                Some(ip) => ip,
+                None => break,
+            };
+
+            if trusted.contains(&ip) {
+                continue;
+            }
+
+            found = Some(ip);
+            break;
+        }
+
+        found.or_else(|| self.client_ip())
+    }
+
     /// Returns a wrapped borrow to the cookies in `self`.
     ///
     /// [`Cookies`] implements internal mutability, so this method allows you to
@@ -290,7 +413,8 @@ impl<'r> Request<'r> {
     pub fn cookies(&self) -> Cookies<'_> {
         // FIXME: Can we do better? This is disappointing.
         match self.state.cookies.try_borrow_mut() {
-            Ok(jar) => Cookies::new(jar, self.state.config.secret_key()),
+            Ok(jar) => Cookies::new(jar, self.state.config.secret_key(),
+                self.state.config.secret_key_fallbacks(), &self.state.config.cookies),
             Err(_) => {
                 error_!("Multiple `Cookies` instances are active at once.");
                 info_!("An instance of `Cookies` must be dropped before another \
@@ -424,6 +548,82 @@ impl<'r> Request<'r> {
         }).as_ref()
     }
 
+    /// Returns the language tags in the Accept-Language header of `self`,
+    /// sorted by descending quality value. Tags with equal quality retain
+    /// their relative order from the header. If the header is missing or
+    /// entirely malformed, returns an empty `Vec`. The result is cached
+    /// after the first call to this function.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// # use rocket::Request;
+    /// # use rocket::http::Method;
+    /// # Request::example(Method::Get, "/uri", |mut request| {
+    /// request.add_header(rocket::http::Header::new("Accept-Language", "da, en;q=0.7"));
+    /// let languages: Vec<_> = request.accept_language().iter()
+    ///     .map(|(tag, _)| tag.as_str())
+    ///     .collect();
+    ///
+    /// assert_eq!(languages, vec!["da", "en"]);
+    /// # });
+    /// ```
+    #[inline(always)]
+    pub fn accept_language(&self) -> &[(LanguageTag, f32)] {
+        self.state.accept_language.get_or_set(|| {
+            match self.headers().get_one("Accept-Language") {
+                Some(header) => parse_accept_language(header),
+                None => Vec::new(),
+            }
+        })
+    }
+
+    /// Returns the credentials in the Authorization header of `self`, if the
+    /// header is present and its value is a recognized, well-formed `Basic`
+    /// or `Bearer` value. Returns `None` otherwise. The result is cached
+    /// after the first call to this function.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// # use rocket::Request;
+    /// # use rocket::http::Method;
+    /// use rocket::http::Authorization;
+    ///
+    /// # Request::example(Method::Get, "/uri", |mut request| {
+    /// request.add_header(rocket::http::Header::new("Authorization", "Bearer sometoken"));
+    /// assert_eq!(request.authorization(), Some(&Authorization::Bearer("sometoken".into())));
+    /// # });
+    /// ```
+    #[inline(always)]
+    pub fn authorization(&self) -> Option<&Authorization> {
+        self.state.authorization.get_or_set(|| {
+            let header = self.headers().get_one("Authorization")?;
+            let auth = parse_authorization(header);
+            if auth.is_none() {
+                debug!("invalid or unrecognized Authorization header");
+            }
+
+            auth
+        }).as_ref()
+    }
+
+    /// Returns the elements of the Forwarded header of `self`, as defined by
+    /// [RFC 7239]. If the header is missing or entirely malformed, returns an
+    /// empty `Vec`. The result is cached after the first call to this
+    /// function.
+    ///
+    /// [RFC 7239]: https://tools.ietf.org/html/rfc7239
+    #[inline(always)]
+    pub fn forwarded(&self) -> &[ForwardedElement] {
+        self.state.forwarded.get_or_set(|| {
+            match self.headers().get_one("Forwarded") {
+                Some(header) => parse_forwarded(header),
+                None => Vec::new(),
+            }
+        })
+    }
+
     /// Returns the media type "format" of the request.
     ///
     /// The "format" of a request is either the Content-Type, if the request
@@ -465,7 +665,42 @@ impl<'r> Request<'r> {
         }
     }
 
-    /// Returns the configured application receive limits.
+    /// Returns the media type that was negotiated between the client's
+    /// `Accept` header and the matched route's `format` parameter, when that
+    /// parameter declares more than one media type.
+    ///
+    /// Unlike [`format()`](Request::format()), which only ever reflects the
+    /// client's single most-preferred type, this picks, among the matched
+    /// route's declared media types, the one the client ranks highest by
+    /// `q` value. Returns `None` if routing hasn't completed yet, the
+    /// matched route didn't declare a `format`, or none of its media types
+    /// are acceptable to the client.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// # use rocket::Request;
+    /// use rocket::http::{Method, Accept};
+    ///
+    /// # Request::example(Method::Get, "/uri", |mut request| {
+    /// request.add_header(Accept::JSON);
+    /// assert_eq!(request.negotiated_format(), None); // no route has matched yet
+    /// # });
+    /// ```
+    pub fn negotiated_format(&self) -> Option<&MediaType> {
+        self.state.negotiated_format.get_or_set(|| {
+            let route = self.route()?;
+            if route.formats.is_empty() {
+                return None;
+            }
+
+            let accept = self.accept()?;
+            crate::router::negotiate_format(&route.formats, accept).cloned()
+        }).as_ref()
+    }
+
+    /// Returns the configured application receive limits, with the matched
+    /// route's `data_limit` override, if any, applied to `limits.forms`.
     ///
     /// # Example
     ///
@@ -476,8 +711,13 @@ impl<'r> Request<'r> {
     /// let json_limit = request.limits().get("json");
     /// # });
     /// ```
-    pub fn limits(&self) -> &'r Limits {
-        &self.state.config.limits
+    pub fn limits(&self) -> &Limits {
+        self.state.limits.get_or_set(|| {
+            match self.route().and_then(|route| route.data_limit) {
+                Some(data_limit) => self.state.config.limits.clone().limit("forms", data_limit),
+                None => self.state.config.limits.clone(),
+            }
+        })
     }
 
     /// Get the presently matched route, if any.
@@ -561,6 +801,36 @@ impl<'r> Request<'r> {
             })
     }
 
+    /// Runs `T`'s request guard, caching the resulting `Outcome` keyed by
+    /// `T`'s type so that further calls for the same `T` -- whether from
+    /// another `guard_cached::<T>()` call on this request or from a
+    /// [`Cached<T>`](crate::request::Cached) parameter -- return the cached
+    /// outcome instead of re-running `T`'s guard. Both `Success` and
+    /// `Failure` outcomes are cached, so a guard that fails, for instance
+    /// because a database lookup turns up nothing, isn't retried just
+    /// because another route parameter also depends on it.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// # use rocket::Request;
+    /// # use rocket::http::Method;
+    /// # type User = Method;
+    /// # Request::example(Method::Get, "/uri", |request| {
+    /// let outcome = request.guard_cached::<User>();
+    /// # });
+    /// ```
+    pub fn guard_cached<'a, T>(&'a self) -> Outcome<&'a T, &'a T::Error>
+        where T: FromRequest<'a, 'r> + Send + Sync + 'static,
+              T::Error: Send + Sync + 'static
+    {
+        match self.local_cache(|| T::from_request(self)) {
+            Outcome::Success(val) => Outcome::Success(val),
+            Outcome::Failure((status, err)) => Outcome::Failure((*status, err)),
+            Outcome::Forward(_) => Outcome::Forward(()),
+        }
+    }
+
     /// Retrieves and parses into `T` the 0-indexed `n`th segment from the
     /// request. Returns `None` if `n` is greater than the number of segments.
     /// Returns `Some(Err(T::Error))` if the parameter type `T` failed to be
@@ -849,6 +1119,7 @@ impl fmt::Debug for Request<'_> {
             .field("uri", &self.uri)
             .field("headers", &self.headers())
             .field("remote", &self.remote())
+            .field("local", &self.local_addr())
             .finish()
     }
 }
@@ -892,3 +1163,25 @@ fn indices(needle: &str, haystack: &str) -> (usize, usize) {
         .expect("segments inside of path/query")
         .indices()
 }
+
+/// Parses a single "X-Forwarded-For" entry, which may be a bare IPv4/IPv6
+/// address, a bracketed IPv6 address (optionally with a port), or an IPv4
+/// address with a trailing port.
+fn parse_forwarded_for_entry(entry: &str) -> Option<IpAddr> {
+    let entry = entry.trim();
+    if entry.is_empty() {
+        return None;
+    }
+
+    if entry.starts_with('[') {
+        let end = entry.find(']')?;
+        return entry[1..end].parse().ok();
+    }
+
+    if let Ok(ip) = entry.parse() {
+        return Some(ip);
+    }
+
+    let host = entry.rsplitn(2, ':').last()?;
+    host.parse().ok()
+}
@@ -0,0 +1,284 @@
+use std::io::{self, Read};
+use std::time::Duration;
+
+use crate::request::Request;
+use crate::response::{Response, Responder, Stream, FlushPolicy, DEFAULT_CHUNK_SIZE};
+use crate::http::{Status, ContentType};
+
+#[cfg(feature = "json")] use serde::Serialize;
+
+/// A single [server-sent event](https://html.spec.whatwg.org/multipage/server-sent-events.html),
+/// built up via builder methods and streamed to the client by [`EventStream`].
+///
+/// A bare event is just a `data:` payload:
+///
+/// ```rust
+/// use rocket::response::event_stream::Event;
+///
+/// let event = Event::data("hello");
+/// ```
+///
+/// The `event`, `id`, and `retry` fields are set with their like-named
+/// builder methods:
+///
+/// ```rust
+/// use rocket::response::event_stream::Event;
+///
+/// let event = Event::data("hello")
+///     .event("greeting")
+///     .id("1")
+///     .retry(15_000);
+/// ```
+///
+/// A `data` payload containing newlines is sent as one `data:` line per line
+/// of input, which is how the SSE wire format represents multi-line data.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Event {
+    data: String,
+    event: Option<String>,
+    id: Option<String>,
+    retry: Option<u64>,
+}
+
+impl Event {
+    /// Constructs an event whose `data` field is `data`. Embedded newlines
+    /// are sent as separate `data:` lines.
+    pub fn data<S: Into<String>>(data: S) -> Self {
+        Event { data: data.into(), event: None, id: None, retry: None }
+    }
+
+    /// Constructs an event whose `data` field is the JSON serialization of
+    /// `value`. Only available when the `json` feature is enabled.
+    #[cfg(feature = "json")]
+    pub fn json<T: Serialize>(value: &T) -> serde_json::Result<Self> {
+        Ok(Event::data(serde_json::to_string(value)?))
+    }
+
+    /// Sets this event's `event` field, naming the event for clients that
+    /// dispatch on it via `EventSource::addEventListener()`.
+    pub fn event<S: Into<String>>(mut self, name: S) -> Self {
+        self.event = Some(name.into());
+        self
+    }
+
+    /// Sets this event's `id` field, recorded by the client as
+    /// `EventSource`'s last event ID and echoed back via the
+    /// `Last-Event-ID` header on reconnection.
+    pub fn id<S: Into<String>>(mut self, id: S) -> Self {
+        self.id = Some(id.into());
+        self
+    }
+
+    /// Sets this event's `retry` field, in milliseconds, overriding how long
+    /// the client waits before reconnecting if the connection is dropped.
+    pub fn retry(mut self, ms: u64) -> Self {
+        self.retry = Some(ms);
+        self
+    }
+
+    fn write_to(&self, buf: &mut Vec<u8>) {
+        if let Some(ref event) = self.event {
+            buf.extend_from_slice(b"event: ");
+            buf.extend_from_slice(event.as_bytes());
+            buf.push(b'\n');
+        }
+
+        if let Some(ref id) = self.id {
+            buf.extend_from_slice(b"id: ");
+            buf.extend_from_slice(id.as_bytes());
+            buf.push(b'\n');
+        }
+
+        if let Some(retry) = self.retry {
+            buf.extend_from_slice(b"retry: ");
+            buf.extend_from_slice(retry.to_string().as_bytes());
+            buf.push(b'\n');
+        }
+
+        for line in self.data.split('\n') {
+            buf.extend_from_slice(b"data: ");
+            buf.extend_from_slice(line.as_bytes());
+            buf.push(b'\n');
+        }
+
+        buf.push(b'\n');
+    }
+}
+
+/// Streams the items of an iterator of [`Event`]s to the client as
+/// `text/event-stream`, setting `Cache-Control: no-cache` and flushing after
+/// every event rather than waiting to fill a chunk.
+///
+/// # Heartbeats
+///
+/// By default, the connection stays open only as long as `events` keeps
+/// producing items. Call [`EventStream::heartbeat()`] to additionally send a
+/// `:` comment line on an interval, which keeps proxies and idle timeouts
+/// from closing the connection while no real event is ready.
+///
+/// # Disconnection
+///
+/// Once the client disconnects, the next write to the underlying network
+/// stream fails, which aborts the response the same way any other failing
+/// [`Stream`] would; `events` is not polled again past that point.
+///
+/// # Example
+///
+/// ```rust
+/// use std::time::Duration;
+/// use rocket::response::event_stream::{EventStream, Event};
+///
+/// # #[allow(unused_variables)]
+/// let response = EventStream((0..10).map(|id| Event::data(id.to_string())))
+///     .heartbeat(Duration::from_secs(15));
+/// ```
+pub struct EventStream<I>(pub I);
+
+impl<I: IntoIterator<Item = Event>> EventStream<I> {
+    /// Sends a `:` comment line every `interval` while `events` hasn't
+    /// produced a new item, to keep the connection alive.
+    pub fn heartbeat(self, interval: Duration) -> HeartbeatEventStream<I> {
+        HeartbeatEventStream { events: self.0, interval }
+    }
+}
+
+/// An [`EventStream`] with a heartbeat interval, created by
+/// [`EventStream::heartbeat()`].
+pub struct HeartbeatEventStream<I> {
+    events: I,
+    interval: Duration,
+}
+
+impl<'r, I> Responder<'r> for EventStream<I>
+    where I: IntoIterator<Item = Event> + Send + 'static, I::IntoIter: Send + 'static
+{
+    fn respond_to(self, req: &Request<'_>) -> Result<Response<'r>, Status> {
+        let reader = EventStreamReader::new(self.0.into_iter());
+        let mut response = Stream::chunked_with(reader, DEFAULT_CHUNK_SIZE, FlushPolicy::Immediate)
+            .respond_to(req)?;
+
+        set_event_stream_headers(&mut response);
+        Ok(response)
+    }
+}
+
+impl<'r, I> Responder<'r> for HeartbeatEventStream<I>
+    where I: IntoIterator<Item = Event> + Send + 'static, I::IntoIter: Send + 'static
+{
+    fn respond_to(self, req: &Request<'_>) -> Result<Response<'r>, Status> {
+        let reader = EventStreamReader::new(self.events.into_iter());
+        let policy = FlushPolicy::Interval(self.interval, b": heartbeat\n\n".to_vec());
+        let mut response = Stream::chunked_with(reader, DEFAULT_CHUNK_SIZE, policy)
+            .respond_to(req)?;
+
+        set_event_stream_headers(&mut response);
+        Ok(response)
+    }
+}
+
+fn set_event_stream_headers(response: &mut Response<'_>) {
+    response.set_header(ContentType::new("text", "event-stream"));
+    response.set_raw_header("Cache-Control", "no-cache");
+}
+
+/// A [`Read`] adapter that lazily formats the items of an iterator of
+/// [`Event`]s as SSE wire format, one event at a time. Used by
+/// [`EventStream`] and [`HeartbeatEventStream`].
+struct EventStreamReader<I: Iterator<Item = Event>> {
+    iter: I,
+    buf: Vec<u8>,
+    pos: usize,
+    done: bool,
+}
+
+impl<I: Iterator<Item = Event>> EventStreamReader<I> {
+    fn new(iter: I) -> Self {
+        EventStreamReader { iter, buf: Vec::new(), pos: 0, done: false }
+    }
+}
+
+impl<I: Iterator<Item = Event>> Read for EventStreamReader<I> {
+    fn read(&mut self, out: &mut [u8]) -> io::Result<usize> {
+        loop {
+            if self.pos < self.buf.len() {
+                let n = std::cmp::min(out.len(), self.buf.len() - self.pos);
+                out[..n].copy_from_slice(&self.buf[self.pos..(self.pos + n)]);
+                self.pos += n;
+                return Ok(n);
+            }
+
+            if self.done {
+                return Ok(0);
+            }
+
+            match self.iter.next() {
+                Some(event) => {
+                    self.buf.clear();
+                    self.pos = 0;
+                    event.write_to(&mut self.buf);
+                }
+                None => {
+                    self.done = true;
+                    return Ok(0);
+                }
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::http::uri::Origin;
+    use crate::http::Method;
+    use crate::request::Request;
+
+    #[test]
+    fn wire_format_matches_sse_framing() {
+        let rocket = crate::ignite();
+        let request = Request::new(&rocket, Method::Get, Origin::dummy());
+
+        let events = vec![
+            Event::data("line one\nline two").event("greeting").id("1"),
+            Event::data("plain"),
+        ];
+
+        let mut response = EventStream(events).respond_to(&request).expect("response");
+        assert_eq!(response.content_type(), Some(ContentType::new("text", "event-stream")));
+        assert_eq!(response.headers().get_one("Cache-Control"), Some("no-cache"));
+
+        let body = response.body_string().expect("body");
+        assert_eq!(body, concat!(
+            "event: greeting\n",
+            "id: 1\n",
+            "data: line one\n",
+            "data: line two\n",
+            "\n",
+            "data: plain\n",
+            "\n",
+        ));
+    }
+
+    #[test]
+    fn heartbeat_emits_comment_while_idle() {
+        use std::sync::mpsc;
+
+        let rocket = crate::ignite();
+        let request = Request::new(&rocket, Method::Get, Origin::dummy());
+
+        let (tx, rx) = mpsc::channel();
+        let events = std::iter::from_fn(move || rx.recv_timeout(Duration::from_secs(1)).ok());
+
+        std::thread::spawn(move || {
+            std::thread::sleep(Duration::from_millis(30));
+            let _ = tx.send(Event::data("late"));
+        });
+
+        let stream = EventStream(events).heartbeat(Duration::from_millis(10));
+        let mut response = stream.respond_to(&request).expect("response");
+        let body = response.body_string().expect("body");
+
+        assert!(body.starts_with(": heartbeat\n\n"));
+        assert!(body.ends_with("data: late\n\n"));
+    }
+}
@@ -26,6 +26,7 @@ mod named_file;
 mod stream;
 mod response;
 mod debug;
+pub mod event_stream;
 
 pub(crate) mod flash;
 
@@ -39,9 +40,11 @@ pub use self::responder::Responder;
 pub use self::redirect::Redirect;
 pub use self::flash::Flash;
 pub use self::named_file::NamedFile;
-pub use self::stream::Stream;
+pub use self::stream::{Stream, FlushPolicy, FlushedStream};
+#[cfg(feature = "json")] pub use self::stream::JsonLines;
 pub use self::debug::Debug;
 #[doc(inline)] pub use self::content::Content;
+#[doc(inline)] pub use self::event_stream::{EventStream, HeartbeatEventStream, Event};
 
 /// Type alias for the `Result` of a `Responder::respond` call.
 pub type Result<'r> = std::result::Result<self::Response<'r>, crate::http::Status>;
@@ -1,16 +1,16 @@
 use std::fs::File;
 use std::path::{Path, PathBuf};
-use std::io;
+use std::io::{self, Read, Seek, SeekFrom};
 use std::ops::{Deref, DerefMut};
 
 use crate::request::Request;
-use crate::response::{self, Responder};
-use crate::http::ContentType;
+use crate::response::{self, Body, Responder, Response};
+use crate::http::{ContentType, Status};
 
 /// A file with an associated name; responds with the Content-Type based on the
 /// file extension.
 #[derive(Debug)]
-pub struct NamedFile(PathBuf, File);
+pub struct NamedFile(PathBuf, File, bool, Option<ContentType>, bool, Option<ContentType>);
 
 impl NamedFile {
     /// Attempts to open a file in read-only mode.
@@ -31,7 +31,100 @@ impl NamedFile {
     /// ```
     pub fn open<P: AsRef<Path>>(path: P) -> io::Result<NamedFile> {
         let file = File::open(path.as_ref())?;
-        Ok(NamedFile(path.as_ref().to_path_buf(), file))
+        Ok(NamedFile(path.as_ref().to_path_buf(), file, false, None, false, None))
+    }
+
+    /// Like [`open()`](NamedFile::open()), but the resulting response will
+    /// carry an `ETag` (and, where available, a `Last-Modified` header)
+    /// derived from the file's metadata, and will honor `If-None-Match` and
+    /// `If-Modified-Since` on the request by replying `304 Not Modified`
+    /// with no body when they indicate the client's cached copy is current.
+    ///
+    /// The metadata backing the `ETag` and `Last-Modified` is read from the
+    /// `File` when the response is actually sent, not here at `open()` time;
+    /// if the file on disk is replaced in between, the client may see an
+    /// `ETag` for content it hasn't received before.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use rocket::response::NamedFile;
+    ///
+    /// # #[allow(unused_variables)]
+    /// let file = NamedFile::open_with_caching("foo.txt");
+    /// ```
+    pub fn open_with_caching<P: AsRef<Path>>(path: P) -> io::Result<NamedFile> {
+        let file = File::open(path.as_ref())?;
+        Ok(NamedFile(path.as_ref().to_path_buf(), file, true, None, false, None))
+    }
+
+    /// Like [`open()`](NamedFile::open()), but the response's `Content-Type`
+    /// is set to `ct` rather than inferred from the file's extension via
+    /// [`ContentType::from_extension()`]. Useful when serving a file with a
+    /// non-standard or missing extension.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use rocket::response::NamedFile;
+    /// use rocket::http::ContentType;
+    ///
+    /// # #[allow(unused_variables)]
+    /// let file = NamedFile::open_with_content_type("archive.tar.zst", ContentType::Binary);
+    /// ```
+    pub fn open_with_content_type<P: AsRef<Path>>(
+        path: P,
+        ct: ContentType
+    ) -> io::Result<NamedFile> {
+        let file = File::open(path.as_ref())?;
+        Ok(NamedFile(path.as_ref().to_path_buf(), file, false, Some(ct), false, None))
+    }
+
+    /// Sets `ct` as the `Content-Type` to fall back to when
+    /// [`ContentType::from_extension()`] can't infer one from the file's
+    /// extension, instead of leaving `Content-Type` unset. Has no effect if
+    /// this `NamedFile` was opened with
+    /// [`open_with_content_type()`](NamedFile::open_with_content_type()),
+    /// since that `Content-Type` is used unconditionally.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use rocket::response::NamedFile;
+    /// use rocket::http::ContentType;
+    ///
+    /// # #[allow(unused_variables)]
+    /// # fn f() -> std::io::Result<()> {
+    /// let file = NamedFile::open("data.bin")?.default_content_type(ContentType::Binary);
+    /// # Ok(())
+    /// # }
+    /// ```
+    #[inline(always)]
+    pub fn default_content_type(mut self, ct: ContentType) -> NamedFile {
+        self.5 = Some(ct);
+        self
+    }
+
+    /// Marks this file to be served as a `Content-Disposition: attachment`,
+    /// prompting the browser to download the file with its name rather than
+    /// display it inline. The filename sent is the file name component of
+    /// the path this `NamedFile` was opened with.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use rocket::response::NamedFile;
+    ///
+    /// # #[allow(unused_variables)]
+    /// # fn f() -> std::io::Result<()> {
+    /// let file = NamedFile::open("archive.tar.gz")?.attachment();
+    /// # Ok(())
+    /// # }
+    /// ```
+    #[inline(always)]
+    pub fn attachment(mut self) -> NamedFile {
+        self.4 = true;
+        self
     }
 
     /// Retrieve the underlying `File`.
@@ -73,21 +166,229 @@ impl NamedFile {
     }
 }
 
+/// Computes a weak `ETag` from a file's size and, if available, its
+/// modification time. This is good enough to detect most changes without
+/// hashing the file's contents.
+fn etag_for(metadata: &std::fs::Metadata) -> String {
+    let mtime = metadata.modified()
+        .ok()
+        .and_then(|t| t.duration_since(std::time::UNIX_EPOCH).ok())
+        .map(|d| d.as_secs())
+        .unwrap_or(0);
+
+    format!("W/\"{:x}-{:x}\"", metadata.len(), mtime)
+}
+
+/// Formats a modification time as an HTTP-date (`Last-Modified` format),
+/// e.g. `Mon, 01 Jan 2020 00:00:00 GMT`. Returns `None` if `mtime` isn't
+/// available on this platform.
+fn last_modified_for(metadata: &std::fs::Metadata) -> Option<String> {
+    let mtime = metadata.modified().ok()?;
+    let unix_secs = mtime.duration_since(std::time::UNIX_EPOCH).ok()?.as_secs();
+    let date = time::OffsetDateTime::from_unix_timestamp(unix_secs as i64);
+    Some(date.format("%a, %d %b %Y %H:%M:%S GMT"))
+}
+
+/// Returns `true` if `etag` weakly matches any of the comma-separated entries
+/// in an `If-None-Match` header value, including the `*` wildcard.
+fn if_none_match_hits(if_none_match: &str, etag: &str) -> bool {
+    let strip_weak = |s: &str| s.trim().trim_start_matches("W/");
+    if_none_match.trim() == "*"
+        || if_none_match.split(',').any(|candidate| strip_weak(candidate) == strip_weak(etag))
+}
+
+/// Builds a `Content-Disposition: attachment` header value for `filename`.
+///
+/// Always includes a quoted `filename` parameter with non-ASCII bytes and
+/// quotes/backslashes escaped, so legacy clients that ignore `filename*`
+/// still get a sane (if mangled) name. Also includes an RFC 6266/5987
+/// `filename*=UTF-8''<percent-encoded>` parameter, which modern clients
+/// prefer, so non-ASCII names round-trip correctly.
+fn content_disposition_for(filename: &str) -> String {
+    let quoted: String = filename.chars().flat_map(|c| match c {
+        '"' | '\\' => vec!['\\', c],
+        c if c.is_ascii() && c != '\r' && c != '\n' => vec![c],
+        _ => vec!['_'],
+    }).collect();
+
+    let encoded = percent_encode_ext_value(filename);
+    format!("attachment; filename=\"{}\"; filename*=UTF-8''{}", quoted, encoded)
+}
+
+/// Percent-encodes `value` per RFC 5987's `attr-char`, for use in the
+/// `filename*=UTF-8''...` extended parameter of a `Content-Disposition`
+/// header.
+fn percent_encode_ext_value(value: &str) -> String {
+    let mut out = String::with_capacity(value.len());
+    for &byte in value.as_bytes() {
+        match byte {
+            b'A'..=b'Z' | b'a'..=b'z' | b'0'..=b'9'
+                | b'-' | b'_' | b'.' | b'~' | b'!' | b'$' | b'&' | b'+' => {
+                out.push(byte as char);
+            }
+            _ => out.push_str(&format!("%{:02X}", byte)),
+        }
+    }
+
+    out
+}
+
+/// Parses a single-range `Range` header value (e.g. `bytes=0-499`,
+/// `bytes=500-`, or `bytes=-500`) against a file of length `len`.
+///
+/// Returns `None` if `header` doesn't start with the `bytes=` unit, in which
+/// case the caller should fall back to a full response. Returns
+/// `Some(Err(()))` if the value is syntactically a byte-range-spec but is
+/// unsatisfiable for `len`, or if it names several, comma-separated ranges,
+/// which this responder doesn't support. Otherwise, returns
+/// `Some(Ok((start, end)))`, an inclusive byte range.
+fn parse_byte_range(header: &str, len: u64) -> Option<Result<(u64, u64), ()>> {
+    let spec = match header.starts_with("bytes=") {
+        true => &header["bytes=".len()..],
+        false => return None,
+    };
+
+    // Multiple ranges are valid HTTP, but we don't support sending a
+    // `multipart/byteranges` response, so we report them as unsatisfiable.
+    if spec.contains(',') {
+        return Some(Err(()));
+    }
+
+    let dash = spec.find('-')?;
+    let (start, end) = (&spec[..dash], &spec[dash + 1..]);
+
+    let (start, end) = if start.is_empty() {
+        // A suffix range: the last `end` bytes of the file.
+        let suffix_len: u64 = end.parse().ok()?;
+        if suffix_len == 0 || len == 0 {
+            return Some(Err(()));
+        }
+
+        (len.saturating_sub(suffix_len), len - 1)
+    } else {
+        let start: u64 = start.parse().ok()?;
+        let end = match end.is_empty() {
+            true => len.saturating_sub(1),
+            false => end.parse().ok()?,
+        };
+
+        (start, end)
+    };
+
+    if len == 0 || start > end || start >= len {
+        return Some(Err(()));
+    }
+
+    Some(Ok((start, std::cmp::min(end, len - 1))))
+}
+
 /// Streams the named file to the client. Sets or overrides the Content-Type in
 /// the response according to the file's extension if the extension is
 /// recognized. See [`ContentType::from_extension()`] for more information. If
 /// you would like to stream a file with a different Content-Type than that
-/// implied by its extension, use a [`File`] directly.
+/// implied by its extension, use
+/// [`open_with_content_type()`](NamedFile::open_with_content_type()). If the
+/// extension isn't recognized and no such override was given, the
+/// Content-Type is left unset unless
+/// [`default_content_type()`](NamedFile::default_content_type()) was used to
+/// provide a fallback.
+///
+/// If the request includes a single-range `Range` header, only the requested
+/// byte range is streamed back with status `206 Partial Content` and a
+/// `Content-Range` header. An unsatisfiable or multi-range `Range` header
+/// produces `416 Range Not Satisfiable` with `Content-Range: bytes
+/// */<length>` and no body. `Accept-Ranges: bytes` is always set, on both
+/// full and partial responses, so clients know ranges are supported.
+///
+/// If the file was opened with
+/// [`open_with_caching()`](NamedFile::open_with_caching()), the response also
+/// carries an `ETag` (and a `Last-Modified`, when the platform can report an
+/// mtime), and a matching `If-None-Match` or `If-Modified-Since` request
+/// header short-circuits the response to `304 Not Modified` with no body.
+///
+/// If the file was marked with [`attachment()`](NamedFile::attachment()), the
+/// response carries a `Content-Disposition: attachment` header naming the
+/// file, prompting the browser to download it rather than render it inline.
 impl Responder<'_> for NamedFile {
     fn respond_to(self, req: &Request<'_>) -> response::Result<'static> {
-        let mut response = self.1.respond_to(req)?;
-        if let Some(ext) = self.0.extension() {
-            if let Some(ct) = ContentType::from_extension(&ext.to_string_lossy()) {
-                response.set_header(ct);
+        let NamedFile(path, mut file, caching, content_type, attachment, default_content_type) = self;
+
+        // Metadata is read here, at send time, rather than when the file was
+        // opened, since `respond_to` is the only place this type of I/O is
+        // expected to happen. This means the `ETag`/`Last-Modified` reflect
+        // the file's state right before it's streamed, not at `open()` time.
+        let metadata = file.metadata().ok();
+        let len = metadata.as_ref().map(|md| md.len()).unwrap_or(0);
+
+        let mut response = Response::build();
+        response.raw_header("Accept-Ranges", "bytes");
+        match content_type {
+            Some(ct) => { response.header(ct); }
+            None => {
+                let inferred = path.extension()
+                    .and_then(|ext| ContentType::from_extension(&ext.to_string_lossy()));
+
+                if let Some(ct) = inferred.or(default_content_type) {
+                    response.header(ct);
+                }
+            }
+        }
+
+        if attachment {
+            if let Some(filename) = path.file_name() {
+                response.raw_header("Content-Disposition",
+                    content_disposition_for(&filename.to_string_lossy()));
+            }
+        }
+
+        if caching {
+            if let Some(ref metadata) = metadata {
+                let etag = etag_for(metadata);
+                let last_modified = last_modified_for(metadata);
+
+                let not_modified = req.headers().get_one("If-None-Match")
+                    .map(|given| if_none_match_hits(given, &etag))
+                    .or_else(|| {
+                        let since = req.headers().get_one("If-Modified-Since")?;
+                        let modified = last_modified.as_deref()?;
+                        Some(since == modified)
+                    })
+                    .unwrap_or(false);
+
+                response.raw_header("ETag", etag);
+                if let Some(last_modified) = last_modified {
+                    response.raw_header("Last-Modified", last_modified);
+                }
+
+                if not_modified {
+                    response.status(Status::NotModified);
+                    return response.ok();
+                }
+            }
+        }
+
+        let range = req.headers().get_one("Range").and_then(|r| parse_byte_range(r, len));
+        match range {
+            Some(Ok((start, end))) => {
+                if file.seek(SeekFrom::Start(start)).is_err() {
+                    return Err(Status::InternalServerError);
+                }
+
+                let range_len = end - start + 1;
+                response.status(Status::PartialContent);
+                response.raw_header("Content-Range", format!("bytes {}-{}/{}", start, end, len));
+                response.raw_body(Body::Sized(file.take(range_len), range_len));
+            }
+            Some(Err(())) => {
+                response.status(Status::RangeNotSatisfiable);
+                response.raw_header("Content-Range", format!("bytes */{}", len));
+            }
+            None => {
+                response.raw_body(Body::Sized(file, len));
             }
         }
 
-        Ok(response)
+        response.ok()
     }
 }
 
@@ -1,4 +1,5 @@
 use std::convert::TryInto;
+use std::time::Duration;
 
 use crate::request::Request;
 use crate::response::{Response, Responder};
@@ -44,7 +45,7 @@ use crate::http::Status;
 /// [`Origin`]: crate::http::uri::Origin
 /// [`uri!`]: ../../rocket_codegen/macro.uri.html
 #[derive(Debug)]
-pub struct Redirect(Status, Option<Uri<'static>>);
+pub struct Redirect(Status, Option<Uri<'static>>, Option<String>);
 
 impl Redirect {
     /// Construct a temporary "see other" (303) redirect response. This is the
@@ -62,7 +63,7 @@ impl Redirect {
     /// let redirect = Redirect::to(format!("https://google.com/{}", query));
     /// ```
     pub fn to<U: TryInto<Uri<'static>>>(uri: U) -> Redirect {
-        Redirect(Status::SeeOther, uri.try_into().ok())
+        Redirect(Status::SeeOther, uri.try_into().ok(), None)
     }
 
    /// Construct a "temporary" (307) redirect response. This response instructs
@@ -81,7 +82,7 @@ impl Redirect {
    /// let redirect = Redirect::temporary(format!("https://google.com/{}", query));
    /// ```
    pub fn temporary<U: TryInto<Uri<'static>>>(uri: U) -> Redirect {
-       Redirect(Status::TemporaryRedirect, uri.try_into().ok())
+       Redirect(Status::TemporaryRedirect, uri.try_into().ok(), None)
    }
 
    /// Construct a "permanent" (308) redirect response. This redirect must only
@@ -101,7 +102,7 @@ impl Redirect {
    /// let redirect = Redirect::permanent(format!("https://google.com/{}", query));
    /// ```
    pub fn permanent<U: TryInto<Uri<'static>>>(uri: U) -> Redirect {
-       Redirect(Status::PermanentRedirect, uri.try_into().ok())
+       Redirect(Status::PermanentRedirect, uri.try_into().ok(), None)
    }
 
    /// Construct a temporary "found" (302) redirect response. This response
@@ -121,7 +122,7 @@ impl Redirect {
    /// let redirect = Redirect::found(format!("https://google.com/{}", query));
    /// ```
    pub fn found<U: TryInto<Uri<'static>>>(uri: U) -> Redirect {
-       Redirect(Status::Found, uri.try_into().ok())
+       Redirect(Status::Found, uri.try_into().ok(), None)
    }
 
    /// Construct a permanent "moved" (301) redirect response. This response
@@ -139,7 +140,38 @@ impl Redirect {
    /// let redirect = Redirect::moved(format!("https://google.com/{}", query));
    /// ```
    pub fn moved<U: TryInto<Uri<'static>>>(uri: U) -> Redirect {
-       Redirect(Status::MovedPermanently, uri.try_into().ok())
+       Redirect(Status::MovedPermanently, uri.try_into().ok(), None)
+   }
+
+   /// Sets a `Cache-Control: max-age=<max_age>` header on the generated
+   /// response, allowing clients to cache the redirect for `max_age`.
+   ///
+   /// # Examples
+   ///
+   /// ```rust
+   /// use std::time::Duration;
+   /// use rocket::response::Redirect;
+   ///
+   /// let redirect = Redirect::permanent("/other_url").cache(Duration::from_secs(3600));
+   /// ```
+   pub fn cache(mut self, max_age: Duration) -> Self {
+       self.2 = Some(format!("max-age={}", max_age.as_secs()));
+       self
+   }
+
+   /// Sets a `Cache-Control: no-store` header on the generated response,
+   /// instructing clients not to cache the redirect.
+   ///
+   /// # Examples
+   ///
+   /// ```rust
+   /// use rocket::response::Redirect;
+   ///
+   /// let redirect = Redirect::to("/other_url").no_cache();
+   /// ```
+   pub fn no_cache(mut self) -> Self {
+       self.2 = Some("no-store".into());
+       self
    }
 }
 
@@ -150,10 +182,13 @@ impl Redirect {
 impl Responder<'_> for Redirect {
     fn respond_to(self, _: &Request<'_>) -> Result<Response<'static>, Status> {
         if let Some(uri) = self.1 {
-            Response::build()
-                .status(self.0)
-                .raw_header("Location", uri.to_string())
-                .ok()
+            let mut response = Response::build();
+            response.status(self.0).raw_header("Location", uri.to_string());
+            if let Some(cache_control) = self.2 {
+                response.raw_header("Cache-Control", cache_control);
+            }
+
+            response.ok()
         } else {
             error!("Invalid URI used for redirect.");
             Err(Status::InternalServerError)
@@ -1,4 +1,5 @@
-use std::{io, fmt, str};
+use std::{fmt, str};
+use std::io::{self, Read};
 use std::borrow::Cow;
 
 use crate::response::Responder;
@@ -12,8 +13,11 @@ pub const DEFAULT_CHUNK_SIZE: u64 = 4096;
 pub enum Body<T> {
     /// A fixed-size body.
     Sized(T, u64),
-    /// A streamed/chunked body, akin to `Transfer-Encoding: chunked`.
-    Chunked(T, u64)
+    /// A streamed/chunked body, akin to `Transfer-Encoding: chunked`. The
+    /// `u64` is the chunk size, in bytes; the `bool` is `true` if every read
+    /// from the underlying reader should be flushed to the client as its own
+    /// chunk rather than accumulated up to the chunk size first.
+    Chunked(T, u64, bool)
 }
 
 impl<T> Body<T> {
@@ -21,7 +25,7 @@ impl<T> Body<T> {
     pub fn as_mut(&mut self) -> Body<&mut T> {
         match *self {
             Body::Sized(ref mut b, n) => Body::Sized(b, n),
-            Body::Chunked(ref mut b, n) => Body::Chunked(b, n)
+            Body::Chunked(ref mut b, n, immediate) => Body::Chunked(b, n, immediate)
         }
     }
 
@@ -31,14 +35,14 @@ impl<T> Body<T> {
     pub fn map<U, F: FnOnce(T) -> U>(self, f: F) -> Body<U> {
         match self {
             Body::Sized(b, n) => Body::Sized(f(b), n),
-            Body::Chunked(b, n) => Body::Chunked(f(b), n)
+            Body::Chunked(b, n, immediate) => Body::Chunked(f(b), n, immediate)
         }
     }
 
     /// Consumes `self` and returns the inner body.
     pub fn into_inner(self) -> T {
         match self {
-            Body::Sized(b, _) | Body::Chunked(b, _) => b
+            Body::Sized(b, _) | Body::Chunked(b, _, _) => b
         }
     }
 
@@ -91,7 +95,7 @@ impl<T> fmt::Debug for Body<T> {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         match *self {
             Body::Sized(_, n) => writeln!(f, "Sized Body [{} bytes]", n),
-            Body::Chunked(_, n) => writeln!(f, "Chunked Body [{} bytes]", n),
+            Body::Chunked(_, n, _) => writeln!(f, "Chunked Body [{} bytes]", n),
         }
     }
 }
@@ -409,6 +413,21 @@ impl<'r> ResponseBuilder<'r> {
         self
     }
 
+    /// Sets the body of the `Response` to be the streamed `body` with a
+    /// custom chunk size and flush behavior. See
+    /// [`Stream::chunked_with()`](crate::response::Stream::chunked_with) for
+    /// the flush policies this enables.
+    #[inline(always)]
+    pub(crate) fn chunked_body_with_flush<B: io::Read + 'r>(
+        &mut self,
+        body: B,
+        chunk_size: u64,
+        immediate: bool
+    ) -> &mut ResponseBuilder<'r> {
+        self.response.set_chunked_body_with_flush(body, chunk_size, immediate);
+        self
+    }
+
     /// Sets the body of `self` to be `body`. This method should typically not
     /// be used, opting instead for one of `sized_body`, `streamed_body`, or
     /// `chunked_body`.
@@ -894,7 +913,7 @@ impl<'r> Response<'r> {
         match self.body.as_mut() {
             Some(body) => Some(match body.as_mut() {
                 Body::Sized(b, size) => Body::Sized(b, size),
-                Body::Chunked(b, chunk_size) => Body::Chunked(b, chunk_size),
+                Body::Chunked(b, chunk_size, immediate) => Body::Chunked(b, chunk_size, immediate),
             }),
             None => None
         }
@@ -945,6 +964,24 @@ impl<'r> Response<'r> {
         self.take_body().and_then(Body::into_bytes)
     }
 
+    /// Like [`body_bytes()`](Response::body_bytes()), but reads at most
+    /// `limit` bytes, returning `None` if the body is longer than `limit`
+    /// rather than buffering the rest.
+    pub(crate) fn take_bytes_with_limit(&mut self, limit: u64) -> Option<Vec<u8>> {
+        let mut reader = self.take_body()?.into_inner().take(limit);
+        let mut bytes = Vec::new();
+        if let Err(e) = reader.read_to_end(&mut bytes) {
+            error_!("Error reading body: {:?}", e);
+            return None;
+        }
+
+        if (bytes.len() as u64) == limit && reader.into_inner().bytes().next().is_some() {
+            return None;
+        }
+
+        Some(bytes)
+    }
+
     /// Moves the body of `self` out and returns it, if there is one, leaving no
     /// body in its place.
     ///
@@ -1049,7 +1086,21 @@ impl<'r> Response<'r> {
     #[inline(always)]
     pub fn set_chunked_body<B>(&mut self, body: B, chunk_size: u64)
             where B: io::Read + 'r {
-        self.body = Some(Body::Chunked(Box::new(body), chunk_size));
+        self.body = Some(Body::Chunked(Box::new(body), chunk_size, false));
+    }
+
+    /// Sets the body of `self` to be `body`, which will be streamed with
+    /// chunk size `chunk_size`. If `immediate` is `true`, every read from
+    /// `body` is flushed to the client as its own chunk instead of being
+    /// accumulated until `chunk_size` bytes are available.
+    #[inline(always)]
+    pub(crate) fn set_chunked_body_with_flush<B>(
+        &mut self,
+        body: B,
+        chunk_size: u64,
+        immediate: bool
+    ) where B: io::Read + 'r {
+        self.body = Some(Body::Chunked(Box::new(body), chunk_size, immediate));
     }
 
     /// Sets the body of `self` to be `body`. This method should typically not
@@ -1073,7 +1124,7 @@ impl<'r> Response<'r> {
     pub fn set_raw_body<T: io::Read + 'r>(&mut self, body: Body<T>) {
         self.body = Some(match body {
             Body::Sized(b, n) => Body::Sized(Box::new(b.take(n)), n),
-            Body::Chunked(b, n) => Body::Chunked(Box::new(b), n),
+            Body::Chunked(b, n, immediate) => Body::Chunked(Box::new(b), n, immediate),
         });
     }
 
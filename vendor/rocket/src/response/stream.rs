@@ -1,21 +1,36 @@
-use std::io::Read;
+use std::io::{self, Read};
 use std::fmt::{self, Debug};
+use std::sync::mpsc;
+use std::thread;
+use std::time::Duration;
 
 use crate::request::Request;
-use crate::response::{Response, Responder, DEFAULT_CHUNK_SIZE};
+use crate::response::{Body, Response, Responder, DEFAULT_CHUNK_SIZE};
 use crate::http::Status;
 
+#[cfg(feature = "json")] use crate::http::ContentType;
+#[cfg(feature = "json")] use crate::response::content::Content;
+
 /// Streams a response to a client from an arbitrary `Read`er type.
 ///
 /// The client is sent a "chunked" response, where the chunk size is at most
 /// 4KiB. This means that at most 4KiB are stored in memory while the response
 /// is being sent. This type should be used when sending responses that are
 /// arbitrarily large in size, such as when streaming from a local socket.
-pub struct Stream<T: Read>(T, u64);
+///
+/// If the total size of the stream is known ahead of time, use
+/// [`Stream::sized()`] instead: it sets `Content-Length`, which lets clients
+/// show progress and avoids the overhead of chunked encoding.
+pub struct Stream<T: Read> {
+    inner: T,
+    chunk_size: u64,
+    len: Option<u64>,
+}
 
 impl<T: Read> Stream<T> {
     /// Create a new stream from the given `reader` and sets the chunk size for
-    /// each streamed chunk to `chunk_size` bytes.
+    /// each streamed chunk to `chunk_size` bytes. A `chunk_size` of `0` makes
+    /// no sense and is silently replaced with [`DEFAULT_CHUNK_SIZE`].
     ///
     /// # Example
     ///
@@ -30,13 +45,285 @@ impl<T: Read> Stream<T> {
     /// let response = Stream::chunked(io::stdin(), 10);
     /// ```
     pub fn chunked(reader: T, chunk_size: u64) -> Stream<T> {
-        Stream(reader, chunk_size)
+        let chunk_size = match chunk_size {
+            0 => DEFAULT_CHUNK_SIZE,
+            n => n,
+        };
+
+        Stream { inner: reader, chunk_size, len: None }
+    }
+
+    /// Create a new stream from the given `reader` whose total length is
+    /// known ahead of time to be `len` bytes.
+    ///
+    /// Unlike [`Stream::chunked()`], the response is sent with a fixed
+    /// `Content-Length` of `len` instead of `Transfer-Encoding: chunked`. If
+    /// `reader` ends before yielding `len` bytes, or still has bytes left
+    /// after yielding `len` of them, the response is aborted, just as it
+    /// would be if reading from `reader` returned an error.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use std::io;
+    /// use rocket::response::Stream;
+    ///
+    /// # #[allow(unused_variables)]
+    /// let response = Stream::sized(io::repeat(97).take(10), 10, 10);
+    /// ```
+    pub fn sized(reader: T, chunk_size: u64, len: u64) -> Stream<T> {
+        Stream { inner: reader, chunk_size, len: Some(len) }
+    }
+}
+
+/// Controls how eagerly a chunked [`Stream`] flushes data it has read from
+/// its underlying reader to the client. Used with [`Stream::chunked_with()`].
+pub enum FlushPolicy {
+    /// Flush to the client after every individual read from the underlying
+    /// reader, no matter how little data it returned. Useful for low-latency
+    /// protocols such as server-sent events, where a handler may produce one
+    /// small write at a time and expects it to reach the client promptly.
+    Immediate,
+    /// Accumulate reads until at least the given number of bytes are
+    /// buffered, then flush. This is the behavior of [`Stream::chunked()`],
+    /// expressed as a policy; larger thresholds trade latency for fewer,
+    /// larger chunks on the wire.
+    Threshold(u64),
+    /// Like [`FlushPolicy::Immediate`], but if the underlying reader hasn't
+    /// produced any data for the given [`Duration`], the given byte sequence
+    /// is sent to the client as a keep-alive before waiting again. Useful for
+    /// preventing idle long-polling or SSE connections from being closed by
+    /// intermediaries.
+    Interval(Duration, Vec<u8>),
+}
+
+impl<T: Read + Send + 'static> Stream<T> {
+    /// Create a new stream from `reader` governed by `policy`, which
+    /// controls how eagerly data read from `reader` is flushed to the
+    /// client. See [`FlushPolicy`] for the available policies. `chunk_size`
+    /// bounds how much is read from `reader` at a time under
+    /// [`FlushPolicy::Immediate`], and the size of each chunk written under
+    /// [`FlushPolicy::Interval`], whose background thread reads `reader` in
+    /// pieces of [`DEFAULT_CHUNK_SIZE`] bytes; it's ignored
+    /// by [`FlushPolicy::Threshold`], which uses its own byte count instead.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use std::io;
+    /// use std::time::Duration;
+    /// use rocket::response::{Stream, FlushPolicy};
+    ///
+    /// # #[allow(unused_variables)]
+    /// let response = Stream::chunked_with(io::stdin(), 4096, FlushPolicy::Immediate);
+    ///
+    /// # #[allow(unused_variables)]
+    /// let response = Stream::chunked_with(io::stdin(), 4096,
+    ///     FlushPolicy::Interval(Duration::from_secs(15), b":\n\n".to_vec()));
+    /// ```
+    pub fn chunked_with(reader: T, chunk_size: u64, policy: FlushPolicy) -> FlushedStream<T> {
+        FlushedStream { reader, chunk_size, policy }
+    }
+}
+
+/// A [`Stream`]-like responder created by [`Stream::chunked_with()`] that
+/// carries an explicit [`FlushPolicy`] in addition to a chunk size.
+pub struct FlushedStream<T> {
+    reader: T,
+    chunk_size: u64,
+    policy: FlushPolicy,
+}
+
+impl<'r, T: Read + Send + 'static> Responder<'r> for FlushedStream<T> {
+    fn respond_to(self, req: &Request<'_>) -> Result<Response<'r>, Status> {
+        let chunk_size = match self.chunk_size {
+            0 => DEFAULT_CHUNK_SIZE,
+            n => n,
+        };
+
+        match self.policy {
+            FlushPolicy::Immediate => {
+                let mut response = Response::build();
+                response.chunked_body_with_flush(self.reader, chunk_size, true);
+                response.ok()
+            }
+            FlushPolicy::Threshold(bytes) => {
+                Stream::chunked(self.reader, bytes).respond_to(req)
+            }
+            FlushPolicy::Interval(interval, keep_alive) => {
+                let reader = KeepAliveReader::spawn(self.reader, interval, keep_alive);
+                let mut response = Response::build();
+                response.chunked_body_with_flush(reader, chunk_size, true);
+                response.ok()
+            }
+        }
+    }
+}
+
+/// A [`Read`] adapter that reads `T` on a background thread and, if no data
+/// arrives within `interval`, yields a keep-alive byte sequence instead of
+/// blocking the caller indefinitely. Backs [`FlushPolicy::Interval`].
+struct KeepAliveReader {
+    rx: mpsc::Receiver<io::Result<Vec<u8>>>,
+    keep_alive: Vec<u8>,
+    interval: Duration,
+    buf: Vec<u8>,
+    pos: usize,
+    done: bool,
+}
+
+impl KeepAliveReader {
+    fn spawn<T: Read + Send + 'static>(
+        mut reader: T,
+        interval: Duration,
+        keep_alive: Vec<u8>,
+    ) -> Self {
+        let (tx, rx) = mpsc::channel();
+        thread::spawn(move || {
+            loop {
+                let mut chunk = vec![0; DEFAULT_CHUNK_SIZE as usize];
+                match reader.read(&mut chunk) {
+                    Ok(0) => { let _ = tx.send(Ok(Vec::new())); break; }
+                    Ok(n) => {
+                        chunk.truncate(n);
+                        if tx.send(Ok(chunk)).is_err() { break; }
+                    }
+                    Err(e) => { let _ = tx.send(Err(e)); break; }
+                }
+            }
+        });
+
+        KeepAliveReader { rx, keep_alive, interval, buf: Vec::new(), pos: 0, done: false }
+    }
+}
+
+impl Read for KeepAliveReader {
+    fn read(&mut self, out: &mut [u8]) -> io::Result<usize> {
+        loop {
+            if self.pos < self.buf.len() {
+                let n = std::cmp::min(out.len(), self.buf.len() - self.pos);
+                out[..n].copy_from_slice(&self.buf[self.pos..(self.pos + n)]);
+                self.pos += n;
+                return Ok(n);
+            }
+
+            if self.done {
+                return Ok(0);
+            }
+
+            match self.rx.recv_timeout(self.interval) {
+                Ok(Ok(chunk)) if chunk.is_empty() => { self.done = true; return Ok(0); }
+                Ok(Ok(chunk)) => { self.buf = chunk; self.pos = 0; }
+                Ok(Err(e)) => { self.done = true; return Err(e); }
+                Err(mpsc::RecvTimeoutError::Timeout) => {
+                    self.buf = self.keep_alive.clone();
+                    self.pos = 0;
+                }
+                Err(mpsc::RecvTimeoutError::Disconnected) => { self.done = true; return Ok(0); }
+            }
+        }
+    }
+}
+
+/// Streams the items of an iterator as newline-delimited JSON, one
+/// `serde_json`-serialized item per line, setting `Content-Type` to
+/// `application/x-ndjson`. Only available when the `json` feature is
+/// enabled.
+///
+/// Items are serialized one at a time as they're read by the client, so,
+/// unlike collecting the iterator into a `Vec` first, this never buffers
+/// more than a single serialized item in memory.
+///
+/// # Failure
+///
+/// If serializing an item fails, the error is logged and the response is
+/// aborted at that point, matching the [failure behavior](Stream#failure) of
+/// [`Stream`] for a failing reader.
+///
+/// # Example
+///
+/// ```rust
+/// use rocket::response::JsonLines;
+/// use serde::Serialize;
+///
+/// #[derive(Serialize)]
+/// struct Event {
+///     id: usize,
+/// }
+///
+/// # #[allow(unused_variables)]
+/// let response = JsonLines((0..10).map(|id| Event { id }));
+/// ```
+#[cfg(feature = "json")]
+#[derive(Debug, Clone)]
+pub struct JsonLines<I>(pub I);
+
+#[cfg(feature = "json")]
+impl<'r, I> Responder<'r> for JsonLines<I>
+    where I: IntoIterator, I::Item: serde::Serialize
+{
+    fn respond_to(self, req: &Request<'_>) -> Result<Response<'r>, Status> {
+        let reader = JsonLinesReader::new(self.0.into_iter());
+        let body = Content(ContentType::new("application", "x-ndjson"), Stream::from(reader));
+        body.respond_to(req)
+    }
+}
+
+/// A [`Read`] adapter that lazily serializes the items of an iterator as
+/// newline-delimited JSON, one item at a time. Used by [`JsonLines`].
+#[cfg(feature = "json")]
+struct JsonLinesReader<I: Iterator> where I::Item: serde::Serialize {
+    iter: I,
+    buf: Vec<u8>,
+    pos: usize,
+    done: bool,
+}
+
+#[cfg(feature = "json")]
+impl<I: Iterator> JsonLinesReader<I> where I::Item: serde::Serialize {
+    fn new(iter: I) -> Self {
+        JsonLinesReader { iter, buf: Vec::new(), pos: 0, done: false }
+    }
+}
+
+#[cfg(feature = "json")]
+impl<I: Iterator> Read for JsonLinesReader<I> where I::Item: serde::Serialize {
+    fn read(&mut self, out: &mut [u8]) -> io::Result<usize> {
+        loop {
+            if self.pos < self.buf.len() {
+                let n = std::cmp::min(out.len(), self.buf.len() - self.pos);
+                out[..n].copy_from_slice(&self.buf[self.pos..(self.pos + n)]);
+                self.pos += n;
+                return Ok(n);
+            }
+
+            if self.done {
+                return Ok(0);
+            }
+
+            match self.iter.next() {
+                Some(item) => {
+                    self.buf.clear();
+                    self.pos = 0;
+                    if let Err(e) = serde_json::to_writer(&mut self.buf, &item) {
+                        self.done = true;
+                        return Err(io::Error::new(io::ErrorKind::Other, e));
+                    }
+
+                    self.buf.push(b'\n');
+                }
+                None => {
+                    self.done = true;
+                    return Ok(0);
+                }
+            }
+        }
     }
 }
 
 impl<T: Read + Debug> Debug for Stream<T> {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
-        f.debug_tuple("Stream").field(&self.0).finish()
+        f.debug_tuple("Stream").field(&self.inner).finish()
     }
 }
 
@@ -56,20 +343,171 @@ impl<T: Read + Debug> Debug for Stream<T> {
 /// ```
 impl<T: Read> From<T> for Stream<T> {
     fn from(reader: T) -> Self {
-        Stream(reader, DEFAULT_CHUNK_SIZE)
+        Stream::chunked(reader, DEFAULT_CHUNK_SIZE)
+    }
+}
+
+/// A reader that wraps another reader and enforces that it yields exactly
+/// `remaining` more bytes: an early EOF or a byte beyond `remaining` is
+/// reported as an `io::Error` instead of silently under- or over-running.
+struct BoundedStream<T> {
+    inner: T,
+    remaining: u64,
+}
+
+impl<T: Read> Read for BoundedStream<T> {
+    fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+        if self.remaining == 0 {
+            let mut probe = [0u8; 1];
+            return match self.inner.read(&mut probe)? {
+                0 => Ok(0),
+                _ => Err(io::Error::new(io::ErrorKind::Other,
+                        "sized stream overran its declared length"))
+            };
+        }
+
+        let max = std::cmp::min(buf.len() as u64, self.remaining) as usize;
+        let n = self.inner.read(&mut buf[..max])?;
+        if n == 0 {
+            return Err(io::Error::new(io::ErrorKind::Other,
+                    "sized stream ended before reaching its declared length"));
+        }
+
+        self.remaining -= n as u64;
+        Ok(n)
     }
 }
 
-/// Sends a response to the client using the "Chunked" transfer encoding. The
-/// maximum chunk size is 4KiB.
+/// Sends a response to the client using, depending on how `self` was
+/// constructed, either the "chunked" transfer encoding (see [`Stream::chunked()`])
+/// or a fixed `Content-Length` (see [`Stream::sized()`]).
 ///
 /// # Failure
 ///
-/// If reading from the input stream fails at any point during the response, the
-/// response is abandoned, and the response ends abruptly. An error is printed
-/// to the console with an indication of what went wrong.
+/// If reading from the input stream fails at any point during the response, or, for
+/// a sized stream, the input stream doesn't yield exactly the declared number of
+/// bytes, the response is abandoned, and the response ends abruptly. An error is
+/// printed to the console with an indication of what went wrong.
 impl<'r, T: Read + 'r> Responder<'r> for Stream<T> {
     fn respond_to(self, _: &Request<'_>) -> Result<Response<'r>, Status> {
-        Response::build().chunked_body(self.0, self.1).ok()
+        match self.len {
+            Some(len) => {
+                let body = BoundedStream { inner: self.inner, remaining: len };
+                Response::build().raw_body(Body::Sized(body, len)).ok()
+            }
+            None => Response::build().chunked_body(self.inner, self.chunk_size).ok()
+        }
+    }
+}
+
+#[cfg(test)]
+mod flush_policy_tests {
+    use super::*;
+    use std::time::Instant;
+    use crate::http::uri::Origin;
+    use crate::http::Method;
+    use crate::request::Request;
+
+    fn chunked_flag<T: Read + Send + 'static>(reader: T, policy: FlushPolicy) -> bool {
+        let rocket = crate::ignite();
+        let request = Request::new(&rocket, Method::Get, Origin::dummy());
+        let mut response = Stream::chunked_with(reader, DEFAULT_CHUNK_SIZE, policy)
+            .respond_to(&request)
+            .expect("response");
+
+        match response.body() {
+            Some(Body::Chunked(_, _, immediate)) => immediate,
+            other => panic!("expected a chunked body, got {:?}", other),
+        }
+    }
+
+    #[test]
+    fn immediate_policy_disables_read_accumulation() {
+        assert!(chunked_flag(io::Cursor::new(b"hi".to_vec()), FlushPolicy::Immediate));
+    }
+
+    #[test]
+    fn threshold_policy_keeps_read_accumulation() {
+        assert!(!chunked_flag(io::Cursor::new(b"hi".to_vec()), FlushPolicy::Threshold(16)));
+    }
+
+    /// A reader that blocks past the keep-alive interval before its first
+    /// read, then yields `data` and ends.
+    struct SlowReader {
+        data: &'static [u8],
+        pos: usize,
+        delay: Duration,
+        slept: bool,
+    }
+
+    impl Read for SlowReader {
+        fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+            if !self.slept {
+                self.slept = true;
+                thread::sleep(self.delay);
+            }
+
+            let n = std::cmp::min(buf.len(), self.data.len() - self.pos);
+            buf[..n].copy_from_slice(&self.data[self.pos..self.pos + n]);
+            self.pos += n;
+            Ok(n)
+        }
+    }
+
+    #[test]
+    fn interval_policy_emits_keep_alive_while_reader_is_idle() {
+        let reader = SlowReader {
+            data: b"payload",
+            pos: 0,
+            delay: Duration::from_millis(80),
+            slept: false,
+        };
+
+        let rocket = crate::ignite();
+        let request = Request::new(&rocket, Method::Get, Origin::dummy());
+        let policy = FlushPolicy::Interval(Duration::from_millis(10), b":\n\n".to_vec());
+
+        let start = Instant::now();
+        let mut response = Stream::chunked_with(reader, DEFAULT_CHUNK_SIZE, policy)
+            .respond_to(&request)
+            .expect("response");
+        let body = response.body_bytes().expect("body");
+        assert!(start.elapsed() >= Duration::from_millis(80));
+
+        assert!(body.ends_with(b"payload"));
+        assert!(body.starts_with(b":\n\n"));
+        assert!(body.len() > b"payload".len());
+    }
+}
+
+#[cfg(all(test, feature = "json"))]
+mod tests {
+    use super::*;
+    use serde::Serialize;
+    use crate::http::uri::Origin;
+    use crate::http::Method;
+    use crate::request::Request;
+
+    #[derive(Serialize)]
+    struct Event {
+        id: usize,
+    }
+
+    #[test]
+    fn streams_one_json_object_per_line() {
+        let rocket = crate::ignite();
+        let request = Request::new(&rocket, Method::Get, Origin::dummy());
+
+        let lines = JsonLines((0..1000).map(|id| Event { id }));
+        let mut response = lines.respond_to(&request).expect("response");
+        let body = response.body_string().expect("body");
+
+        let lines: Vec<&str> = body.lines().collect();
+        assert_eq!(lines.len(), 1000);
+
+        for (i, line) in lines.iter().enumerate() {
+            let event: Event = serde_json::from_str(line).expect("valid json per line");
+            assert_eq!(event.id, i);
+        }
     }
 }
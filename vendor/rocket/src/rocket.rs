@@ -1,9 +1,9 @@
 use std::collections::HashMap;
 use std::str::from_utf8;
 use std::cmp::min;
-use std::io::{self, Write};
+use std::io::{self, Read, Write};
 use std::time::Duration;
-use std::mem;
+use std::{fmt, mem, process};
 
 use yansi::Paint;
 use state::Container;
@@ -11,6 +11,7 @@ use state::Container;
 #[cfg(feature = "tls")] use crate::http::tls::TlsServer;
 
 use crate::{logger, handler};
+use crate::logger::LoggingLevel;
 use crate::ext::ReadExt;
 use crate::config::{self, Config, LoggedValue};
 use crate::request::{Request, FormItems};
@@ -21,6 +22,7 @@ use crate::catcher::{self, Catcher};
 use crate::outcome::Outcome;
 use crate::error::{LaunchError, LaunchErrorKind};
 use crate::fairing::{Fairing, Fairings};
+use crate::shutdown::Shutdown;
 
 use crate::http::{Method, Status, Header};
 use crate::http::hyper::{self, header};
@@ -35,6 +37,7 @@ pub struct Rocket {
     catchers: HashMap<u16, Catcher>,
     pub(crate) state: Container,
     fairings: Fairings,
+    shutdown: Shutdown,
 }
 
 #[doc(hidden)]
@@ -69,7 +72,7 @@ impl hyper::Handler for Rocket {
         };
 
         // Retrieve the data from the hyper body.
-        let data = match Data::from_hyp(h_body) {
+        let data = match Data::from_hyp(h_body, crate::data::peek_cap(&self.config.limits)) {
             Ok(data) => data,
             Err(reason) => {
                 error_!("Bad data in request: {}", reason);
@@ -113,6 +116,18 @@ macro_rules! serve {
     })
 }
 
+/// Reads the `shutdown.grace` config extra (seconds to let in-flight
+/// requests finish after a [`Shutdown`] handle is triggered), defaulting to
+/// `0` if it's absent or malformed.
+fn shutdown_grace(config: &Config) -> Duration {
+    let grace = config.get_extra("shutdown")
+        .ok()
+        .and_then(|value| config::find(value, "grace"))
+        .and_then(config::as_u32);
+
+    Duration::from_secs(grace.unwrap_or(0) as u64)
+}
+
 impl Rocket {
     #[inline]
     fn issue_response(&self, response: Response<'_>, hyp_res: hyper::FreshResponse<'_>) {
@@ -148,18 +163,27 @@ impl Rocket {
                 io::copy(body, &mut stream)?;
                 stream.end()
             }
-            Some(Body::Chunked(mut body, chunk_size)) => {
+            Some(Body::Chunked(mut body, chunk_size, immediate)) => {
                 // This _might_ happen on a 32-bit machine!
                 if chunk_size > (usize::max_value() as u64) {
                     let msg = "chunk size exceeds limits of usize type";
                     return Err(io::Error::new(io::ErrorKind::Other, msg));
                 }
 
-                // The buffer stores the current chunk being written out.
+                // The buffer stores the current chunk being written out. When
+                // `immediate` is set, we write out whatever a single `read()`
+                // returns instead of accumulating up to `chunk_size` first, so
+                // that small, infrequent writes from the reader reach the
+                // client without delay.
                 let mut buffer = vec![0; chunk_size as usize];
                 let mut stream = hyp_res.start()?;
                 loop {
-                    match body.read_max(&mut buffer)? {
+                    let n = match immediate {
+                        true => body.read(&mut buffer)?,
+                        false => body.read_max(&mut buffer)?,
+                    };
+
+                    match n {
                         0 => break,
                         n => stream.write_all(&buffer[..n])?,
                     }
@@ -396,6 +420,42 @@ impl Rocket {
         Rocket::configured(config)
     }
 
+    /// Unwraps a `Result<Rocket, E>` produced by fallible setup code, such as
+    /// a builder function that reads templates or connects to a database
+    /// before mounting routes, returning the `Rocket` on `Ok`.
+    ///
+    /// This is typically called on the result of a user-written "build"
+    /// function, right before [`launch()`](Rocket::launch()):
+    ///
+    /// # Panics
+    ///
+    /// This function does not panic. If `result` is an `Err`, the error is
+    /// logged through the same colored, leveled output
+    /// [`ignite()`](Rocket::ignite()) uses for a malformed `Rocket.toml`,
+    /// and the process exits with a non-zero status.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # use rocket::Rocket;
+    /// # #[derive(Debug)]
+    /// # struct SetupError;
+    /// fn build() -> Result<Rocket, SetupError> {
+    ///     Ok(rocket::ignite())
+    /// }
+    ///
+    /// # if false {
+    /// rocket::Rocket::execute(build()).launch();
+    /// # }
+    /// ```
+    pub fn execute<E: fmt::Debug>(result: Result<Rocket, E>) -> Rocket {
+        result.unwrap_or_else(|e| {
+            logger::init(LoggingLevel::Debug);
+            error!("failed to build Rocket instance: {:?}", e);
+            process::exit(1)
+        })
+    }
+
     #[inline]
     fn configured(config: Config) -> Rocket {
         if logger::try_init(config.log_level, false) {
@@ -443,6 +503,7 @@ impl Rocket {
             catchers: catcher::defaults::get(),
             state: Container::new(),
             fairings: Fairings::new(),
+            shutdown: Shutdown::new(),
         }
     }
 
@@ -657,6 +718,28 @@ impl Rocket {
         self
     }
 
+    /// Returns a [`Shutdown`] handle that can be used to gracefully
+    /// terminate a call to [`launch()`](Rocket::launch()) from another
+    /// thread, without sending the process a signal. Must be called before
+    /// `launch()`, which consumes `self`.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// # if false {
+    /// let rocket = rocket::ignite();
+    /// let shutdown = rocket.shutdown_handle();
+    ///
+    /// std::thread::spawn(move || shutdown.shutdown());
+    ///
+    /// rocket.launch();
+    /// # }
+    /// ```
+    #[inline]
+    pub fn shutdown_handle(&self) -> Shutdown {
+        self.shutdown.clone()
+    }
+
     pub(crate) fn prelaunch_check(mut self) -> Result<Rocket, LaunchError> {
         self.router = match self.router.collisions() {
             Ok(router) => router,
@@ -681,6 +764,13 @@ impl Rocket {
     /// without first being inspected. See the [`LaunchError`] documentation for
     /// more information.
     ///
+    /// `launch()` also returns a `LaunchError`, of kind
+    /// [`LaunchErrorKind::Shutdown`](crate::error::LaunchErrorKind::Shutdown),
+    /// when a [`Shutdown`] handle obtained via
+    /// [`shutdown_handle()`](Rocket::shutdown_handle()) is used to stop the
+    /// server; this isn't a failure, just `launch()`'s only way to report
+    /// that it returned on purpose.
+    ///
     /// # Example
     ///
     /// ```rust
@@ -730,11 +820,20 @@ impl Rocket {
             logger::pop_max_level();
 
             let threads = self.config.workers as usize;
-            if let Err(e) = server.handle_threads(self, threads) {
-                return LaunchError::from(e);
-            }
+            let shutdown = self.shutdown.clone();
+            let grace = shutdown_grace(&self.config);
+            let mut listening = match server.handle_threads(self, threads) {
+                Ok(listening) => listening,
+                Err(e) => return LaunchError::from(e),
+            };
 
-            unreachable!("the call to `handle_threads` should block on success")
+            // `Listening::close()` doesn't stop the listener from accepting
+            // connections (a known limitation of this hyper version); we
+            // only use it to keep its `Drop` impl from blocking this thread,
+            // so that we can wait on `shutdown` instead.
+            let _ = listening.close();
+            shutdown.wait(grace);
+            LaunchError::new(LaunchErrorKind::Shutdown)
         })
     }
 
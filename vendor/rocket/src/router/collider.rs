@@ -1,6 +1,6 @@
 use super::Route;
 
-use crate::http::MediaType;
+use crate::http::{Accept, MediaType, QMediaType};
 use crate::http::route::Kind;
 use crate::request::Request;
 
@@ -120,30 +120,51 @@ fn formats_collide(route: &Route, other: &Route) -> bool {
     // requests as having a `Content-Type` if they're fully specified. If a
     // route doesn't have a `format`, it accepts all `Content-Type`s. If a
     // request doesn't have a format, it only matches routes without a format.
-    match (route.format.as_ref(), other.format.as_ref()) {
-        (Some(a), Some(b)) => media_types_collide(a, b),
-        _ => true
+    if route.formats.is_empty() || other.formats.is_empty() {
+        return true;
     }
+
+    route.formats.iter().any(|a| other.formats.iter().any(|b| media_types_collide(a, b)))
 }
 
 fn formats_match(route: &Route, request: &Request<'_>) -> bool {
+    if route.formats.is_empty() {
+        return true;
+    }
+
     if !route.method.supports_payload() {
-        route.format.as_ref()
-            .and_then(|a| request.format().map(|b| (a, b)))
-            .map(|(a, b)| media_types_collide(a, b))
-            .unwrap_or(true)
+        match request.accept() {
+            Some(accept) => negotiate_format(&route.formats, accept).is_some(),
+            None => true,
+        }
     } else {
-        match route.format.as_ref() {
-            Some(a) => match request.format() {
-                Some(b) if b.specificity() == 2 => media_types_collide(a, b),
-                _ => false
+        match request.format() {
+            Some(b) if b.specificity() == 2 => {
+                route.formats.iter().any(|a| media_types_collide(a, b))
             }
-            None => true
+            _ => false
         }
     }
 }
 
-fn media_types_collide(first: &MediaType, other: &MediaType) -> bool {
+/// Picks the media type in `formats` the client most prefers, according to
+/// the `q` values (if any) in `accept`. Candidates are considered in
+/// decreasing order of weight, defaulting to `1.0` for entries without an
+/// explicit `q`, and the first that collides with one of `formats` wins.
+pub(crate) fn negotiate_format<'m>(
+    formats: &'m [MediaType],
+    accept: &Accept
+) -> Option<&'m MediaType> {
+    let mut candidates: Vec<&QMediaType> = accept.iter().collect();
+    candidates.sort_by(|a, b| {
+        b.weight_or(1.0).partial_cmp(&a.weight_or(1.0)).unwrap_or(std::cmp::Ordering::Equal)
+    });
+
+    candidates.into_iter()
+        .find_map(|q| formats.iter().find(|f| media_types_collide(f, q.media_type())))
+}
+
+pub(crate) fn media_types_collide(first: &MediaType, other: &MediaType) -> bool {
     let collide = |a, b| a == "*" || b == "*" || a == b;
     collide(first.top(), other.top()) && collide(first.sub(), other.sub())
 }
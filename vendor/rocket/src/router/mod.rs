@@ -4,6 +4,7 @@ mod route;
 use std::collections::hash_map::HashMap;
 
 pub use self::route::Route;
+pub(crate) use self::collider::negotiate_format;
 
 use crate::request::Request;
 use crate::http::Method;
@@ -27,8 +27,16 @@ pub struct Route {
     pub uri: Origin<'static>,
     /// The rank of this route. Lower ranks have higher priorities.
     pub rank: isize,
-    /// The media type this route matches against, if any.
-    pub format: Option<MediaType>,
+    /// The media types this route matches against. Empty means any type.
+    pub formats: Vec<MediaType>,
+    /// A route-local override, in bytes, of the data limit otherwise given by
+    /// `limits.forms`, if one was declared.
+    pub data_limit: Option<u64>,
+    /// A route-local override of whether a CORS fairing should handle this
+    /// route, if one was declared with `cors`. `Some(false)` opts the route
+    /// out of CORS handling entirely; `None` leaves the decision to the
+    /// fairing's own defaults.
+    pub cors: Option<bool>,
     /// Cached metadata that aids in routing later.
     pub(crate) metadata: Metadata
 }
@@ -181,7 +189,9 @@ impl Route {
 
         let mut route = Route {
             name: None,
-            format: None,
+            formats: Vec::new(),
+            data_limit: None,
+            cors: None,
             base: Origin::dummy(),
             handler: Box::new(handler),
             metadata: Metadata::default(),
@@ -288,8 +298,11 @@ impl fmt::Display for Route {
             write!(f, " [{}]", Paint::default(&self.rank).bold())?;
         }
 
-        if let Some(ref format) = self.format {
-            write!(f, " {}", Paint::yellow(format))?;
+        if let Some((first, rest)) = self.formats.split_first() {
+            write!(f, " {}", Paint::yellow(first))?;
+            for format in rest {
+                write!(f, "{}{}", Paint::yellow(","), Paint::yellow(format))?;
+            }
         }
 
         if let Some(name) = self.name {
@@ -309,7 +322,9 @@ impl fmt::Debug for Route {
             .field("base", &self.base)
             .field("uri", &self.uri)
             .field("rank", &self.rank)
-            .field("format", &self.format)
+            .field("formats", &self.formats)
+            .field("data_limit", &self.data_limit)
+            .field("cors", &self.cors)
             .field("metadata", &self.metadata)
             .finish()
     }
@@ -320,12 +335,25 @@ impl From<&StaticRouteInfo> for Route {
     fn from(info: &StaticRouteInfo) -> Route {
         // This should never panic since `info.path` is statically checked.
         let mut route = Route::new(info.method, info.path, info.handler);
-        route.format = info.format.clone();
+        route.formats = info.format.map(|f| f.to_vec()).unwrap_or_default();
+        route.data_limit = info.data_limit;
+        route.cors = info.cors;
         route.name = Some(info.name);
-        if let Some(rank) = info.rank {
-            route.rank = rank;
+        match (info.rank, info.rank_offset) {
+            (Some(rank), _) => route.rank = rank,
+            (None, Some(offset)) => route.rank += offset,
+            (None, None) => { /* keep the rank `Route::new` computed */ }
         }
 
         route
     }
 }
+
+#[doc(hidden)]
+impl From<&[StaticRouteInfo]> for Vec<Route> {
+    fn from(infos: &[StaticRouteInfo]) -> Vec<Route> {
+        // A `#[route(GET, HEAD, path = "...")]` generates one `StaticRouteInfo`
+        // per method, sharing a path and handler; we mount one `Route` each.
+        infos.iter().map(Route::from).collect()
+    }
+}
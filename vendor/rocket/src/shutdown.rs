@@ -0,0 +1,121 @@
+use std::sync::{Arc, Condvar, Mutex};
+use std::time::Duration;
+
+/// A handle for triggering a graceful shutdown of a running [`Rocket`]
+/// instance without sending the process a signal.
+///
+/// Retrieve a `Shutdown` from a built, not-yet-launched `Rocket` with
+/// [`Rocket::shutdown_handle()`], then move it into whatever other thread
+/// (a test harness, an admin endpoint) should be able to stop the server.
+/// Calling [`shutdown()`](Shutdown::shutdown()) on the handle, or on any of
+/// its clones, causes the corresponding [`launch()`](crate::Rocket::launch())
+/// call to return a [`LaunchError`] of kind
+/// [`Shutdown`](crate::error::LaunchErrorKind::Shutdown) once requests
+/// already in flight have had `shutdown.grace` seconds (`0` if unset) to
+/// finish.
+///
+/// [`Rocket`]: crate::Rocket
+/// [`Rocket::shutdown_handle()`]: crate::Rocket::shutdown_handle()
+/// [`LaunchError`]: crate::error::LaunchError
+///
+/// # Example
+///
+/// ```rust
+/// # if false {
+/// use std::thread;
+///
+/// let rocket = rocket::ignite();
+/// let shutdown = rocket.shutdown_handle();
+///
+/// thread::spawn(move || {
+///     // ...once the test harness is done issuing requests...
+///     shutdown.shutdown();
+/// });
+///
+/// rocket.launch();
+/// # }
+/// ```
+#[derive(Clone)]
+pub struct Shutdown(Arc<(Mutex<bool>, Condvar)>);
+
+impl Shutdown {
+    pub(crate) fn new() -> Shutdown {
+        Shutdown(Arc::new((Mutex::new(false), Condvar::new())))
+    }
+
+    /// Asks the `Rocket` instance this handle was obtained from to shut
+    /// down. Returns immediately; it does not wait for the shutdown, or its
+    /// grace period, to complete.
+    pub fn shutdown(&self) {
+        let (triggered, condvar) = &*self.0;
+        *triggered.lock().expect("shutdown mutex poisoned") = true;
+        condvar.notify_all();
+    }
+
+    /// Blocks the calling thread until [`shutdown()`](Shutdown::shutdown())
+    /// is called on this handle or a clone of it, then for the additional
+    /// `grace` period.
+    pub(crate) fn wait(&self, grace: Duration) {
+        let (triggered, condvar) = &*self.0;
+        let mut triggered = triggered.lock().expect("shutdown mutex poisoned");
+        while !*triggered {
+            triggered = condvar.wait(triggered).expect("shutdown mutex poisoned");
+        }
+
+        drop(triggered);
+        std::thread::sleep(grace);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::io::{Read, Write};
+    use std::net::TcpStream;
+    use std::thread;
+    use std::time::{Duration, Instant};
+
+    use crate::{get, routes};
+    use crate::config::{Config, Environment, Table, Value};
+    use crate::error::LaunchErrorKind;
+
+    #[get("/")]
+    fn hello() -> &'static str { "hi" }
+
+    #[test]
+    fn shutdown_handle_stops_launch_within_grace_period() {
+        let mut shutdown_extra = Table::new();
+        shutdown_extra.insert("grace".into(), Value::Integer(1));
+
+        let config = Config::build(Environment::Development)
+            .address("127.0.0.1")
+            .port(8641)
+            .extra("shutdown", Value::Table(shutdown_extra))
+            .finalize()
+            .expect("valid config");
+
+        let rocket = crate::custom(config).mount("/", routes![hello]);
+        let shutdown = rocket.shutdown_handle();
+        let launched = thread::spawn(move || rocket.launch());
+
+        let mut stream = loop {
+            match TcpStream::connect("127.0.0.1:8641") {
+                Ok(stream) => break stream,
+                Err(_) => thread::sleep(Duration::from_millis(10)),
+            }
+        };
+
+        stream.write_all(b"GET / HTTP/1.1\r\nHost: x\r\nConnection: close\r\n\r\n").unwrap();
+        let mut response = String::new();
+        stream.read_to_string(&mut response).unwrap();
+        assert!(response.contains("200 OK"));
+
+        let before_shutdown = Instant::now();
+        shutdown.shutdown();
+        let error = launched.join().expect("launch thread panicked");
+        assert!(before_shutdown.elapsed() < Duration::from_secs(5));
+        match error.kind() {
+            LaunchErrorKind::Shutdown => { /* o.k. */ }
+            other => panic!("expected a graceful shutdown, got {:?}", other),
+        }
+    }
+}
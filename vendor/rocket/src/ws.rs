@@ -0,0 +1,221 @@
+//! A request guard and responder for completing a WebSocket handshake.
+//!
+//! # Scope
+//!
+//! This module implements the HTTP-level part of the WebSocket handshake
+//! described by RFC 6455: validating the `Upgrade`, `Connection`, and
+//! `Sec-WebSocket-*` request headers, computing `Sec-WebSocket-Accept`, and
+//! negotiating a subprotocol. It stops there. Once [`Upgrade`] writes its
+//! `101 Switching Protocols` response, the connection is handed back to
+//! Hyper exactly like any other response's. Hyper 0.10's synchronous
+//! `Handler` trait has no hook for detaching the underlying connection
+//! after a response is written -- there's no equivalent of `hyper::upgrade`
+//! in this version -- so relaying actual WebSocket frames (text, binary,
+//! ping/pong, close) isn't possible without a lower-level rewrite of how
+//! Rocket drives Hyper. An async Hyper migration would be the place to
+//! finish this.
+//!
+//! # Example
+//!
+//! ```rust
+//! use rocket::ws::{WebSocket, Upgrade};
+//!
+//! #[get("/chat")]
+//! fn chat(ws: WebSocket) -> Upgrade {
+//!     Upgrade::new(ws).protocol("chat")
+//! }
+//! # fn main() {}
+//! ```
+
+use crate::http::Status;
+use crate::http::uncased::uncased_eq;
+use crate::request::{self, FromRequest, Request};
+use crate::response::{self, Responder, Response};
+use crate::outcome::Outcome::{Success, Forward};
+
+/// The magic GUID RFC 6455 mixes into the client's `Sec-WebSocket-Key`
+/// before hashing, so that a server that doesn't understand WebSocket can't
+/// accidentally produce a response that looks like a valid handshake.
+const GUID: &str = "258EAFA5-E914-47DA-95CA-C5AB0DC85B11";
+
+/// Computes the `Sec-WebSocket-Accept` header value for a client's
+/// `Sec-WebSocket-Key`, per RFC 6455 §1.3.
+fn accept_key(client_key: &str) -> String {
+    let mut input = String::with_capacity(client_key.len() + GUID.len());
+    input.push_str(client_key);
+    input.push_str(GUID);
+
+    let digest = sha1::Sha1::from(input.as_bytes()).digest();
+    base64::encode(&digest.bytes())
+}
+
+/// Returns `true` if `header_value` contains `token` as one of its
+/// comma-separated, whitespace-trimmed items, case-insensitively. Used to
+/// check for `Upgrade` among a `Connection` header's possibly multiple
+/// values (`Connection: keep-alive, Upgrade`).
+fn has_token(header_value: &str, token: &str) -> bool {
+    header_value.split(',').any(|part| uncased_eq(part.trim(), token))
+}
+
+/// A request guard that validates an incoming WebSocket handshake.
+///
+/// Succeeds only for a request carrying `Upgrade: websocket`, a
+/// `Connection` header naming `Upgrade`, a `Sec-WebSocket-Key`, and
+/// `Sec-WebSocket-Version: 13`; forwards otherwise, so a route can fall
+/// back to serving an ordinary response to a non-handshake request at the
+/// same path.
+///
+/// Hand a `WebSocket` guard to [`Upgrade::new()`] to produce the handshake
+/// response.
+pub struct WebSocket {
+    accept: String,
+    protocols: Vec<String>,
+}
+
+impl WebSocket {
+    /// The subprotocols the client offered via `Sec-WebSocket-Protocol`, in
+    /// the order it sent them.
+    pub fn protocols(&self) -> &[String] {
+        &self.protocols
+    }
+}
+
+impl<'a, 'r> FromRequest<'a, 'r> for WebSocket {
+    type Error = std::convert::Infallible;
+
+    fn from_request(request: &'a Request<'r>) -> request::Outcome<Self, Self::Error> {
+        let headers = request.headers();
+
+        let is_upgrade = headers.get_one("Connection")
+            .map(|value| has_token(value, "upgrade"))
+            .unwrap_or(false);
+
+        let is_websocket = headers.get_one("Upgrade")
+            .map(|value| uncased_eq(value.trim(), "websocket"))
+            .unwrap_or(false);
+
+        let version_ok = headers.get_one("Sec-WebSocket-Version") == Some("13");
+
+        let key = match headers.get_one("Sec-WebSocket-Key") {
+            Some(key) if is_upgrade && is_websocket && version_ok => key,
+            _ => return Forward(()),
+        };
+
+        let protocols = headers.get_one("Sec-WebSocket-Protocol")
+            .map(|value| value.split(',').map(|p| p.trim().to_string()).collect())
+            .unwrap_or_default();
+
+        Success(WebSocket { accept: accept_key(key), protocols })
+    }
+}
+
+/// A [`Responder`] that completes a WebSocket handshake validated by a
+/// [`WebSocket`] request guard, responding `101 Switching Protocols`.
+///
+/// See the [module-level docs](self) for what this does and doesn't do: it
+/// completes the HTTP handshake, but doesn't relay WebSocket frames.
+pub struct Upgrade {
+    accept: String,
+    offered: Vec<String>,
+    protocol: Option<String>,
+}
+
+impl Upgrade {
+    /// Begins building a handshake response for `ws`.
+    pub fn new(ws: WebSocket) -> Self {
+        Upgrade { accept: ws.accept, offered: ws.protocols, protocol: None }
+    }
+
+    /// Negotiates `protocol` via `Sec-WebSocket-Protocol`, provided the
+    /// client offered it in its handshake request. Has no effect if the
+    /// client didn't offer it.
+    pub fn protocol(mut self, protocol: impl Into<String>) -> Self {
+        let protocol = protocol.into();
+        if self.offered.iter().any(|offered| uncased_eq(offered, &protocol)) {
+            self.protocol = Some(protocol);
+        }
+
+        self
+    }
+}
+
+impl<'r> Responder<'r> for Upgrade {
+    fn respond_to(self, _: &Request<'_>) -> response::Result<'r> {
+        let mut builder = Response::build();
+        builder.status(Status::SwitchingProtocols)
+            .raw_header("Upgrade", "websocket")
+            .raw_header("Connection", "Upgrade")
+            .raw_header("Sec-WebSocket-Accept", self.accept);
+
+        if let Some(protocol) = self.protocol {
+            builder.raw_header("Sec-WebSocket-Protocol", protocol);
+        }
+
+        builder.ok()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::local::Client;
+    use crate::http::Header;
+
+    #[get("/chat")]
+    fn chat(ws: WebSocket) -> Upgrade {
+        Upgrade::new(ws).protocol("chat")
+    }
+
+    fn client() -> Client {
+        let rocket = crate::ignite().mount("/", routes![chat]);
+        Client::new(rocket).expect("valid rocket")
+    }
+
+    fn handshake(client: &Client) -> crate::local::LocalRequest<'_> {
+        client.get("/chat")
+            .header(Header::new("Connection", "Upgrade"))
+            .header(Header::new("Upgrade", "websocket"))
+            .header(Header::new("Sec-WebSocket-Version", "13"))
+            .header(Header::new("Sec-WebSocket-Key", "dGhlIHNhbXBsZSBub25jZQ=="))
+    }
+
+    #[test]
+    fn a_valid_handshake_is_accepted_with_the_correct_accept_key() {
+        let client = client();
+        let response = handshake(&client).dispatch();
+
+        assert_eq!(response.status(), Status::SwitchingProtocols);
+        assert_eq!(response.headers().get_one("Upgrade"), Some("websocket"));
+        assert_eq!(
+            response.headers().get_one("Sec-WebSocket-Accept"),
+            Some("s3pPLMBiTxaQ9kYGzzhZRbK+xOo=")
+        );
+    }
+
+    #[test]
+    fn an_offered_subprotocol_is_negotiated() {
+        let client = client();
+        let response = handshake(&client)
+            .header(Header::new("Sec-WebSocket-Protocol", "chat, superchat"))
+            .dispatch();
+
+        assert_eq!(response.headers().get_one("Sec-WebSocket-Protocol"), Some("chat"));
+    }
+
+    #[test]
+    fn a_non_offered_subprotocol_is_not_negotiated() {
+        let client = client();
+        let response = handshake(&client)
+            .header(Header::new("Sec-WebSocket-Protocol", "superchat"))
+            .dispatch();
+
+        assert_eq!(response.headers().get_one("Sec-WebSocket-Protocol"), None);
+    }
+
+    #[test]
+    fn a_non_upgrade_request_forwards_to_a_404() {
+        let client = client();
+        let response = client.get("/chat").dispatch();
+        assert_eq!(response.status(), Status::NotFound);
+    }
+}
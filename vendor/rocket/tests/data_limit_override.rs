@@ -0,0 +1,65 @@
+#![feature(proc_macro_hygiene)]
+
+#[macro_use] extern crate rocket;
+
+use rocket::request::Form;
+
+#[derive(FromForm)]
+struct Simple {
+    value: String
+}
+
+#[post("/small", data = "<form>")]
+fn small(form: Form<Simple>) -> String {
+    form.into_inner().value
+}
+
+#[post("/big", data = "<form>", data_limit = "2MiB")]
+fn big(form: Form<Simple>) -> String {
+    form.into_inner().value
+}
+
+mod data_limit_override_tests {
+    use super::*;
+
+    use rocket::config::{Environment, Config, Limits};
+    use rocket::local::Client;
+    use rocket::http::{Status, ContentType};
+
+    fn client() -> Client {
+        // A tiny global `forms` limit, so `small` rejects a 1 MiB body while
+        // `big`'s `data_limit` override still accepts it in full.
+        let config = Config::build(Environment::Development)
+            .limits(Limits::default().limit("forms", 64))
+            .unwrap();
+
+        Client::new(rocket::custom(config).mount("/", routes![small, big])).unwrap()
+    }
+
+    fn one_mib_body() -> String {
+        format!("value={}", "a".repeat(1024 * 1024))
+    }
+
+    #[test]
+    fn small_route_rejects_large_body() {
+        let client = client();
+        let response = client.post("/small")
+            .body(one_mib_body())
+            .header(ContentType::Form)
+            .dispatch();
+
+        assert_eq!(response.status(), Status::PayloadTooLarge);
+    }
+
+    #[test]
+    fn big_route_accepts_same_size_body() {
+        let client = client();
+        let mut response = client.post("/big")
+            .body(one_mib_body())
+            .header(ContentType::Form)
+            .dispatch();
+
+        assert_eq!(response.status(), Status::Ok);
+        assert_eq!(response.body_string().map(|s| s.len()), Some(1024 * 1024));
+    }
+}
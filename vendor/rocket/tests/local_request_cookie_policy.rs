@@ -0,0 +1,136 @@
+#![feature(proc_macro_hygiene)]
+
+#[macro_use] extern crate rocket;
+
+mod cookie_policy_test {
+    use rocket::http::{Cookie, Cookies};
+
+    #[get("/set")]
+    fn set_plain_cookie(mut cookies: Cookies) -> &'static str {
+        cookies.add(Cookie::new("name", "value"));
+        "ok"
+    }
+
+    #[get("/set_explicit")]
+    fn set_explicit_cookie(mut cookies: Cookies) -> &'static str {
+        cookies.add(Cookie::build("name", "value").secure(false).finish());
+        "ok"
+    }
+
+    #[get("/set_host_prefixed")]
+    fn set_host_prefixed_cookie(mut cookies: Cookies) -> &'static str {
+        cookies.add(Cookie::new("__Host-name", "value"));
+        "ok"
+    }
+
+    #[get("/set_host_prefixed_with_domain")]
+    fn set_host_prefixed_cookie_with_domain(mut cookies: Cookies) -> &'static str {
+        cookies.add(Cookie::build("__Host-name", "value").domain("example.com").finish());
+        "ok"
+    }
+
+    #[get("/set_private")]
+    fn set_private_cookie(mut cookies: Cookies) -> &'static str {
+        cookies.add_private(Cookie::new("name", "value"));
+        "ok"
+    }
+
+    #[get("/set_signed")]
+    fn set_signed_cookie(mut cookies: Cookies) -> &'static str {
+        cookies.add_signed(Cookie::new("name", "value"));
+        "ok"
+    }
+
+    mod tests {
+        use super::*;
+        use rocket::local::Client;
+        use rocket::config::{Config, Environment};
+        use rocket::http::CookiePolicy;
+
+        fn client(policy: CookiePolicy) -> Client {
+            let config = Config::build(Environment::Development)
+                .cookies(policy)
+                .unwrap();
+
+            let rocket = rocket::custom(config)
+                .mount("/", routes![
+                    set_plain_cookie,
+                    set_explicit_cookie,
+                    set_host_prefixed_cookie,
+                    set_host_prefixed_cookie_with_domain,
+                    set_private_cookie,
+                    set_signed_cookie,
+                ]);
+
+            Client::untracked(rocket).unwrap()
+        }
+
+        #[test]
+        fn policy_defaults_are_applied_to_unset_attributes() {
+            let policy = CookiePolicy {
+                secure: Some(true),
+                same_site: Some(rocket::http::SameSite::Lax),
+                ..CookiePolicy::default()
+            };
+
+            let response = client(policy).get("/set").dispatch();
+            let set_cookie = response.headers().get_one("Set-Cookie").unwrap();
+            assert!(set_cookie.contains("Secure"));
+            assert!(set_cookie.contains("SameSite=Lax"));
+        }
+
+        #[test]
+        fn explicit_settings_override_policy_defaults() {
+            let policy = CookiePolicy { secure: Some(true), ..CookiePolicy::default() };
+
+            let response = client(policy).get("/set_explicit").dispatch();
+            let set_cookie = response.headers().get_one("Set-Cookie").unwrap();
+            assert!(!set_cookie.contains("Secure"));
+        }
+
+        #[test]
+        fn host_prefix_gets_required_attributes() {
+            let response = client(CookiePolicy::default()).get("/set_host_prefixed").dispatch();
+            let set_cookie = response.headers().get_one("Set-Cookie").unwrap();
+            assert!(set_cookie.contains("Secure"));
+            assert!(set_cookie.contains("Path=/"));
+        }
+
+        #[test]
+        fn host_prefix_with_conflicting_domain_is_refused() {
+            let response = client(CookiePolicy::default())
+                .get("/set_host_prefixed_with_domain")
+                .dispatch();
+
+            assert!(response.headers().get_one("Set-Cookie").is_none());
+        }
+
+        #[test]
+        fn policy_defaults_are_applied_to_private_cookies() {
+            let policy = CookiePolicy {
+                secure: Some(true),
+                same_site: Some(rocket::http::SameSite::Lax),
+                ..CookiePolicy::default()
+            };
+
+            let response = client(policy).get("/set_private").dispatch();
+            let set_cookie = response.headers().get_one("Set-Cookie").unwrap();
+            assert!(set_cookie.contains("Secure"));
+            assert!(set_cookie.contains("SameSite=Lax"));
+        }
+
+        #[test]
+        fn policy_defaults_are_applied_to_signed_cookies() {
+            let policy = CookiePolicy {
+                secure: Some(true),
+                same_site: Some(rocket::http::SameSite::Lax),
+                ..CookiePolicy::default()
+            };
+
+            let response = client(policy).get("/set_signed").dispatch();
+            let set_cookie = response.headers().get_one("Set-Cookie").unwrap();
+            assert!(set_cookie.contains("Secure"));
+            assert!(set_cookie.contains("SameSite=Lax"));
+        }
+    }
+}
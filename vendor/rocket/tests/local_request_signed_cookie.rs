@@ -0,0 +1,110 @@
+#![feature(proc_macro_hygiene)]
+
+#[macro_use]
+#[cfg(feature = "private-cookies")]
+extern crate rocket;
+
+#[cfg(feature = "private-cookies")]
+mod signed_cookie_test {
+    use rocket::http::Cookies;
+
+    #[get("/")]
+    fn return_signed_cookie(mut cookies: Cookies) -> Option<String> {
+        match cookies.get_signed("locale") {
+            Some(cookie) => Some(cookie.value().into()),
+            None => None,
+        }
+    }
+
+    #[get("/set")]
+    fn set_signed_cookie(mut cookies: Cookies) -> &'static str {
+        cookies.add_signed(rocket::http::Cookie::new("locale", "en-US"));
+        "ok"
+    }
+
+    mod tests {
+        use super::*;
+        use rocket::local::Client;
+        use rocket::http::{Cookie, Status};
+        use rocket::config::{Config, Environment};
+
+        fn client() -> Client {
+            let rocket = rocket::ignite()
+                .mount("/", routes![return_signed_cookie, set_signed_cookie]);
+
+            Client::new(rocket).unwrap()
+        }
+
+        #[test]
+        fn signed_cookie_is_returned() {
+            let client = client();
+            let req = client.get("/").signed_cookie(Cookie::new("locale", "en-US"));
+            let mut response = req.dispatch();
+
+            assert_eq!(response.body_string(), Some("en-US".into()));
+        }
+
+        #[test]
+        fn value_is_readable_but_tamper_proof() {
+            let client = client();
+            let req = client.get("/").signed_cookie(Cookie::new("locale", "en-US"));
+            let mut response = req.dispatch();
+            assert_eq!(response.body_string(), Some("en-US".into()));
+
+            // Corrupt the value the client tracked, simulating a client that
+            // edits its own cookie jar.
+            let mut req = client.get("/");
+            let mut tampered = Cookie::new("locale", "en-US");
+            req = req.cookie({
+                tampered.set_value("en-US-tampered");
+                tampered
+            });
+
+            let mut response = req.dispatch();
+            assert_eq!(response.body_string(), None);
+        }
+
+        #[test]
+        fn tracked_client_round_trips_signed_cookie_across_requests() {
+            let client = client();
+
+            let response = client.get("/set").dispatch();
+            assert_eq!(response.status(), Status::Ok);
+
+            let mut response = client.get("/").dispatch();
+            assert_eq!(response.body_string(), Some("en-US".into()));
+        }
+
+        #[test]
+        fn value_signed_with_fallback_key_still_verifies() {
+            let old_key = "8Xui8SN4mI+7egV/9dlfYYLGQJeEx4+DwmSQLwDVXJg=";
+            let new_key = "Oy3qo6tn+MVEGC5cQ3eKvOAu8NkhwNM2UkBPVBdZvBI=";
+
+            // Sign a cookie under `old_key`.
+            let old_config = Config::build(Environment::Development)
+                .secret_key(old_key)
+                .unwrap();
+
+            let old_rocket = rocket::custom(old_config)
+                .mount("/", routes![set_signed_cookie]);
+
+            let old_client = Client::untracked(old_rocket).unwrap();
+            let response = old_client.get("/set").dispatch();
+            let set_cookie = response.headers().get_one("Set-Cookie").unwrap();
+            let signed_cookie = Cookie::parse_encoded(set_cookie).unwrap().into_owned();
+
+            // Verify it under `new_key`, with `old_key` listed as a fallback.
+            let new_config = Config::build(Environment::Development)
+                .secret_key(new_key)
+                .secret_key_fallbacks(vec![old_key])
+                .unwrap();
+
+            let new_rocket = rocket::custom(new_config)
+                .mount("/", routes![return_signed_cookie]);
+
+            let new_client = Client::new(new_rocket).unwrap();
+            let mut response = new_client.get("/").cookie(signed_cookie).dispatch();
+            assert_eq!(response.body_string(), Some("en-US".into()));
+        }
+    }
+}
@@ -0,0 +1,51 @@
+#![feature(proc_macro_hygiene)]
+
+#[macro_use] extern crate rocket;
+
+use rocket::Request;
+use rocket::http::MediaType;
+
+#[get("/", format("json", "msgpack"))]
+fn negotiated(req: &Request<'_>) -> String {
+    req.negotiated_format()
+        .map(MediaType::to_string)
+        .unwrap_or_else(|| "none".into())
+}
+
+mod multi_format_negotiation_tests {
+    use super::*;
+
+    use rocket::local::Client;
+    use rocket::http::{Status, Accept};
+
+    fn client() -> Client {
+        Client::new(rocket::ignite().mount("/", routes![negotiated])).unwrap()
+    }
+
+    #[test]
+    fn prefers_the_higher_weighted_acceptable_format() {
+        let client = client();
+        let accept = "application/msgpack;q=0.9, application/json;q=0.1"
+            .parse::<Accept>()
+            .unwrap();
+
+        let mut response = client.get("/").header(accept).dispatch();
+        assert_eq!(response.status(), Status::Ok);
+        assert_eq!(response.body_string(), Some("application/msgpack".into()));
+    }
+
+    #[test]
+    fn falls_back_to_the_only_acceptable_format() {
+        let client = client();
+        let mut response = client.get("/").header(Accept::JSON).dispatch();
+        assert_eq!(response.status(), Status::Ok);
+        assert_eq!(response.body_string(), Some("application/json".into()));
+    }
+
+    #[test]
+    fn unmatched_accept_is_not_found() {
+        let client = client();
+        let response = client.get("/").header(Accept::HTML).dispatch();
+        assert_eq!(response.status(), Status::NotFound);
+    }
+}
@@ -0,0 +1,40 @@
+#![feature(proc_macro_hygiene)]
+
+#[macro_use] extern crate rocket;
+
+#[route(GET, HEAD, path = "/")]
+fn both() -> &'static str {
+    "hi"
+}
+
+mod multi_method_route_tests {
+    use super::*;
+    use rocket::local::Client;
+    use rocket::http::Status;
+
+    fn client() -> Client {
+        Client::new(rocket::ignite().mount("/", routes![both])).unwrap()
+    }
+
+    #[test]
+    fn get_dispatches() {
+        let client = client();
+        let mut response = client.get("/").dispatch();
+        assert_eq!(response.status(), Status::Ok);
+        assert_eq!(response.body_string(), Some("hi".into()));
+    }
+
+    #[test]
+    fn head_dispatches_to_the_same_handler() {
+        let client = client();
+        let response = client.head("/").dispatch();
+        assert_eq!(response.status(), Status::Ok);
+    }
+
+    #[test]
+    fn other_methods_still_404() {
+        let client = client();
+        let response = client.post("/").body("x").dispatch();
+        assert_eq!(response.status(), Status::NotFound);
+    }
+}
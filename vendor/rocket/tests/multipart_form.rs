@@ -0,0 +1,78 @@
+#![feature(proc_macro_hygiene)]
+
+#[macro_use] extern crate rocket;
+
+use rocket::request::Form;
+
+#[derive(FromForm)]
+struct Simple {
+    value: String
+}
+
+#[post("/", data = "<form>")]
+fn index(form: Form<Simple>) -> String {
+    form.into_inner().value
+}
+
+mod multipart_form_tests {
+    use rocket;
+    use rocket::local::Client;
+    use rocket::http::{Status, ContentType};
+
+    fn client() -> Client {
+        Client::new(rocket::ignite().mount("/", routes![super::index])).unwrap()
+    }
+
+    fn multipart_content_type() -> ContentType {
+        ContentType::with_params("multipart", "form-data", ("boundary", "X-BOUNDARY"))
+    }
+
+    #[test]
+    fn simple_text_field() {
+        let body = [
+            "--X-BOUNDARY",
+            "Content-Disposition: form-data; name=\"value\"",
+            "",
+            "Hello world",
+            "--X-BOUNDARY--",
+            "",
+        ].join("\r\n");
+
+        let mut response = client().post("/")
+            .body(body)
+            .header(multipart_content_type())
+            .dispatch();
+
+        assert_eq!(response.body_string(), Some("Hello world".into()));
+    }
+
+    #[test]
+    fn file_part_is_rejected() {
+        let body = [
+            "--X-BOUNDARY",
+            "Content-Disposition: form-data; name=\"value\"; filename=\"a.txt\"",
+            "Content-Type: text/plain",
+            "",
+            "Hello world",
+            "--X-BOUNDARY--",
+            "",
+        ].join("\r\n");
+
+        let response = client().post("/")
+            .body(body)
+            .header(multipart_content_type())
+            .dispatch();
+
+        assert_eq!(response.status(), Status::UnprocessableEntity);
+    }
+
+    #[test]
+    fn missing_boundary_is_bad_request() {
+        let response = client().post("/")
+            .body("irrelevant")
+            .header(ContentType::new("multipart", "form-data"))
+            .dispatch();
+
+        assert_eq!(response.status(), Status::BadRequest);
+    }
+}
@@ -0,0 +1,36 @@
+#![feature(proc_macro_hygiene)]
+
+#[macro_use] extern crate rocket;
+
+use rocket::response::NamedFile;
+
+#[get("/file")]
+fn file() -> std::io::Result<NamedFile> {
+    Ok(NamedFile::open(
+        concat!(env!("CARGO_MANIFEST_DIR"), "/tests/named_file_attachment.rs")
+    )?.attachment())
+}
+
+mod named_file_attachment_tests {
+    use rocket;
+    use rocket::local::Client;
+    use rocket::http::Status;
+
+    fn client() -> Client {
+        Client::new(rocket::ignite().mount("/", routes![super::file])).unwrap()
+    }
+
+    #[test]
+    fn attachment_sets_content_disposition_with_filename() {
+        let client = client();
+        let response = client.get("/file").dispatch();
+        assert_eq!(response.status(), Status::Ok);
+
+        let disposition = response.headers().get_one("Content-Disposition")
+            .expect("response should carry a Content-Disposition header");
+
+        assert!(disposition.starts_with("attachment;"));
+        assert!(disposition.contains("filename=\"named_file_attachment.rs\""));
+        assert!(disposition.contains("filename*=UTF-8''named_file_attachment.rs"));
+    }
+}
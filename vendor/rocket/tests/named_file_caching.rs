@@ -0,0 +1,37 @@
+#![feature(proc_macro_hygiene)]
+
+#[macro_use] extern crate rocket;
+
+use rocket::response::NamedFile;
+
+#[get("/file")]
+fn file() -> std::io::Result<NamedFile> {
+    NamedFile::open_with_caching(concat!(env!("CARGO_MANIFEST_DIR"), "/tests/named_file_caching.rs"))
+}
+
+mod named_file_caching_tests {
+    use rocket;
+    use rocket::local::Client;
+    use rocket::http::{Status, Header};
+
+    fn client() -> Client {
+        Client::new(rocket::ignite().mount("/", routes![super::file])).unwrap()
+    }
+
+    #[test]
+    fn second_request_with_etag_is_not_modified() {
+        let client = client();
+        let response = client.get("/file").dispatch();
+        assert_eq!(response.status(), Status::Ok);
+
+        let etag = response.headers().get_one("ETag")
+            .expect("response should carry an ETag")
+            .to_string();
+
+        let response = client.get("/file")
+            .header(Header::new("If-None-Match", etag))
+            .dispatch();
+
+        assert_eq!(response.status(), Status::NotModified);
+    }
+}
@@ -0,0 +1,32 @@
+#![feature(proc_macro_hygiene)]
+
+#[macro_use] extern crate rocket;
+
+use rocket::response::NamedFile;
+use rocket::http::ContentType;
+
+#[get("/file")]
+fn file() -> std::io::Result<NamedFile> {
+    NamedFile::open_with_content_type(
+        concat!(env!("CARGO_MANIFEST_DIR"), "/tests/named_file_content_type.rs"),
+        ContentType::Binary,
+    )
+}
+
+mod named_file_content_type_tests {
+    use rocket;
+    use rocket::local::Client;
+    use rocket::http::{Status, ContentType};
+
+    fn client() -> Client {
+        Client::new(rocket::ignite().mount("/", routes![super::file])).unwrap()
+    }
+
+    #[test]
+    fn content_type_override_wins_over_extension() {
+        let client = client();
+        let response = client.get("/file").dispatch();
+        assert_eq!(response.status(), Status::Ok);
+        assert_eq!(response.content_type(), Some(ContentType::Binary));
+    }
+}
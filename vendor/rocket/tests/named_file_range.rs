@@ -0,0 +1,94 @@
+#![feature(proc_macro_hygiene)]
+
+#[macro_use] extern crate rocket;
+
+use rocket::response::NamedFile;
+
+#[get("/file")]
+fn file() -> std::io::Result<NamedFile> {
+    NamedFile::open(concat!(env!("CARGO_MANIFEST_DIR"), "/tests/named_file_range.rs"))
+}
+
+mod named_file_range_tests {
+    use rocket;
+    use rocket::local::Client;
+    use rocket::http::{Status, Header};
+
+    fn client() -> Client {
+        Client::new(rocket::ignite().mount("/", routes![super::file])).unwrap()
+    }
+
+    fn body_len() -> u64 {
+        std::fs::metadata(concat!(env!("CARGO_MANIFEST_DIR"), "/tests/named_file_range.rs"))
+            .unwrap()
+            .len()
+    }
+
+    #[test]
+    fn full_response_sets_accept_ranges() {
+        let client = client();
+        let response = client.get("/file").dispatch();
+        assert_eq!(response.status(), Status::Ok);
+        assert_eq!(response.headers().get_one("Accept-Ranges"), Some("bytes"));
+    }
+
+    #[test]
+    fn open_ended_range() {
+        let client = client();
+        let mut response = client.get("/file")
+            .header(Header::new("Range", "bytes=100-"))
+            .dispatch();
+
+        assert_eq!(response.status(), Status::PartialContent);
+        let len = body_len();
+        assert_eq!(
+            response.headers().get_one("Content-Range"),
+            Some(format!("bytes 100-{}/{}", len - 1, len).as_str())
+        );
+
+        let body = response.body_bytes().unwrap();
+        assert_eq!(body.len() as u64, len - 100);
+    }
+
+    #[test]
+    fn suffix_range() {
+        let client = client();
+        let mut response = client.get("/file")
+            .header(Header::new("Range", "bytes=-50"))
+            .dispatch();
+
+        assert_eq!(response.status(), Status::PartialContent);
+        let body = response.body_bytes().unwrap();
+        assert_eq!(body.len(), 50);
+    }
+
+    #[test]
+    fn out_of_bounds_range() {
+        let client = client();
+        let len = body_len();
+        let response = client.get("/file")
+            .header(Header::new("Range", format!("bytes={}-", len + 100)))
+            .dispatch();
+
+        assert_eq!(response.status(), Status::RangeNotSatisfiable);
+        assert_eq!(
+            response.headers().get_one("Content-Range"),
+            Some(format!("bytes */{}", len).as_str())
+        );
+    }
+
+    #[test]
+    fn multiple_ranges_are_unsatisfiable() {
+        let client = client();
+        let len = body_len();
+        let response = client.get("/file")
+            .header(Header::new("Range", "bytes=0-10,20-30"))
+            .dispatch();
+
+        assert_eq!(response.status(), Status::RangeNotSatisfiable);
+        assert_eq!(
+            response.headers().get_one("Content-Range"),
+            Some(format!("bytes */{}", len).as_str())
+        );
+    }
+}
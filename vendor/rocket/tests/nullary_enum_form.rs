@@ -0,0 +1,66 @@
+#![feature(proc_macro_hygiene)]
+
+#[macro_use] extern crate rocket;
+
+use rocket::request::Form;
+
+#[derive(Debug, PartialEq, FromForm)]
+#[form(tag = "status")]
+enum Status {
+    Active,
+    Inactive,
+    Pending,
+}
+
+#[post("/", data = "<status>")]
+fn index(status: Form<Status>) -> String {
+    format!("{:?}", status.into_inner())
+}
+
+mod nullary_enum_form_tests {
+    use super::*;
+    use rocket::local::Client;
+    use rocket::http::{Status as HttpStatus, ContentType};
+
+    fn client() -> Client {
+        Client::new(rocket::ignite().mount("/", routes![index])).unwrap()
+    }
+
+    #[test]
+    fn matches_each_variant_by_name() {
+        let mut response = client().post("/")
+            .header(ContentType::Form)
+            .body("status=Active")
+            .dispatch();
+
+        assert_eq!(response.status(), HttpStatus::Ok);
+        assert_eq!(response.body_string(), Some("Active".into()));
+
+        let mut response = client().post("/")
+            .header(ContentType::Form)
+            .body("status=Pending")
+            .dispatch();
+
+        assert_eq!(response.body_string(), Some("Pending".into()));
+    }
+
+    #[test]
+    fn rejects_unknown_value() {
+        let response = client().post("/")
+            .header(ContentType::Form)
+            .body("status=Deleted")
+            .dispatch();
+
+        assert_eq!(response.status(), HttpStatus::UnprocessableEntity);
+    }
+
+    #[test]
+    fn rejects_missing_tag() {
+        let response = client().post("/")
+            .header(ContentType::Form)
+            .body("other=1")
+            .dispatch();
+
+        assert_eq!(response.status(), HttpStatus::UnprocessableEntity);
+    }
+}
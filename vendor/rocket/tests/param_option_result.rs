@@ -0,0 +1,81 @@
+#![feature(proc_macro_hygiene)]
+
+// Option<T>/Result<T, T::Error> path segments, and Option<T> path..
+// segments, already bind instead of forwarding on a parse failure: codegen
+// just calls `FromParam`/`FromSegments` on the declared type, and this
+// crate's `FromParam for Option<T>`/`FromParam for Result<T, T::Error>`/
+// `FromSegments for Option<T>` impls already turn a parse failure into
+// `None`/`Err` rather than a forward. These are regression tests for that
+// existing behavior, not a new codegen special-case.
+
+#[macro_use] extern crate rocket;
+
+use std::path::PathBuf;
+
+use rocket::http::RawStr;
+
+#[get("/users/<id>")]
+fn user(id: Option<usize>) -> String {
+    match id {
+        Some(id) => format!("user {}", id),
+        None => "not a user id".into(),
+    }
+}
+
+#[get("/parse/<id>")]
+fn parse(id: Result<usize, &RawStr>) -> String {
+    match id {
+        Ok(id) => format!("parsed {}", id),
+        Err(bad) => format!("unparseable {}", bad),
+    }
+}
+
+#[get("/files/<path..>")]
+fn files(path: Option<PathBuf>) -> String {
+    match path {
+        Some(path) => format!("path {}", path.display()),
+        None => "no path".into(),
+    }
+}
+
+mod param_option_result_tests {
+    use super::*;
+    use rocket::local::Client;
+    use rocket::http::Status;
+
+    fn client() -> Client {
+        Client::new(rocket::ignite().mount("/", routes![user, parse, files])).unwrap()
+    }
+
+    #[test]
+    fn option_path_param_binds_none_instead_of_forwarding() {
+        let client = client();
+        let mut response = client.get("/users/abc").dispatch();
+        assert_eq!(response.status(), Status::Ok);
+        assert_eq!(response.body_string(), Some("not a user id".into()));
+
+        let mut response = client.get("/users/10").dispatch();
+        assert_eq!(response.status(), Status::Ok);
+        assert_eq!(response.body_string(), Some("user 10".into()));
+    }
+
+    #[test]
+    fn result_path_param_binds_err_instead_of_forwarding() {
+        let client = client();
+        let mut response = client.get("/parse/abc").dispatch();
+        assert_eq!(response.status(), Status::Ok);
+        assert_eq!(response.body_string(), Some("unparseable abc".into()));
+    }
+
+    #[test]
+    fn option_multi_segment_param_binds_none_instead_of_forwarding() {
+        let client = client();
+        let mut response = client.get("/files/.hidden").dispatch();
+        assert_eq!(response.status(), Status::Ok);
+        assert_eq!(response.body_string(), Some("no path".into()));
+
+        let mut response = client.get("/files/a/b/c").dispatch();
+        assert_eq!(response.status(), Status::Ok);
+        assert_eq!(response.body_string(), Some("path a/b/c".into()));
+    }
+}
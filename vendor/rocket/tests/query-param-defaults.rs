@@ -0,0 +1,42 @@
+#![feature(proc_macro_hygiene)]
+
+#[macro_use] extern crate rocket;
+
+#[get("/?<page=1>&<limit>")]
+fn paged(page: usize, limit: usize) -> String {
+    format!("{}/{}", page, limit)
+}
+
+mod query_param_defaults_tests {
+    use super::*;
+    use rocket::local::Client;
+    use rocket::http::Status;
+
+    fn client() -> Client {
+        let rocket = rocket::ignite().mount("/", routes![paged]);
+        Client::new(rocket).unwrap()
+    }
+
+    #[test]
+    fn missing_defaulted_param_falls_back_to_the_literal() {
+        let client = client();
+        let mut response = client.get("/?limit=20").dispatch();
+        assert_eq!(response.status(), Status::Ok);
+        assert_eq!(response.body_string(), Some("1/20".into()));
+    }
+
+    #[test]
+    fn present_defaulted_param_overrides_the_literal() {
+        let client = client();
+        let mut response = client.get("/?page=3&limit=20").dispatch();
+        assert_eq!(response.status(), Status::Ok);
+        assert_eq!(response.body_string(), Some("3/20".into()));
+    }
+
+    #[test]
+    fn missing_non_defaulted_param_still_forwards() {
+        let client = client();
+        let response = client.get("/?page=3").dispatch();
+        assert_eq!(response.status(), Status::NotFound);
+    }
+}
@@ -0,0 +1,48 @@
+#![feature(proc_macro_hygiene)]
+
+#[macro_use] extern crate rocket;
+
+use rocket::request::{Request, QueryParamFailures};
+
+#[get("/?<a>&<b>")]
+fn strict(a: usize, b: usize) -> String {
+    format!("{}/{}", a, b)
+}
+
+#[catch(404)]
+fn not_found(request: &Request) -> String {
+    let failures = request.local_cache(|| QueryParamFailures(vec![]));
+    let mut names: Vec<&str> = failures.0.iter().map(|(name, _)| name.as_str()).collect();
+    names.sort();
+    names.join(",")
+}
+
+mod query_param_failures_tests {
+    use super::*;
+    use rocket::local::Client;
+    use rocket::http::Status;
+
+    fn client() -> Client {
+        let rocket = rocket::ignite()
+            .mount("/", routes![strict])
+            .register(catchers![not_found]);
+
+        Client::new(rocket).unwrap()
+    }
+
+    #[test]
+    fn both_bad_params_are_reported() {
+        let client = client();
+        let mut response = client.get("/?a=not_a_number&b=also_not_a_number").dispatch();
+        assert_eq!(response.status(), Status::NotFound);
+        assert_eq!(response.body_string(), Some("a,b".into()));
+    }
+
+    #[test]
+    fn valid_params_never_forward() {
+        let client = client();
+        let mut response = client.get("/?a=1&b=2").dispatch();
+        assert_eq!(response.status(), Status::Ok);
+        assert_eq!(response.body_string(), Some("1/2".into()));
+    }
+}
@@ -0,0 +1,50 @@
+#![feature(proc_macro_hygiene)]
+
+#[macro_use] extern crate rocket;
+
+use rocket::request::{FromQuery, Query};
+
+struct Trail(Vec<(String, String)>);
+
+impl<'q> FromQuery<'q> for Trail {
+    type Error = std::convert::Infallible;
+
+    fn from_query(query: Query<'q>) -> Result<Self, Self::Error> {
+        Ok(Trail(query.map(|i| (i.key.to_string(), i.value.to_string())).collect()))
+    }
+}
+
+#[get("/?<id>&<trail..>")]
+fn index(id: usize, trail: Trail) -> String {
+    let pairs: Vec<String> = trail.0.iter().map(|(k, v)| format!("{}={}", k, v)).collect();
+    format!("{}:{}", id, pairs.join(","))
+}
+
+mod query_trail_ordering_tests {
+    use super::*;
+    use rocket::local::Client;
+    use rocket::http::Status;
+
+    fn client() -> Client {
+        let rocket = rocket::ignite().mount("/", routes![index]);
+        Client::new(rocket).unwrap()
+    }
+
+    #[test]
+    fn trail_preserves_raw_order_and_duplicates() {
+        let client = client();
+        let mut response = client.get("/?id=1&tag=a&tag=b&color=red&tag=c").dispatch();
+
+        assert_eq!(response.status(), Status::Ok);
+        assert_eq!(response.body_string(), Some("1:tag=a,tag=b,color=red,tag=c".into()));
+    }
+
+    #[test]
+    fn trail_skips_only_the_statically_matched_key() {
+        let client = client();
+        let mut response = client.get("/?tag=a&id=42&tag=b").dispatch();
+
+        assert_eq!(response.status(), Status::Ok);
+        assert_eq!(response.body_string(), Some("42:tag=a,tag=b".into()));
+    }
+}
@@ -0,0 +1,78 @@
+#![feature(proc_macro_hygiene)]
+
+#[macro_use] extern crate rocket;
+
+use std::time::Duration;
+
+use rocket::response::Redirect;
+
+#[get("/permanent")]
+fn permanent() -> Redirect {
+    Redirect::permanent("/target").cache(Duration::from_secs(3600))
+}
+
+#[get("/moved")]
+fn moved() -> Redirect {
+    Redirect::moved("/target")
+}
+
+#[get("/found")]
+fn found() -> Redirect {
+    Redirect::found("/target")
+}
+
+#[get("/temporary")]
+fn temporary() -> Redirect {
+    Redirect::temporary("/target").no_cache()
+}
+
+#[get("/invalid")]
+fn invalid() -> Redirect {
+    Redirect::to("not a valid uri")
+}
+
+mod redirect_variants_and_caching_tests {
+    use super::*;
+    use rocket::local::Client;
+    use rocket::http::Status;
+
+    fn client() -> Client {
+        Client::new(rocket::ignite().mount("/", routes![permanent, moved, found, temporary, invalid]))
+            .unwrap()
+    }
+
+    #[test]
+    fn permanent_redirect_sets_status_and_cache_control() {
+        let response = client().get("/permanent").dispatch();
+        assert_eq!(response.status(), Status::PermanentRedirect);
+        assert_eq!(response.headers().get_one("Location"), Some("/target"));
+        assert_eq!(response.headers().get_one("Cache-Control"), Some("max-age=3600"));
+    }
+
+    #[test]
+    fn moved_redirect_sets_status() {
+        let response = client().get("/moved").dispatch();
+        assert_eq!(response.status(), Status::MovedPermanently);
+        assert_eq!(response.headers().get_one("Location"), Some("/target"));
+    }
+
+    #[test]
+    fn found_redirect_sets_status() {
+        let response = client().get("/found").dispatch();
+        assert_eq!(response.status(), Status::Found);
+        assert_eq!(response.headers().get_one("Location"), Some("/target"));
+    }
+
+    #[test]
+    fn temporary_redirect_sets_no_store() {
+        let response = client().get("/temporary").dispatch();
+        assert_eq!(response.status(), Status::TemporaryRedirect);
+        assert_eq!(response.headers().get_one("Cache-Control"), Some("no-store"));
+    }
+
+    #[test]
+    fn invalid_uri_is_internal_server_error_not_panic() {
+        let response = client().get("/invalid").dispatch();
+        assert_eq!(response.status(), Status::InternalServerError);
+    }
+}
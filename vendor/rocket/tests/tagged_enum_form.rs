@@ -0,0 +1,72 @@
+#![feature(proc_macro_hygiene)]
+
+#[macro_use] extern crate rocket;
+
+use rocket::request::Form;
+
+#[derive(FromForm)]
+#[form(tag = "type")]
+enum Shape {
+    Circle { radius: usize },
+    Rectangle { width: usize, height: usize },
+}
+
+#[post("/", data = "<shape>")]
+fn index(shape: Form<Shape>) -> String {
+    match shape.into_inner() {
+        Shape::Circle { radius } => format!("circle {}", radius),
+        Shape::Rectangle { width, height } => format!("rectangle {} {}", width, height),
+    }
+}
+
+mod tagged_enum_form_tests {
+    use super::*;
+    use rocket::local::Client;
+    use rocket::http::{Status, ContentType};
+
+    fn client() -> Client {
+        Client::new(rocket::ignite().mount("/", routes![index])).unwrap()
+    }
+
+    #[test]
+    fn posts_circle_variant() {
+        let mut response = client().post("/")
+            .header(ContentType::Form)
+            .body("type=Circle&radius=4")
+            .dispatch();
+
+        assert_eq!(response.status(), Status::Ok);
+        assert_eq!(response.body_string(), Some("circle 4".into()));
+    }
+
+    #[test]
+    fn posts_rectangle_variant() {
+        let mut response = client().post("/")
+            .header(ContentType::Form)
+            .body("type=Rectangle&width=2&height=3")
+            .dispatch();
+
+        assert_eq!(response.status(), Status::Ok);
+        assert_eq!(response.body_string(), Some("rectangle 2 3".into()));
+    }
+
+    #[test]
+    fn rejects_unknown_tag() {
+        let response = client().post("/")
+            .header(ContentType::Form)
+            .body("type=Triangle&radius=4")
+            .dispatch();
+
+        assert_eq!(response.status(), Status::UnprocessableEntity);
+    }
+
+    #[test]
+    fn rejects_missing_tag() {
+        let response = client().post("/")
+            .header(ContentType::Form)
+            .body("radius=4")
+            .dispatch();
+
+        assert_eq!(response.status(), Status::UnprocessableEntity);
+    }
+}
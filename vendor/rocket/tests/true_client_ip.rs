@@ -0,0 +1,118 @@
+#![feature(proc_macro_hygiene)]
+
+#[macro_use] extern crate rocket;
+
+use rocket::Outcome::Success;
+use rocket::Request;
+use rocket::request::{self, FromRequest};
+
+struct TrueClientIp(String);
+
+impl<'a, 'r> FromRequest<'a, 'r> for TrueClientIp {
+    type Error = ();
+
+    fn from_request(request: &'a Request<'r>) -> request::Outcome<Self, ()> {
+        let ip = request.true_client_ip()
+            .map(|ip| ip.to_string())
+            .unwrap_or_else(|| "unknown".into());
+
+        Success(TrueClientIp(ip))
+    }
+}
+
+#[get("/")]
+fn whoami(ip: TrueClientIp) -> String {
+    ip.0
+}
+
+mod true_client_ip_tests {
+    use super::*;
+
+    use rocket::config::{Environment, Config, TrustedProxies};
+    use rocket::local::Client;
+    use rocket::http::Header;
+
+    fn client() -> Client {
+        let proxies = TrustedProxies::parse(vec!["10.0.0.0/8"]).unwrap();
+        let config = Config::build(Environment::Development)
+            .proxies(proxies)
+            .unwrap();
+
+        Client::new(rocket::custom(config).mount("/", routes![whoami])).unwrap()
+    }
+
+    fn body_of(client: &Client, remote: &str, forwarded_for: Option<&str>) -> String {
+        let mut req = client.get("/").remote(remote.parse().unwrap());
+        if let Some(header) = forwarded_for {
+            req = req.header(Header::new("X-Forwarded-For", header));
+        }
+
+        req.dispatch().body_string().unwrap()
+    }
+
+    #[test]
+    fn resolves_plain_ipv4_chain() {
+        let client = client();
+        let ip = body_of(&client, "10.0.0.1:1234", Some("203.0.113.5, 10.0.0.1"));
+        assert_eq!(ip, "203.0.113.5");
+    }
+
+    #[test]
+    fn resolves_ipv6_entry() {
+        let client = client();
+        let ip = body_of(&client, "10.0.0.1:1234", Some("2001:db8::1, 10.0.0.1"));
+        assert_eq!(ip, "2001:db8::1");
+    }
+
+    #[test]
+    fn resolves_bracketed_ipv6_with_port() {
+        let client = client();
+        let ip = body_of(&client, "10.0.0.1:1234", Some("[2001:db8::1]:4000, 10.0.0.1"));
+        assert_eq!(ip, "2001:db8::1");
+    }
+
+    #[test]
+    fn resolves_port_suffixed_ipv4() {
+        let client = client();
+        let ip = body_of(&client, "10.0.0.1:1234", Some("203.0.113.5:5000, 10.0.0.1"));
+        assert_eq!(ip, "203.0.113.5");
+    }
+
+    #[test]
+    fn tolerates_surrounding_whitespace() {
+        let client = client();
+        let ip = body_of(&client, "10.0.0.1:1234", Some("  203.0.113.5  ,  10.0.0.1  "));
+        assert_eq!(ip, "203.0.113.5");
+    }
+
+    #[test]
+    fn skips_multiple_trusted_hops() {
+        let client = client();
+        let ip = body_of(&client, "10.0.0.1:1234", Some("203.0.113.5, 10.0.0.2, 10.0.0.1"));
+        assert_eq!(ip, "203.0.113.5");
+    }
+
+    #[test]
+    fn falls_back_when_peer_is_untrusted() {
+        let client = client();
+        let ip = body_of(&client, "203.0.113.9:1234", Some("203.0.113.5, 10.0.0.1"));
+        assert_eq!(ip, "203.0.113.9");
+    }
+
+    #[test]
+    fn falls_back_when_header_is_absent() {
+        let client = client();
+        let ip = body_of(&client, "10.0.0.1:1234", None);
+        assert_eq!(ip, "10.0.0.1");
+    }
+
+    #[test]
+    fn stops_conservatively_on_malformed_entry() {
+        // The walk reaches "garbage" before finding any untrusted hop, so it
+        // gives up and falls back to the ordinary `client_ip()` rather than
+        // guessing at "203.0.113.5" further left in the header.
+        let client = client();
+        let ip = body_of(&client, "10.0.0.1:1234", Some("203.0.113.5, garbage, 10.0.0.1"));
+        assert_eq!(ip, "10.0.0.1");
+    }
+}
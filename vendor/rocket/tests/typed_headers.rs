@@ -0,0 +1,60 @@
+#![feature(proc_macro_hygiene)]
+
+#[macro_use] extern crate rocket;
+
+use rocket::Request;
+use rocket::http::{Method, Header, Authorization};
+
+#[test]
+fn accept_language_is_sorted_by_quality() {
+    Request::example(Method::Get, "/uri", |mut request| {
+        request.add_header(Header::new("Accept-Language", "da, en-gb;q=0.8, en;q=0.7"));
+
+        let languages: Vec<_> = request.accept_language().iter()
+            .map(|(tag, q)| (tag.as_str().to_string(), *q))
+            .collect();
+
+        assert_eq!(languages, vec![
+            ("da".to_string(), 1.0),
+            ("en-gb".to_string(), 0.8),
+            ("en".to_string(), 0.7),
+        ]);
+    });
+}
+
+#[test]
+fn authorization_parses_basic_and_bearer() {
+    Request::example(Method::Get, "/uri", |mut request| {
+        request.add_header(Header::new("Authorization", "Basic dXNlcjpwYXNz"));
+        assert_eq!(request.authorization(), Some(&Authorization::Basic {
+            user: "user".into(),
+            pass: "pass".into(),
+        }));
+    });
+
+    Request::example(Method::Get, "/uri", |mut request| {
+        request.add_header(Header::new("Authorization", "Bearer sometoken"));
+        assert_eq!(request.authorization(), Some(&Authorization::Bearer("sometoken".into())));
+    });
+}
+
+#[test]
+fn authorization_is_none_for_malformed_header() {
+    Request::example(Method::Get, "/uri", |mut request| {
+        request.add_header(Header::new("Authorization", "Digest realm=\"x\""));
+        assert_eq!(request.authorization(), None);
+    });
+}
+
+#[test]
+fn forwarded_parses_elements() {
+    Request::example(Method::Get, "/uri", |mut request| {
+        request.add_header(Header::new("Forwarded", "for=192.0.2.60;proto=http, for=_hidden"));
+
+        let elements = request.forwarded();
+        assert_eq!(elements.len(), 2);
+        assert_eq!(elements[0].r#for, Some("192.0.2.60".into()));
+        assert_eq!(elements[0].proto, Some("http".into()));
+        assert_eq!(elements[1].r#for, Some("_hidden".into()));
+    });
+}
@@ -0,0 +1,77 @@
+#![feature(proc_macro_hygiene)]
+
+#[macro_use]
+#[cfg(feature = "uuid")]
+extern crate rocket;
+
+#[cfg(feature = "uuid")]
+mod uuid_test {
+    use uuid_::Uuid;
+
+    #[get("/item/<id>")]
+    fn get_item(id: Uuid) -> String {
+        id.to_string()
+    }
+
+    #[derive(FromForm)]
+    struct Search {
+        id: Uuid,
+    }
+
+    #[get("/search?<search..>")]
+    fn search(search: Search) -> String {
+        search.id.to_string()
+    }
+
+    mod tests {
+        use super::*;
+        use rocket::local::Client;
+        use rocket::http::Status;
+
+        const SIMPLE: &str = "c5aeb8fc4ad44a5e9a2e9e8a5e2dafef";
+        const HYPHENATED: &str = "c5aeb8fc-4ad4-4a5e-9a2e-9e8a5e2dafef";
+
+        #[test]
+        fn matches_hyphenated_uuid_in_path() {
+            let rocket = rocket::ignite().mount("/", routes![get_item]);
+            let client = Client::new(rocket).unwrap();
+
+            let mut response = client.get(format!("/item/{}", HYPHENATED)).dispatch();
+            assert_eq!(response.body_string(), Some(HYPHENATED.into()));
+        }
+
+        #[test]
+        fn matches_simple_uuid_in_path() {
+            let rocket = rocket::ignite().mount("/", routes![get_item]);
+            let client = Client::new(rocket).unwrap();
+
+            let mut response = client.get(format!("/item/{}", SIMPLE)).dispatch();
+            assert_eq!(response.body_string(), Some(HYPHENATED.into()));
+        }
+
+        #[test]
+        fn forwards_on_malformed_uuid() {
+            let rocket = rocket::ignite().mount("/", routes![get_item]);
+            let client = Client::new(rocket).unwrap();
+
+            let response = client.get("/item/not-a-uuid").dispatch();
+            assert_eq!(response.status(), Status::NotFound);
+        }
+
+        #[test]
+        fn parses_uuid_query_param_via_from_form() {
+            let rocket = rocket::ignite().mount("/", routes![search]);
+            let client = Client::new(rocket).unwrap();
+
+            let mut response = client.get(format!("/search?id={}", SIMPLE)).dispatch();
+            assert_eq!(response.body_string(), Some(HYPHENATED.into()));
+        }
+
+        #[test]
+        fn uri_macro_round_trips_uuid() {
+            let id = Uuid::parse_str(HYPHENATED).unwrap();
+            let uri = uri!(get_item: id);
+            assert_eq!(uri.path(), format!("/item/{}", HYPHENATED));
+        }
+    }
+}
@@ -1,3 +1,6 @@
+use std::collections::hash_map::DefaultHasher;
+use std::hash::{Hash, Hasher};
+
 use proc_macro::{TokenStream, Span};
 use devise::{syn, Spanned, Result, FromMeta};
 use crate::proc_macro2::TokenStream as TokenStream2;
@@ -5,7 +8,7 @@ use crate::proc_macro2::TokenStream as TokenStream2;
 use crate::http_codegen::Status;
 use crate::syn_ext::{syn_to_diag, IdentExt, ReturnTypeExt};
 use self::syn::{Attribute, parse::Parser};
-use crate::{CATCH_FN_PREFIX, CATCH_STRUCT_PREFIX};
+use crate::{CATCH_FN_PREFIX, CATCH_STRUCT_PREFIX, CATCHER_URI_MACRO_PREFIX};
 
 /// The raw, parsed `#[catch(code)]` attribute.
 #[derive(Debug, FromMeta)]
@@ -38,6 +41,35 @@ fn parse_params(args: TokenStream2, input: TokenStream) -> Result<CatchParams> {
     Ok(CatchParams { status: attribute.status, function })
 }
 
+fn generate_internal_catcher_uri_macro(catch: &CatchParams) -> TokenStream2 {
+    let mut hasher = DefaultHasher::new();
+    let catch_span = catch.function.span();
+    catch_span.source_file().path().hash(&mut hasher);
+    let line_column = catch_span.start();
+    line_column.line.hash(&mut hasher);
+    line_column.column.hash(&mut hasher);
+
+    let mut generated_macro_name = catch.function.sig.ident.prepend(CATCHER_URI_MACRO_PREFIX);
+    generated_macro_name.set_span(Span::call_site().into());
+    let inner_generated_macro_name = generated_macro_name.append(&hasher.finish().to_string());
+    let status_code = catch.status.0.code;
+
+    quote! {
+        #[doc(hidden)]
+        #[macro_export]
+        macro_rules! #inner_generated_macro_name {
+            () => {{
+                extern crate std;
+                extern crate rocket;
+                rocket::rocket_internal_catcher_uri!(#status_code)
+            }};
+        }
+
+        #[doc(hidden)]
+        pub use #inner_generated_macro_name as #generated_macro_name;
+    }
+}
+
 pub fn _catch(args: TokenStream, input: TokenStream) -> Result<TokenStream> {
     // Parse and validate all of the user's input.
     let catch = parse_params(TokenStream2::from(args), input)?;
@@ -49,6 +81,7 @@ pub fn _catch(args: TokenStream, input: TokenStream) -> Result<TokenStream> {
     let generated_fn_name = user_catcher_fn_name.prepend(CATCH_FN_PREFIX);
     let (vis, status) = (&catch.function.vis, &catch.status);
     let status_code = status.0.code;
+    let generated_internal_uri_macro = generate_internal_catcher_uri_macro(&catch);
 
     // Variables names we'll use and reuse.
     define_vars_and_mods!(req, catcher, response, Request, Response);
@@ -98,6 +131,9 @@ pub fn _catch(args: TokenStream, input: TokenStream) -> Result<TokenStream> {
                 code: #status_code,
                 handler: #generated_fn_name,
             };
+
+        /// Rocket code generated wrapping uri macro.
+        #generated_internal_uri_macro
     }.into())
 }
 
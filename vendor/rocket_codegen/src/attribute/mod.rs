@@ -1,3 +1,4 @@
 pub mod catch;
 pub mod route;
 pub mod segments;
+pub mod path_shape;
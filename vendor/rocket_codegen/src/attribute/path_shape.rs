@@ -0,0 +1,25 @@
+//! A small, compile-time approximation of `rocket::router::collider`'s
+//! request-time path matching, used by `routes_checked!` to decide whether
+//! two routes' static path segments could both match the same request.
+//!
+//! Unlike the real router, this only looks at the path (not the query), and
+//! never considers two paths of different segment counts to overlap; it
+//! exists to catch the common, obvious case of two same-shaped routes with
+//! the same explicit rank, not to replace the router's own collision check.
+
+use crate::attribute::segments::{Segment, Kind};
+
+/// Returns `true` if there's some concrete request path that both `a` and
+/// `b` could match, based solely on their static/dynamic segment pattern.
+pub fn paths_overlap(a: &[Segment], b: &[Segment]) -> bool {
+    a.len() == b.len() && a.iter().zip(b).all(|(x, y)| segments_overlap(x, y))
+}
+
+fn segments_overlap(a: &Segment, b: &Segment) -> bool {
+    match (a.kind, b.kind) {
+        // Two static segments only overlap if they're spelled identically;
+        // a dynamic segment (`Single` or `Multi`) overlaps anything.
+        (Kind::Static, Kind::Static) => a.name == b.name,
+        _ => true,
+    }
+}
@@ -3,26 +3,142 @@ use std::hash::{Hash, Hasher};
 
 use proc_macro::{TokenStream, Span};
 use crate::proc_macro2::TokenStream as TokenStream2;
-use devise::{syn, Spanned, SpanWrapped, Result, FromMeta, ext::TypeExt};
+use devise::{syn, Spanned, SpanWrapped, Result, FromMeta, MetaItem, ext::TypeExt};
 use indexmap::IndexSet;
 
 use crate::proc_macro_ext::{Diagnostics, StringLit};
 use crate::syn_ext::{syn_to_diag, IdentExt};
 use self::syn::{Attribute, parse::Parser};
 
-use crate::http_codegen::{Method, MediaType, RoutePath, DataSegment, Optional};
+use crate::http_codegen::{Method, MediaTypes, DataLimit, RoutePath, DataSegment, Optional};
 use crate::attribute::segments::{Source, Kind, Segment};
 use crate::{ROUTE_FN_PREFIX, ROUTE_STRUCT_PREFIX, URI_MACRO_PREFIX, ROCKET_PARAM_PREFIX};
 
+/// A parsed `rank` attribute parameter: either an explicit, literal rank or
+/// an offset to apply to whatever rank Rocket would otherwise compute for
+/// the route, written as `"auto"`, `"auto+N"`, or `"auto-N"`.
+#[derive(Debug, Copy, Clone)]
+enum Rank {
+    Literal(isize),
+    Offset(isize),
+}
+
+impl FromMeta for Rank {
+    fn from_meta(meta: MetaItem<'_>) -> Result<Self> {
+        if let Ok(n) = isize::from_meta(meta) {
+            return Ok(Rank::Literal(n));
+        }
+
+        let value = String::from_meta(meta)?;
+        let offset = value.strip_prefix("auto").ok_or_else(|| {
+            meta.value_span().error("invalid value: expected an integer or `\"auto\"`")
+                .help("relative ranks look like `\"auto+1\"` or `\"auto-2\"`")
+        })?;
+
+        if offset.is_empty() {
+            return Ok(Rank::Offset(0));
+        }
+
+        offset.parse::<isize>().map(Rank::Offset).map_err(|_| {
+            meta.value_span().error(format!("invalid rank offset: `{}`", value))
+                .help("relative ranks look like `\"auto+1\"` or `\"auto-2\"`")
+        })
+    }
+}
+
 /// The raw, parsed `#[route]` attribute.
-#[derive(Debug, FromMeta)]
+///
+/// Unlike `MethodRouteAttribute`, this isn't derived: a `#[route]` can name
+/// more than one method (`#[route(GET, HEAD, path = "/x")]`), and devise's
+/// `#[meta(naked)]` matcher only ever consumes a single leading bare item, so
+/// there's no field type that would let `#[derive(FromMeta)]` collect a
+/// variable-length run of them on its own.
+#[derive(Debug)]
 struct RouteAttribute {
-    #[meta(naked)]
-    method: SpanWrapped<Method>,
+    methods: Vec<SpanWrapped<Method>>,
     path: RoutePath,
     data: Option<SpanWrapped<DataSegment>>,
-    format: Option<MediaType>,
+    format: Option<MediaTypes>,
+    data_limit: Option<DataLimit>,
     rank: Option<isize>,
+    rank_offset: Option<isize>,
+    deny_payload: Option<bool>,
+    cors: Option<bool>,
+}
+
+impl FromMeta for RouteAttribute {
+    fn from_meta(meta: MetaItem<'_>) -> Result<Self> {
+        let span = meta.span();
+        let mut list = match meta {
+            MetaItem::List(list) => list.iter().peekable(),
+            _ => return Err(span.error("malformed attribute")
+                .help("expected syntax: #[route(GET, path = \"/\")]"))
+        };
+
+        // Consume a run of one or more leading bare methods: `GET, HEAD, ..`.
+        let mut methods: Vec<SpanWrapped<Method>> = vec![];
+        while let Some(true) = list.peek().map(|item| item.is_bare()) {
+            let item = list.next().expect("just peeked");
+            let method = SpanWrapped::<Method>::from_meta(item)?;
+            if let Some(prev) = methods.iter().find(|m| m.value == method.value) {
+                return Err(method.span.error(
+                        format!("duplicate HTTP method: `{}`", method.value.0))
+                    .span_note(prev.span, "previously specified here"));
+            }
+
+            methods.push(method);
+        }
+
+        if methods.is_empty() {
+            return Err(span.error("missing expected parameter: `method`")
+                .help("expected syntax: #[route(GET, path = \"/\")]"));
+        }
+
+        // Parse everything else as the usual, named attribute parameters.
+        let (mut path, mut data, mut format, mut rank) = (None, None, None, None);
+        let (mut data_limit, mut deny_payload, mut cors) = (None, None, None);
+        for meta in list {
+            let meta_span = meta.span();
+            let name = match meta.name() {
+                Some(name) => name.to_string(),
+                None => return Err(meta_span.error("expected key/value pair"))
+            };
+
+            let duplicate = || meta_span.error(format!("duplicate attribute parameter: {}", name));
+            match name.as_str() {
+                "path" if path.is_some() => return Err(duplicate()),
+                "path" => path = Some(RoutePath::from_meta(meta)?),
+                "data" if data.is_some() => return Err(duplicate()),
+                "data" => data = Some(SpanWrapped::<DataSegment>::from_meta(meta)?),
+                "format" if format.is_some() => return Err(duplicate()),
+                "format" => format = Some(MediaTypes::from_meta(meta)?),
+                "data_limit" if data_limit.is_some() => return Err(duplicate()),
+                "data_limit" => data_limit = Some(DataLimit::from_meta(meta)?),
+                "rank" if rank.is_some() => return Err(duplicate()),
+                "rank" => rank = Some(Rank::from_meta(meta)?),
+                "deny_payload" if deny_payload.is_some() => return Err(duplicate()),
+                "deny_payload" => deny_payload = Some(bool::from_meta(meta)?),
+                "cors" if cors.is_some() => return Err(duplicate()),
+                "cors" => cors = Some(bool::from_meta(meta)?),
+                _ => return Err(meta_span.error(
+                        format!("unexpected attribute parameter: `{}`", name)))
+            }
+        }
+
+        let path = path.ok_or_else(|| {
+            span.error("missing required attribute parameter: `path`")
+        })?;
+
+        let (rank, rank_offset) = match rank {
+            Some(Rank::Literal(n)) => (Some(n), None),
+            Some(Rank::Offset(n)) => (None, Some(n)),
+            None => (None, None),
+        };
+
+        Ok(RouteAttribute {
+            methods, path, data, format, data_limit, rank, rank_offset, deny_payload, cors
+        })
+    }
 }
 
 /// The raw, parsed `#[method]` (e.g, `get`, `put`, `post`, etc.) attribute.
@@ -31,8 +147,47 @@ struct MethodRouteAttribute {
     #[meta(naked)]
     path: RoutePath,
     data: Option<SpanWrapped<DataSegment>>,
-    format: Option<MediaType>,
-    rank: Option<isize>,
+    format: Option<MediaTypes>,
+    data_limit: Option<DataLimit>,
+    rank: Option<Rank>,
+    deny_payload: Option<bool>,
+    cors: Option<bool>,
+}
+
+/// The method, static path segments, and explicit rank of a single route, as
+/// declared by whichever route attribute (`#[route]` or a method shorthand
+/// like `#[get]`) is attached to `function`. A `#[route]` with more than one
+/// method contributes one entry per method.
+///
+/// This is used by `routes_checked!` to compare the routes it's given
+/// without running the rest of this attribute's code generation.
+pub(crate) fn route_shapes(function: &syn::ItemFn) -> Result<Vec<(Method, Vec<Segment>, Option<isize>)>> {
+    const METHOD_ATTRS: &[(&str, crate::http::Method)] = &[
+        ("get", crate::http::Method::Get), ("put", crate::http::Method::Put),
+        ("post", crate::http::Method::Post), ("delete", crate::http::Method::Delete),
+        ("head", crate::http::Method::Head), ("patch", crate::http::Method::Patch),
+        ("options", crate::http::Method::Options),
+    ];
+
+    if let Some(result) = RouteAttribute::from_attrs("route", &function.attrs) {
+        let RouteAttribute { methods, path, rank, .. } = result?;
+        return Ok(methods.into_iter().map(|m| (m.value, path.path.clone(), rank)).collect());
+    }
+
+    for &(name, method) in METHOD_ATTRS {
+        if let Some(result) = MethodRouteAttribute::from_attrs(name, &function.attrs) {
+            let attr = result?;
+            let rank = attr.rank.and_then(|r| match r {
+                Rank::Literal(n) => Some(n),
+                Rank::Offset(_) => None,
+            });
+
+            return Ok(vec![(Method(method), attr.path.path, rank)]);
+        }
+    }
+
+    Err(function.sig.ident.span().unstable().error(
+        "expected a function annotated with a route attribute, e.g. `#[get(\"/\")]`"))
 }
 
 /// This structure represents the parsed `route` attribute and associated items.
@@ -54,26 +209,61 @@ fn parse_route(attr: RouteAttribute, function: syn::ItemFn) -> Result<Route> {
     // Gather diagnostics as we proceed.
     let mut diags = Diagnostics::new();
 
-    // Emit a warning if a `data` param was supplied for non-payload methods.
+    // Diagnose a `data` param supplied on a method that doesn't support it.
+    // Under `deny_payload`, this is a hard error instead of just a warning.
     if let Some(ref data) = attr.data {
-        if !attr.method.0.supports_payload() {
-            let msg = format!("'{}' does not typically support payloads", attr.method.0);
-            data.full_span.warning("`data` used with non-payload-supporting method")
-                .span_note(attr.method.span, msg)
-                .emit()
+        if !attr.methods.iter().any(|m| m.0.supports_payload()) {
+            let methods = attr.methods.iter()
+                .map(|m| m.0.to_string())
+                .collect::<Vec<_>>()
+                .join(", ");
+
+            let msg = match attr.methods.len() {
+                1 => format!("'{}' does not typically support payloads", methods),
+                _ => format!("none of [{}] typically support payloads", methods),
+            };
+            if attr.deny_payload.unwrap_or(false) {
+                diags.push(data.full_span.error("`data` used with non-payload-supporting method")
+                    .span_note(attr.methods[0].span, msg));
+            } else {
+                data.full_span.warning("`data` used with non-payload-supporting method")
+                    .span_note(attr.methods[0].span, msg)
+                    .emit();
+            }
         }
     }
 
     // Collect all of the dynamic segments in an `IndexSet`, checking for dups.
     let mut segments: IndexSet<Segment> = IndexSet::new();
+    fn source_name(source: Source) -> &'static str {
+        match source {
+            Source::Path => "path",
+            Source::Query => "query",
+            Source::Data => "data",
+            Source::Unknown => "function",
+        }
+    }
+
+    // Note that `set` accumulates across every call in a given route, so a
+    // name reused across path, query, and data parameters (not just within
+    // one of them) is caught here too, not only within a single source.
     fn dup_check<I>(set: &mut IndexSet<Segment>, iter: I, diags: &mut Diagnostics)
         where I: Iterator<Item = Segment>
     {
         for segment in iter.filter(|s| s.kind != Kind::Static) {
             let span = segment.span;
+            let source = segment.source;
             if let Some(previous) = set.replace(segment) {
-                diags.push(span.error(format!("duplicate parameter: `{}`", previous.name))
-                    .span_note(previous.span, "previous parameter with the same name here"))
+                let msg = if previous.source == source {
+                    format!("duplicate parameter: `{}`", previous.name)
+                } else {
+                    format!("`{}` is used as both a {} and a {} parameter",
+                        previous.name, source_name(previous.source), source_name(source))
+                };
+
+                diags.push(span.error(msg)
+                    .span_note(previous.span, format!("previously declared as a {} parameter here",
+                        source_name(previous.source))))
             }
         }
     }
@@ -118,14 +308,54 @@ fn parse_route(attr: RouteAttribute, function: syn::ItemFn) -> Result<Route> {
         true => function.span()
     };
 
+    // Arguments that don't name a declared segment become request guards, so
+    // a typo'd segment or argument name fails far away with a confusing
+    // `FromRequest` trait-bound error. Cross-reference the two "unmatched"
+    // sets and, when a name is a likely typo of the other, say so.
+    let unmatched_args: Vec<_> = fn_segments.difference(&segments).collect();
     for missing in segments.difference(&fn_segments) {
-        diags.push(missing.span.error("unused dynamic parameter")
-            .span_note(span, format!("expected argument named `{}` here", missing.name)))
+        let mut e = missing.span.error("unused dynamic parameter")
+            .span_note(span, format!("expected argument named `{}` here", missing.name));
+
+        if let Some(arg) = unmatched_args.iter()
+            .filter(|arg| levenshtein_distance(&arg.name, &missing.name) <= 2)
+            .min_by_key(|arg| levenshtein_distance(&arg.name, &missing.name))
+        {
+            e = e.span_note(arg.span, format!(
+                "argument `{}` doesn't match any declared parameter: did you mean `{}`?",
+                arg.name, missing.name));
+        }
+
+        diags.push(e);
     }
 
     diags.head_err_or(Route { attribute: attr, function, inputs, segments })
 }
 
+/// The number of single-character edits needed to turn `a` into `b`. Used
+/// only to guess whether a misspelled argument or segment name was probably
+/// meant to be the other.
+fn levenshtein_distance(a: &str, b: &str) -> usize {
+    let (a, b): (Vec<char>, Vec<char>) = (a.chars().collect(), b.chars().collect());
+    let mut row: Vec<usize> = (0..=b.len()).collect();
+    for i in 1..=a.len() {
+        let mut prev = row[0];
+        row[0] = i;
+        for j in 1..=b.len() {
+            let cur = row[j];
+            row[j] = if a[i - 1] == b[j - 1] {
+                prev
+            } else {
+                1 + prev.min(row[j]).min(row[j - 1])
+            };
+
+            prev = cur;
+        }
+    }
+
+    row[b.len()]
+}
+
 fn param_expr(seg: &Segment, ident: &syn::Ident, ty: &syn::Type) -> TokenStream2 {
     define_vars_and_mods!(req, data, error, log, request, _None, _Some, _Ok, _Err, Outcome);
     let i = seg.index.expect("dynamic parameters must be indexed");
@@ -205,9 +435,10 @@ fn data_expr(ident: &syn::Ident, ty: &syn::Type) -> TokenStream2 {
 
 fn query_exprs(route: &Route) -> Option<TokenStream2> {
     define_vars_and_mods!(_None, _Some, _Ok, _Err, _Option);
-    define_vars_and_mods!(data, trail, log, request, req, Outcome, SmallVec, Query);
+    define_vars_and_mods!(data, trail, log, request, req, Outcome, SmallVec, Query, RawStr);
     let query_segments = route.attribute.path.query.as_ref()?;
     let (mut decls, mut matchers, mut builders) = (vec![], vec![], vec![]);
+    let mut failure_idents = vec![];
     for segment in query_segments {
         let name = &segment.name;
         let (ident, ty, span) = if segment.kind != Kind::Static {
@@ -222,10 +453,14 @@ fn query_exprs(route: &Route) -> Option<TokenStream2> {
             (None, None, segment.span.into())
         };
 
+        let failed = ident.map(|ident| ident.append("_failed"));
+
         let decl = match segment.kind {
             Kind::Single => quote_spanned! { span =>
                 #[allow(non_snake_case)]
                 let mut #ident: #_Option<#ty> = #_None;
+                #[allow(non_snake_case)]
+                let mut #failed = false;
             },
             Kind::Multi => quote_spanned! { span =>
                 #[allow(non_snake_case)]
@@ -236,17 +471,16 @@ fn query_exprs(route: &Route) -> Option<TokenStream2> {
 
         let matcher = match segment.kind {
             Kind::Single => quote_spanned! { span =>
-                (_, #name, __v) => {
+                (__raw, #name, __v) => {
                     #[allow(unreachable_patterns, unreachable_code)]
-                    let __v = match <#ty as #request::FromFormValue>::from_form_value(__v) {
-                        #_Ok(__v) => __v,
+                    match <#ty as #request::FromFormValue>::from_form_value(__v) {
+                        #_Ok(__v) => #ident = #_Some(__v),
                         #_Err(__e) => {
                             #log::warn_(&format!("Failed to parse '{}': {:?}", #name, __e));
-                            return #Outcome::Forward(#data);
+                            __query_failures.push((#name.to_string(), __raw.to_string()));
+                            #failed = true;
                         }
-                    };
-
-                    #ident = #_Some(__v);
+                    }
                 }
             },
             Kind::Static => quote! {
@@ -258,36 +492,69 @@ fn query_exprs(route: &Route) -> Option<TokenStream2> {
         };
 
         let builder = match segment.kind {
-            Kind::Single => quote_spanned! { span =>
-                #[allow(non_snake_case)]
-                let #ident = match #ident.or_else(<#ty as #request::FromFormValue>::default) {
-                    #_Some(__v) => __v,
-                    #_None => {
-                        #log::warn_(&format!("Missing required query parameter '{}'.", #name));
-                        return #Outcome::Forward(#data);
-                    }
+            Kind::Single => {
+                let missing = match &segment.default {
+                    Some(default) => quote_spanned! { span =>
+                        match <#ty as #request::FromFormValue>::from_form_value(#RawStr::from_str(#default)) {
+                            #_Ok(__v) => #_Some(__v),
+                            #_Err(__e) => {
+                                #log::warn_(&format!(
+                                    "Failed to parse default value '{}' for '{}': {:?}",
+                                    #default, #name, __e));
+                                __query_failures.push((#name.to_string(), #default.to_string()));
+                                #_None
+                            }
+                        }
+                    },
+                    None => quote_spanned! { span =>
+                        match <#ty as #request::FromFormValue>::default() {
+                            #_Some(__v) => #_Some(__v),
+                            #_None => {
+                                #log::warn_(&format!("Missing required query parameter '{}'.", #name));
+                                __query_failures.push((#name.to_string(), String::new()));
+                                #_None
+                            }
+                        }
+                    },
                 };
+
+                quote_spanned! { span =>
+                    #[allow(non_snake_case)]
+                    let #ident: #_Option<#ty> = match #ident {
+                        #_Some(__v) => #_Some(__v),
+                        #_None if #failed => #_None,
+                        #_None => #missing,
+                    };
+                }
             },
             Kind::Multi => quote_spanned! { span =>
                 #[allow(non_snake_case)]
-                let #ident = match <#ty as #request::FromQuery>::from_query(#Query(&#trail)) {
-                    #_Ok(__v) => __v,
+                let #ident: #_Option<#ty> = match <#ty as #request::FromQuery>::from_query(#Query(&#trail)) {
+                    #_Ok(__v) => #_Some(__v),
                     #_Err(__e) => {
                         #log::warn_(&format!("Failed to parse '{}': {:?}", #name, __e));
-                        return #Outcome::Forward(#data);
+                        __query_failures.push((#name.to_string(), String::new()));
+                        #_None
                     }
                 };
             },
             Kind::Static => quote!()
         };
 
+        if segment.kind != Kind::Static {
+            failure_idents.push(ident.cloned());
+        }
+
         decls.push(decl);
         matchers.push(matcher);
         builders.push(builder);
     }
 
+    let failure_idents: Vec<_> = failure_idents.into_iter().flatten().collect();
     matchers.push(quote!(_ => continue));
     Some(quote! {
+        let mut __query_failures: Vec<(String, String)> = Vec::new();
+
         #(#decls)*
 
         if let #_Some(__items) = #req.raw_query_items() {
@@ -305,6 +572,16 @@ fn query_exprs(route: &Route) -> Option<TokenStream2> {
             #[allow(unreachable_patterns, unreachable_code)]
             #builders
         )*
+
+        if !__query_failures.is_empty() {
+            #req.local_cache(|| #request::QueryParamFailures(__query_failures));
+            return #Outcome::Forward(#data);
+        }
+
+        #(
+            #[allow(non_snake_case)]
+            let #failure_idents = #failure_idents.expect("checked via __query_failures above");
+        )*
     })
 }
 
@@ -411,10 +688,26 @@ fn codegen_route(route: Route) -> Result<TokenStream> {
     let generated_internal_uri_macro = generate_internal_uri_macro(&route);
     let generated_respond_expr = generate_respond_expr(&route);
 
-    let method = route.attribute.method;
     let path = route.attribute.path.origin.0.to_string();
     let rank = Optional(route.attribute.rank);
+    let rank_offset = Optional(route.attribute.rank_offset);
     let format = Optional(route.attribute.format);
+    let data_limit = Optional(route.attribute.data_limit);
+    let cors = Optional(route.attribute.cors);
+    let num_methods = route.attribute.methods.len();
+    let route_infos = route.attribute.methods.iter().map(|method| quote! {
+        #StaticRouteInfo {
+            name: stringify!(#user_handler_fn_name),
+            method: #method,
+            path: #path,
+            handler: #generated_fn_name,
+            format: #format,
+            data_limit: #data_limit,
+            cors: #cors,
+            rank: #rank,
+            rank_offset: #rank_offset,
+        }
+    });
 
     Ok(quote! {
         #user_handler_fn
@@ -434,17 +727,11 @@ fn codegen_route(route: Route) -> Result<TokenStream> {
         /// Rocket code generated wrapping URI macro.
         #generated_internal_uri_macro
 
-        /// Rocket code generated static route info.
+        /// Rocket code generated static route info. One entry per declared
+        /// HTTP method; `routes!` mounts a `Route` for each of them.
         #[allow(non_upper_case_globals)]
-        #vis static #generated_struct_name: #StaticRouteInfo =
-            #StaticRouteInfo {
-                name: stringify!(#user_handler_fn_name),
-                method: #method,
-                path: #path,
-                handler: #generated_fn_name,
-                format: #format,
-                rank: #rank,
-            };
+        #vis static #generated_struct_name: [#StaticRouteInfo; #num_methods] =
+            [#(#route_infos),*];
     }.into())
 }
 
@@ -484,14 +771,24 @@ fn incomplete_route(
         None => return Err(Span::call_site().error("internal error: bad attribute"))
     };
 
+    let (rank, rank_offset) = match method_attribute.rank {
+        Some(Rank::Literal(n)) => (Some(n), None),
+        Some(Rank::Offset(n)) => (None, Some(n)),
+        None => (None, None),
+    };
+
     let attribute = RouteAttribute {
-        method: SpanWrapped {
+        methods: vec![SpanWrapped {
             full_span: method_span, span: method_span, value: Method(method)
-        },
+        }],
         path: method_attribute.path,
         data: method_attribute.data,
         format: method_attribute.format,
-        rank: method_attribute.rank,
+        data_limit: method_attribute.data_limit,
+        rank,
+        rank_offset,
+        deny_payload: method_attribute.deny_payload,
+        cors: method_attribute.cors,
     };
 
     codegen_route(parse_route(attribute, function)?)
@@ -16,6 +16,7 @@ pub struct Segment {
     pub source: Source,
     pub name: String,
     pub index: Option<usize>,
+    pub default: Option<String>,
 }
 
 impl Segment {
@@ -27,7 +28,8 @@ impl Segment {
         };
 
         let (kind, index) = (segment.kind, segment.index);
-        Segment { span, kind, source, index, name: segment.name.into_owned() }
+        let default = segment.default.map(|c| c.into_owned());
+        Segment { span, kind, source, index, default, name: segment.name.into_owned() }
     }
 }
 
@@ -39,6 +41,7 @@ impl From<&syn::Ident> for Segment {
             span: ident.span().unstable(),
             name: ident.to_string(),
             index: None,
+            default: None,
         }
     }
 }
@@ -0,0 +1,18 @@
+use proc_macro::TokenStream;
+use devise::{syn, Result};
+use self::syn::LitInt;
+
+use crate::syn_ext::syn_to_diag;
+use crate::bang::prefix_last_segment;
+use crate::CATCHER_URI_MACRO_PREFIX;
+
+pub fn _catcher_uri_macro(input: TokenStream) -> Result<TokenStream> {
+    let mut path = syn::parse::<syn::Path>(input).map_err(syn_to_diag)?;
+    prefix_last_segment(&mut path, CATCHER_URI_MACRO_PREFIX);
+    Ok(quote!(#path!()).into())
+}
+
+pub fn _catcher_uri_internal_macro(input: TokenStream) -> Result<TokenStream> {
+    let status_code = syn::parse::<LitInt>(input).map_err(syn_to_diag)?;
+    Ok(quote!(::rocket::CatcherUri::new(#status_code)).into())
+}
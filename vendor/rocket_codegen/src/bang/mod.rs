@@ -8,7 +8,9 @@ use crate::{ROUTE_STRUCT_PREFIX, CATCH_STRUCT_PREFIX};
 
 mod uri;
 mod uri_parsing;
+mod catcher_uri;
 mod test_guide;
+mod routes_checked;
 
 pub fn prefix_last_segment(path: &mut Path, prefix: &str) {
     let mut last_seg = path.segments.last_mut().expect("syn::Path has segments");
@@ -46,8 +48,32 @@ fn prefixed_vec(prefix: &str, input: TokenStream, ty: TokenStream2) -> TokenStre
     }).into()
 }
 
+fn _routes_macro(input: TokenStream) -> Result<TokenStream2> {
+    // Parse a comma-separated list of paths.
+    let mut paths = <Punctuated<Path, Comma>>::parse_terminated
+        .parse(input)
+        .map_err(syn_to_diag)?;
+
+    // Prefix the last segment in each path with the generated struct prefix.
+    paths.iter_mut().for_each(|p| prefix_last_segment(p, ROUTE_STRUCT_PREFIX));
+
+    // Each path names a `[StaticRouteInfo; N]` (one entry per HTTP method the
+    // route was declared with); flatten all of them into one `Vec<Route>`.
+    let route_lists = paths.iter()
+        .map(|path| quote_spanned!(path.span().into() => <Vec<::rocket::Route>>::from(&#path)));
+
+    Ok(quote! {{
+        let mut __vector: Vec<::rocket::Route> = Vec::new();
+        #(__vector.extend(#route_lists);)*
+        __vector
+    }})
+}
+
 pub fn routes_macro(input: TokenStream) -> TokenStream {
-    prefixed_vec(ROUTE_STRUCT_PREFIX, input, quote!(::rocket::Route))
+    _routes_macro(input)
+        .map_err(|diag| diag.emit())
+        .unwrap_or_else(|_| quote!(Vec::<::rocket::Route>::new()))
+        .into()
 }
 
 pub fn catchers_macro(input: TokenStream) -> TokenStream {
@@ -66,8 +92,24 @@ pub fn uri_internal_macro(input: TokenStream) -> TokenStream {
         .unwrap_or_else(|_| quote!(()).into())
 }
 
+pub fn catcher_uri_macro(input: TokenStream) -> TokenStream {
+    catcher_uri::_catcher_uri_macro(input)
+        .map_err(|diag| diag.emit())
+        .unwrap_or_else(|_| quote!(()).into())
+}
+
+pub fn catcher_uri_internal_macro(input: TokenStream) -> TokenStream {
+    catcher_uri::_catcher_uri_internal_macro(input)
+        .map_err(|diag| diag.emit())
+        .unwrap_or_else(|_| quote!(()).into())
+}
+
 pub fn guide_tests_internal(input: TokenStream) -> TokenStream {
     test_guide::_macro(input)
         .map_err(|diag| diag.emit())
         .unwrap_or_else(|_| quote!(()).into())
 }
+
+pub fn routes_checked_macro(input: TokenStream) -> TokenStream {
+    routes_checked::routes_checked_macro(input)
+}
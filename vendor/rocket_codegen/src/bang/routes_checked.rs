@@ -0,0 +1,81 @@
+use proc_macro::TokenStream;
+use crate::proc_macro2::TokenStream as TokenStream2;
+
+use devise::{syn, Spanned, Result};
+use self::syn::parse::{Parse, ParseStream};
+use self::syn::ItemFn;
+
+use crate::proc_macro_ext::Diagnostics;
+use crate::syn_ext::syn_to_diag;
+use crate::attribute::route::route_shapes;
+use crate::attribute::path_shape::paths_overlap;
+
+/// A single declared method/path/rank, traced back to the function that
+/// declared it, so conflicts can be reported against the handler's name.
+struct RouteShape<'f> {
+    function: &'f ItemFn,
+    method: crate::http_codegen::Method,
+    path: Vec<crate::attribute::segments::Segment>,
+    rank: Option<isize>,
+}
+
+struct Items(Vec<ItemFn>);
+
+impl Parse for Items {
+    fn parse(input: ParseStream<'_>) -> syn::parse::Result<Self> {
+        let mut items = vec![];
+        while !input.is_empty() {
+            items.push(input.parse::<ItemFn>()?);
+        }
+
+        Ok(Items(items))
+    }
+}
+
+fn _routes_checked_macro(input: TokenStream) -> Result<TokenStream2> {
+    let Items(functions) = syn::parse::<Items>(input).map_err(syn_to_diag)?;
+
+    // Expand every function's route attribute into its declared shapes,
+    // without running the rest of that attribute's code generation.
+    let mut shapes = vec![];
+    for function in &functions {
+        for (method, path, rank) in route_shapes(function)? {
+            shapes.push(RouteShape { function, method, path, rank });
+        }
+    }
+
+    // Two shapes only conflict if they'd be mounted with the same method and
+    // the same explicit rank, and their paths could match the same request;
+    // an implicit (absent) rank is never compared, since Rocket would assign
+    // each a different rank based on specificity at mount time.
+    let mut diags = Diagnostics::new();
+    for (i, a) in shapes.iter().enumerate() {
+        for b in &shapes[(i + 1)..] {
+            let conflicts = a.method == b.method
+                && a.rank.is_some()
+                && a.rank == b.rank
+                && paths_overlap(&a.path, &b.path);
+
+            if conflicts {
+                let a_name = &a.function.sig.ident;
+                let b_name = &b.function.sig.ident;
+                diags.push(b_name.span().unstable()
+                    .error(format!("route collides with `{}`", a_name))
+                    .span_note(a_name.span().unstable(), "previously declared here"));
+            }
+        }
+    }
+
+    diags.head_err_or(())?;
+
+    // No conflicts: re-emit the functions unchanged so their own route
+    // attributes still expand normally.
+    Ok(quote!(#(#functions)*))
+}
+
+pub fn routes_checked_macro(input: TokenStream) -> TokenStream {
+    _routes_checked_macro(input)
+        .map_err(|diag| diag.emit())
+        .unwrap_or_else(|_| quote!())
+        .into()
+}
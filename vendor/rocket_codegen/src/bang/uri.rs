@@ -204,7 +204,7 @@ fn explode_query<'a, I: Iterator<Item = (&'a Ident, &'a Type, &'a ArgExpr)>>(
 // (`<param>`) with `param=<param>`.
 fn build_origin(internal: &InternalUriParams) -> Origin<'static> {
     let mount_point = internal.uri_params.mount_point.as_ref()
-        .map(|origin| origin.path())
+        .map(|prefix| prefix.path())
         .unwrap_or("");
 
     let path = format!("{}/{}", mount_point, internal.route_uri.path());
@@ -223,8 +223,29 @@ pub fn _uri_internal_macro(input: TokenStream) -> Result<TokenStream> {
     let path = explode_path(&uri, &mut bindings, path_params);
     let query = Optional(explode_query(&uri, &mut bindings, query_params));
 
+    let origin_expr = quote!(#uri_mod::UriArguments { path: #path, query: #query, }.into_origin());
+    let uri_expr = match internal.uri_params.mount_point.as_ref() {
+        // A leading absolute URI mount point: wrap the generated origin in an
+        // `Absolute`, reusing the scheme and authority already parsed out of
+        // the prefix literal when the macro invocation was parsed.
+        Some(Prefix::Absolute(absolute)) => {
+            let scheme = absolute.scheme();
+            let authority = absolute.authority()
+                .expect("parsing rejects an absolute prefix without an authority")
+                .to_string();
+
+            quote!(#uri_mod::Absolute::new(
+                #scheme,
+                Some(#uri_mod::Authority::parse(#authority)
+                    .expect("generated authority is always valid")),
+                Some(#origin_expr)
+            ))
+        }
+        _ => origin_expr,
+    };
+
      Ok(quote!({
          #(#bindings)*
-         #uri_mod::UriArguments { path: #path, query: #query, }.into_origin()
+         #uri_expr
      }).into())
 }
@@ -9,7 +9,7 @@ use self::syn::{Expr, Ident, LitStr, Path, Token, Type};
 use self::syn::parse::{self, Parse, ParseStream};
 use self::syn::punctuated::Punctuated;
 
-use crate::http::{uri::Origin, ext::IntoOwned};
+use crate::http::{uri::{Origin, Absolute}, ext::IntoOwned};
 use indexmap::IndexMap;
 
 #[derive(Debug)]
@@ -30,6 +30,27 @@ pub enum Args {
     Named(Punctuated<Arg, Token![,]>),
 }
 
+/// A leading prefix to a `uri!` invocation: either a static, absolute origin
+/// (`"/mount/point"`) or a static, absolute URI with a scheme and authority
+/// (`"https://example.com:8000/mount"`), the latter producing an `Absolute`
+/// URI instead of an `Origin` one.
+#[derive(Debug)]
+pub enum Prefix {
+    Origin(Origin<'static>),
+    Absolute(Absolute<'static>),
+}
+
+impl Prefix {
+    /// The path to prepend to the route's path: the prefix's origin path, or
+    /// `""` if the prefix is an absolute URI with no origin part.
+    pub fn path(&self) -> &str {
+        match self {
+            Prefix::Origin(origin) => origin.path(),
+            Prefix::Absolute(absolute) => absolute.origin().map(|o| o.path()).unwrap_or(""),
+        }
+    }
+}
+
 // For an invocation that looks like:
 //  uri!("/mount/point", this::route: e1, e2, e3);
 //       ^-------------| ^----------| ^---------|
@@ -37,7 +58,7 @@ pub enum Args {
 //                      uri_params.route_path
 #[derive(Debug)]
 pub struct UriParams {
-    pub mount_point: Option<Origin<'static>>,
+    pub mount_point: Option<Prefix>,
     pub route_path: Path,
     pub arguments: Args,
 }
@@ -117,11 +138,25 @@ impl Parse for UriParams {
         // Parse the mount point and suffixing ',', if any.
         let mount_point = if input.peek(LitStr) {
             let string = input.parse::<LitStr>()?;
-            let mount_point = Origin::parse_owned(string.value()).map_err(|_| {
+            let value = string.value();
+            let invalid_prefix = || {
                 // TODO(proc_macro): use error, add example as a help
-                parse::Error::new(string.span(), "invalid mount point; \
-                    mount points must be static, absolute URIs: `/example`")
-            })?;
+                parse::Error::new(string.span(), "invalid mount point; mount points must be \
+                    static, absolute URIs: `/example` or `https://example.com`")
+            };
+
+            // A prefix starting with `/` is an origin; otherwise, it must be
+            // an absolute URI with a scheme and authority, such as a mount
+            // point meant to produce an `Absolute` URI from `uri!`.
+            let prefix = if value.starts_with('/') {
+                Origin::parse_owned(value).map(Prefix::Origin)
+                    .map_err(|_| invalid_prefix())?
+            } else {
+                Absolute::parse_owned(value).ok()
+                    .filter(|absolute| absolute.authority().is_some())
+                    .map(Prefix::Absolute)
+                    .ok_or_else(invalid_prefix)?
+            };
 
             if !input.peek(Token![,]) && input.cursor().eof() {
                 return err(string.span().unstable(), "unexpected end of input: \
@@ -129,7 +164,7 @@ impl Parse for UriParams {
             }
 
             input.parse::<Token![,]>()?;
-            Some(mount_point)
+            Some(prefix)
         } else {
             None
         };
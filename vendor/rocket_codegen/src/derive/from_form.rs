@@ -1,14 +1,100 @@
 use proc_macro::{Span, TokenStream};
-use devise::{*, ext::{TypeExt, Split3}};
+use devise::{*, proc_macro2::TokenStream as TokenStream2, ext::{TypeExt, Split3}};
 
 #[derive(FromMeta)]
 pub struct Form {
-    pub field: FormField,
+    pub field: Option<FormField>,
+    pub default: Option<DefaultExpr>,
+    pub validate: Option<ValidateExpr>,
+    /// Matches this field's name against the form case-insensitively, even
+    /// if the container isn't itself `#[form(case_insensitive)]`.
+    pub case_insensitive: Option<bool>,
+}
+
+/// Container-level `#[form(...)]` attribute.
+#[derive(FromMeta)]
+struct FormContainer {
+    /// Required on enums: names the field whose value selects the variant.
+    /// If every variant is nullary, that value is matched directly against
+    /// each variant's name; otherwise, it selects which variant's named
+    /// fields are parsed from the rest of the form.
+    tag: Option<String>,
+    /// Matches every field's name against the form case-insensitively.
+    case_insensitive: Option<bool>,
+    /// In strict mode, collects every unexpected field into
+    /// `FormParseError::UnknownFields` instead of returning
+    /// `FormParseError::Unknown` for the first one encountered. Has no effect
+    /// in lenient mode.
+    collect_unknown: Option<bool>,
+}
+
+impl FormContainer {
+    fn case_insensitive(gen: &DeriveGenerator) -> Result<bool> {
+        match FormContainer::from_attrs("form", &gen.input.attrs) {
+            Some(result) => Ok(result?.case_insensitive.unwrap_or(false)),
+            None => Ok(false),
+        }
+    }
+
+    fn collect_unknown(gen: &DeriveGenerator) -> Result<bool> {
+        match FormContainer::from_attrs("form", &gen.input.attrs) {
+            Some(result) => Ok(result?.collect_unknown.unwrap_or(false)),
+            None => Ok(false),
+        }
+    }
 }
 
 pub struct FormField {
     pub span: Span,
-    pub name: String
+    /// The names the field matches in the incoming form. Always at least
+    /// one; more than one when the field has aliases.
+    pub names: Vec<String>,
+}
+
+/// A field's `#[form(default = ...)]` fallback, used when the field is
+/// absent from the incoming form. A bare literal (`default = 42`) is used
+/// as-is; anything else (`default = "MyType::default()"`) must be a string
+/// literal, since attribute syntax doesn't otherwise admit arbitrary
+/// expressions, and is parsed into an `Expr` here.
+pub struct DefaultExpr {
+    pub span: Span,
+    pub expr: syn::Expr,
+}
+
+impl FromMeta for DefaultExpr {
+    fn from_meta(meta: MetaItem<'_>) -> Result<Self> {
+        let span = meta.value_span();
+        let lit = meta.lit()?.clone();
+        let expr = match lit {
+            syn::Lit::Str(ref s) => syn::parse_str(&s.value())
+                .map_err(|_| span.error("invalid expression: expected a Rust expression"))?,
+            lit => syn::Expr::Lit(syn::ExprLit { attrs: vec![], lit }),
+        };
+
+        Ok(DefaultExpr { span, expr })
+    }
+}
+
+/// A field's `#[form(validate = "path::to::fn")]` validator, called with a
+/// reference to the field's already-parsed value once it's available. Like
+/// [`DefaultExpr`], the validator is given as a string literal (attribute
+/// syntax doesn't otherwise admit a bare path on the right of `=`) and parsed
+/// into an `Expr` here; it's expected to evaluate to a `fn(&T) -> Result<(),
+/// E>` for the field's type `T`.
+pub struct ValidateExpr {
+    pub span: Span,
+    pub expr: syn::Expr,
+}
+
+impl FromMeta for ValidateExpr {
+    fn from_meta(meta: MetaItem<'_>) -> Result<Self> {
+        let span = meta.value_span();
+        let string = String::from_meta(meta)?;
+        let expr = syn::parse_str(&string)
+            .map_err(|_| span.error("invalid expression: expected a Rust expression"))?;
+
+        Ok(ValidateExpr { span, expr })
+    }
 }
 
 fn is_valid_field_name(s: &str) -> bool {
@@ -23,13 +109,19 @@ fn is_valid_field_name(s: &str) -> bool {
 }
 
 impl FromMeta for FormField {
+    // `#[form(field = "a, b")]` names `a` as an alias for `b`: the field
+    // matches whichever of the comma-separated names appears in the
+    // incoming form. (Attribute syntax only admits a single literal on the
+    // right of `=`, so a list like `field = ["a", "b"]` isn't available;
+    // a comma-separated string is the closest valid equivalent.)
     fn from_meta(meta: MetaItem<'_>) -> Result<Self> {
         let string = String::from_meta(meta)?;
-        if !is_valid_field_name(&string) {
+        let names: Vec<String> = string.split(',').map(|s| s.trim().to_string()).collect();
+        if names.iter().any(|name| !is_valid_field_name(name)) {
             return Err(meta.value_span().error("invalid form field name"));
         }
 
-        Ok(FormField { span: meta.value_span(), name: string })
+        Ok(FormField { span: meta.value_span(), names })
     }
 }
 
@@ -43,26 +135,134 @@ fn validate_struct(gen: &DeriveGenerator, data: Struct<'_>) -> Result<()> {
         let id = field.ident.as_ref().expect("named field");
         let field = match Form::from_attrs("form", &field.attrs) {
             Some(result) => result?.field,
-            None => FormField { span: Spanned::span(&id), name: id.to_string() }
-        };
+            None => None,
+        }.unwrap_or_else(|| FormField { span: Spanned::span(&id), names: vec![id.to_string()] });
 
-        if let Some(span) = names.get(&field.name) {
-            return Err(field.span.error("duplicate field name")
-                       .span_note(*span, "previous definition here"));
+        for name in field.names {
+            if let Some(span) = names.get(&name) {
+                return Err(field.span.error("duplicate field name")
+                           .span_note(*span, "previous definition here"));
+            }
+
+            names.insert(name, field.span);
         }
+    }
+
+    Ok(())
+}
+
+fn validate_enum(gen: &DeriveGenerator, data: Enum<'_>) -> Result<()> {
+    let tag = FormContainer::from_attrs("form", &gen.input.attrs).transpose()?.and_then(|c| c.tag);
+    if tag.is_none() {
+        return Err(gen.input.span().error(
+            "enums deriving `FromForm` require a `#[form(tag = \"...\")]` attribute"
+        ));
+    }
+
+    // Nullary variants (no fields at all) are matched directly against the
+    // tag field's raw value, much like a `FromFormValue` derive would; any
+    // other shape needs named fields of its own to parse the rest of the
+    // form into.
+    if data.variants().all(|v| v.fields().is_empty()) {
+        return Ok(());
+    }
 
-        names.insert(field.name, field.span);
+    for variant in data.variants() {
+        if !variant.fields().are_named() {
+            return Err(variant.span().error("variants must have named fields")
+                .help("or make every variant nullary to match a single discriminant field"));
+        }
     }
 
     Ok(())
 }
 
+// Generates, for a single set of named fields, the `let mut <field> = None;`
+// constructors, the `<name> => { <field> = Some(...) }` match arms, and the
+// `<field>: <field>.unwrap_or(...)` struct-literal builders used to parse
+// those fields out of a form. Shared between the plain struct case and each
+// variant's fields in the tagged-enum case below.
+//
+// `container_case_insensitive` is the container's `#[form(case_insensitive)]`
+// setting; a field matches case-insensitively if that's set or if the field
+// has its own `#[form(case_insensitive)]` attribute.
+fn fields_mapper(
+    form_error: &TokenStream2,
+    container_case_insensitive: bool,
+    fields: Fields<'_>
+) -> Result<(Vec<TokenStream2>, Vec<TokenStream2>, Vec<TokenStream2>)> {
+    define_vars_and_mods!(_None, _Some, _Err);
+    Ok(fields.iter().map(|field| {
+        let (ident, span) = (&field.ident, field.span().into());
+        let default_name = ident.as_ref().expect("named").to_string();
+        let form_attr = Form::from_attrs("form", &field.attrs).transpose()?;
+        let names = form_attr.as_ref()
+            .and_then(|form| form.field.as_ref())
+            .map(|field| field.names.clone())
+            .unwrap_or_else(|| vec![default_name]);
+        let primary_name = names[0].clone();
+        let case_insensitive = container_case_insensitive
+            || form_attr.as_ref().and_then(|form| form.case_insensitive).unwrap_or(false);
+
+        let ty = field.ty.with_stripped_lifetimes();
+        let ty = quote_spanned! {
+            span => <#ty as ::rocket::request::FromFormValue>
+        };
+
+        let constructor = quote_spanned!(span => let mut #ident = #_None;);
+
+        let validate = form_attr.as_ref().and_then(|form| form.validate.as_ref()).map(|v| {
+            let (validate_span, expr) = (v.span, &v.expr);
+            quote_spanned! { validate_span =>
+                if let ::std::result::Result::Err(_) = (#expr)(&__val) {
+                    return #_Err(#form_error::BadValue(__k, __v));
+                }
+            }
+        });
+
+        let parse_and_validate = quote_spanned! { span =>
+            let __val = #ty::from_form_value(__v)
+                .map_err(|_| #form_error::BadValue(__k, __v))?;
+            #validate
+            #ident = #_Some(__val);
+        };
+
+        let matcher = if case_insensitive {
+            quote_spanned! { span =>
+                _ if #(__k.as_uncased_str() == #names)||* => { #parse_and_validate },
+            }
+        } else {
+            quote_spanned! { span =>
+                #(#names)|* => { #parse_and_validate },
+            }
+        };
+
+        let builder = match form_attr.and_then(|form| form.default) {
+            Some(DefaultExpr { span: default_span, expr: default }) => {
+                let default = quote_spanned!(default_span => #default);
+                quote_spanned! { span =>
+                    #ident: match #ident.or_else(#ty::default) {
+                        #_Some(__v) => __v,
+                        #_None => #default,
+                    },
+                }
+            },
+            None => quote_spanned! { span =>
+                #ident: #ident.or_else(#ty::default)
+                    .ok_or_else(|| #form_error::Missing(#primary_name.into()))?,
+            },
+        };
+
+        Ok((constructor, matcher, builder))
+    }).collect::<Result<Vec<_>>>()?.into_iter().split3())
+}
+
 pub fn derive_from_form(input: TokenStream) -> TokenStream {
     let form_error = quote!(::rocket::request::FormParseError);
     DeriveGenerator::build_for(input, quote!(impl<'__f> ::rocket::request::FromForm<'__f>))
         .generic_support(GenericSupport::Lifetime | GenericSupport::Type)
         .replace_generic(0, 0)
-        .data_support(DataSupport::NamedStruct)
+        .data_support(DataSupport::NamedStruct | DataSupport::Enum)
         .map_type_generic(|_, ident, _| quote! {
             #ident : ::rocket::request::FromFormValue<'__f>
         })
@@ -71,6 +271,7 @@ pub fn derive_from_form(input: TokenStream) -> TokenStream {
             false => Ok(())
         })
         .validate_struct(validate_struct)
+        .validate_enum(validate_enum)
         .function(|_, inner| quote! {
             type Error = ::rocket::request::FormParseError<'__f>;
 
@@ -81,49 +282,167 @@ pub fn derive_from_form(input: TokenStream) -> TokenStream {
                 #inner
             }
         })
-        .try_map_fields(move |_, fields| {
-            define_vars_and_mods!(_None, _Some, _Ok, _Err);
-            let (constructors, matchers, builders) = fields.iter().map(|field| {
-                let (ident, span) = (&field.ident, field.span().into());
-                let default_name = ident.as_ref().expect("named").to_string();
-                let name = Form::from_attrs("form", &field.attrs)
-                    .map(|result| result.map(|form| form.field.name))
-                    .unwrap_or_else(|| Ok(default_name))?;
-
-                let ty = field.ty.with_stripped_lifetimes();
-                let ty = quote_spanned! {
-                    span => <#ty as ::rocket::request::FromFormValue>
-                };
+        .try_map_fields({
+            let form_error = form_error.clone();
+            move |gen, fields| {
+                define_vars_and_mods!(_Ok, _Err);
+                let case_insensitive = FormContainer::case_insensitive(gen)?;
+                let collect_unknown = FormContainer::collect_unknown(gen)?;
+                let (constructors, matchers, builders) =
+                    fields_mapper(&form_error, case_insensitive, fields)?;
 
-                let constructor = quote_spanned!(span => let mut #ident = #_None;);
+                let unknown_field_decl = if collect_unknown {
+                    quote!(let mut __unknown = Vec::new();)
+                } else {
+                    quote!()
+                };
 
-                let matcher = quote_spanned! { span =>
-                    #name => { #ident = #_Some(#ty::from_form_value(__v)
-                                .map_err(|_| #form_error::BadValue(__k, __v))?); },
+                let unknown_field_handling = if collect_unknown {
+                    quote! {
+                        _ if __strict && __k != "_method" => __unknown.push((__k, __v)),
+                        _ => { /* lenient or "method"; let it pass */ }
+                    }
+                } else {
+                    quote! {
+                        _ if __strict && __k != "_method" => {
+                            return #_Err(#form_error::Unknown(__k, __v));
+                        }
+                        _ => { /* lenient or "method"; let it pass */ }
+                    }
                 };
 
-                let builder = quote_spanned! { span =>
-                    #ident: #ident.or_else(#ty::default)
-                        .ok_or_else(|| #form_error::Missing(#name.into()))?,
+                let unknown_field_check = if collect_unknown {
+                    quote! {
+                        if !__unknown.is_empty() {
+                            return #_Err(#form_error::UnknownFields(__unknown));
+                        }
+                    }
+                } else {
+                    quote!()
                 };
 
-                Ok((constructor, matcher, builder))
-            }).collect::<Result<Vec<_>>>()?.into_iter().split3();
+                Ok(quote! {
+                    #(#constructors)*
+                    #unknown_field_decl
 
-            Ok(quote! {
-                #(#constructors)*
+                    for (__k, __v) in __items.map(|item| item.key_value()) {
+                        match __k.as_str() {
+                            #(#matchers)*
+                            #unknown_field_handling
+                        }
+                    }
 
-                for (__k, __v) in __items.map(|item| item.key_value()) {
-                    match __k.as_str() {
-                        #(#matchers)*
-                        _ if __strict && __k != "_method" => {
+                    #unknown_field_check
+                    #_Ok(Self { #(#builders)* })
+                })
+            }
+        })
+        .try_map_enum(move |gen, data| {
+            define_vars_and_mods!(_Ok, _Err);
+            let tag = FormContainer::from_attrs("form", &gen.input.attrs)
+                .expect("presence checked in validate_enum")?
+                .tag.expect("presence checked in validate_enum");
+            let case_insensitive = FormContainer::case_insensitive(gen)?;
+            let collect_unknown = FormContainer::collect_unknown(gen)?;
+
+            // Nullary variants have nothing else to parse: the tag field's
+            // raw value is matched directly against each variant's name,
+            // the same comparison a `FromFormValue` derive would make.
+            if data.variants().all(|v| v.fields().is_empty()) {
+                let arms = data.variants().map(|variant| {
+                    let variant_name = variant.ident.to_string();
+                    let builder = variant.builder(|_| unreachable!());
+                    let comparand = if case_insensitive {
+                        quote!(__tag_value.as_uncased_str())
+                    } else {
+                        quote!(__tag_value.as_str())
+                    };
+
+                    quote! {
+                        if #comparand == #variant_name {
+                            return #_Ok(#builder);
+                        }
+                    }
+                });
+
+                return Ok(quote! {
+                    let __tag_value = __items.map(|item| item.key_value())
+                        .find(|(__k, _)| __k.as_str() == #tag)
+                        .map(|(_, __v)| __v)
+                        .ok_or_else(|| #form_error::Missing(#tag.into()))?;
+
+                    #(#arms)*
+                    #_Err(#form_error::BadValue(#tag.into(), __tag_value))
+                });
+            }
+
+            let arms = data.variants().map(|variant| {
+                let variant_ident = &variant.ident;
+                let variant_name = variant_ident.to_string();
+                let (constructors, matchers, builders) =
+                    fields_mapper(&form_error, case_insensitive, variant.fields())?;
+
+                let unknown_field_decl = if collect_unknown {
+                    quote!(let mut __unknown = Vec::new();)
+                } else {
+                    quote!()
+                };
+
+                let unknown_field_handling = if collect_unknown {
+                    quote! {
+                        _ if __strict && __k != "_method" && __k != #tag => {
+                            __unknown.push((__k, __v))
+                        }
+                        _ => { /* lenient, "method", or the tag field itself */ }
+                    }
+                } else {
+                    quote! {
+                        _ if __strict && __k != "_method" && __k != #tag => {
                             return #_Err(#form_error::Unknown(__k, __v));
                         }
-                        _ => { /* lenient or "method"; let it pass */ }
+                        _ => { /* lenient, "method", or the tag field itself */ }
                     }
-                }
+                };
 
-                #_Ok(Self { #(#builders)* })
+                let unknown_field_check = if collect_unknown {
+                    quote! {
+                        if !__unknown.is_empty() {
+                            return #_Err(#form_error::UnknownFields(__unknown));
+                        }
+                    }
+                } else {
+                    quote!()
+                };
+
+                Ok(quote! {
+                    #variant_name => {
+                        #(#constructors)*
+                        #unknown_field_decl
+
+                        for (__k, __v) in __buffered.iter().map(|(__k, __v)| (*__k, *__v)) {
+                            match __k.as_str() {
+                                #(#matchers)*
+                                #unknown_field_handling
+                            }
+                        }
+
+                        #unknown_field_check
+                        #_Ok(Self::#variant_ident { #(#builders)* })
+                    }
+                })
+            }).collect::<Result<Vec<_>>>()?;
+
+            Ok(quote! {
+                let __buffered: Vec<_> = __items.map(|item| item.key_value()).collect();
+                let __tag_value = __buffered.iter()
+                    .find(|(__k, _)| __k.as_str() == #tag)
+                    .map(|(_, __v)| *__v)
+                    .ok_or_else(|| #form_error::Missing(#tag.into()))?;
+
+                match __tag_value.as_str() {
+                    #(#arms)*
+                    _ => #_Err(#form_error::Unknown(#tag.into(), __tag_value)),
+                }
             })
         })
         .to_tokens()
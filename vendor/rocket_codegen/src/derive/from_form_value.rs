@@ -3,7 +3,62 @@ use proc_macro::TokenStream;
 
 #[derive(FromMeta)]
 struct Form {
-    value: String,
+    value: FormValues,
+}
+
+/// One or more strings that should parse into the variant carrying this
+/// attribute, as in `#[form(value = "yes")]` or the list form
+/// `#[form(value("yes", "y", "true"))]`, which accepts any of them.
+struct FormValues(Vec<String>);
+
+impl FromMeta for FormValues {
+    fn from_meta(meta: MetaItem<'_>) -> Result<Self> {
+        if let MetaItem::List(list) = meta {
+            let values = list.iter()
+                .map(String::from_meta)
+                .collect::<Result<Vec<_>>>()?;
+
+            if values.is_empty() {
+                return Err(list.span().error("expected at least one value"));
+            }
+
+            return Ok(FormValues(values));
+        }
+
+        String::from_meta(meta).map(|value| FormValues(vec![value]))
+    }
+}
+
+/// Container-level `#[form_value(case_sensitive)]` attribute.
+#[derive(FromMeta)]
+struct CaseSensitive {
+    #[meta(naked)]
+    case_sensitive: bool,
+}
+
+/// Variant-level `#[form_value(catch_all)]` attribute. The catch-all variant
+/// is returned unconditionally once reached, so it should be the last
+/// variant declared in the enum. At most one variant may carry this
+/// attribute; `from_form_value()` otherwise returns the raw value as the
+/// error when no variant matches.
+#[derive(FromMeta)]
+struct CatchAll {
+    #[meta(naked)]
+    catch_all: bool,
+}
+
+fn is_case_sensitive(generator: &DeriveGenerator) -> Result<bool> {
+    match CaseSensitive::from_attrs("form_value", &generator.input.attrs) {
+        Some(result) => Ok(result?.case_sensitive),
+        None => Ok(false),
+    }
+}
+
+fn is_catch_all(variant: &Variant<'_>) -> Result<bool> {
+    match CatchAll::from_attrs("form_value", &variant.attrs) {
+        Some(result) => Ok(result?.catch_all),
+        None => Ok(false),
+    }
 }
 
 pub fn derive_from_form_value(input: TokenStream) -> TokenStream {
@@ -24,28 +79,57 @@ pub fn derive_from_form_value(input: TokenStream) -> TokenStream {
                 generator.input.span().warning("deriving for empty enum").emit();
             }
 
+            // There can be at most one catch-all variant.
+            let mut catch_all_span = None;
+            for variant in data.variants() {
+                if is_catch_all(&variant)? {
+                    if let Some(first) = catch_all_span {
+                        return Err(variant.span().error("duplicate `catch_all` variant")
+                                   .span_note(first, "previous `catch_all` variant here"));
+                    }
+
+                    catch_all_span = Some(variant.span());
+                }
+            }
+
             Ok(())
         })
-        .function(move |_, inner| quote! {
-            type Error = &'__v ::rocket::http::RawStr;
-
-            fn from_form_value(
-                value: &'__v ::rocket::http::RawStr
-            ) -> #_Result<Self, Self::Error> {
-                let uncased = value.as_uncased_str();
-                #inner
-                #_Err(value)
+        .function(move |generator, inner| {
+            let bind_comparand = match is_case_sensitive(generator) {
+                Ok(true) => quote!(let comparand = value.as_str();),
+                _ => quote!(let comparand = value.as_uncased_str();),
+            };
+
+            quote! {
+                type Error = &'__v ::rocket::http::RawStr;
+
+                fn from_form_value(
+                    value: &'__v ::rocket::http::RawStr
+                ) -> #_Result<Self, Self::Error> {
+                    #bind_comparand
+                    #inner
+                    #_Err(value)
+                }
             }
         })
         .try_map_enum(null_enum_mapper)
         .try_map_variant(move |_, variant| {
-            let variant_str = Form::from_attrs("form", &variant.attrs)
-                .unwrap_or_else(|| Ok(Form { value: variant.ident.to_string() }))?
-                .value;
+            let default = || Ok(Form { value: FormValues(vec![variant.ident.to_string()]) });
+            let values = Form::from_attrs("form", &variant.attrs)
+                .unwrap_or_else(default)?
+                .value.0;
 
             let builder = variant.builder(|_| unreachable!());
+            if is_catch_all(&variant)? {
+                return Ok(quote!(return #_Ok(#builder);));
+            }
+
+            let mut comparisons = values.iter().map(|value| quote!(comparand == #value));
+            let first = comparisons.next().expect("at least one value");
+            let condition = comparisons.fold(first, |acc, next| quote!(#acc || #next));
+
             Ok(quote! {
-                if uncased == #variant_str {
+                if #condition {
                     return #_Ok(#builder);
                 }
             })
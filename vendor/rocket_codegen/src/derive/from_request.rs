@@ -0,0 +1,65 @@
+use proc_macro::TokenStream;
+use devise::{*, ext::TypeExt};
+
+/// Field-level `#[from_request(...)]` attribute.
+#[derive(Default, FromMeta)]
+struct FieldAttr {
+    /// Skips running a request guard for this field; it's built with
+    /// `Default::default()` instead. The field's type must implement
+    /// `Default`, though this isn't (and can't easily be) checked here; a
+    /// missing impl surfaces as a normal type error at the call site.
+    skip: bool,
+}
+
+pub fn derive_from_request(input: TokenStream) -> TokenStream {
+    DeriveGenerator::build_for(input, quote!(impl<'__a, '__r> ::rocket::request::FromRequest<'__a, '__r>))
+        .generic_support(GenericSupport::Lifetime)
+        .replace_generic(0, 0)
+        .data_support(DataSupport::NamedStruct)
+        .validate_generics(|_, generics| match generics.lifetimes().count() > 1 {
+            true => Err(generics.span().error("only one lifetime is supported")),
+            false => Ok(())
+        })
+        .function(|_, inner| quote! {
+            type Error = ();
+
+            fn from_request(
+                __req: &'__a ::rocket::Request<'__r>
+            ) -> ::rocket::request::Outcome<Self, Self::Error> {
+                #inner
+            }
+        })
+        .try_map_fields(|_, fields| {
+            define_vars_and_mods!(_Ok);
+            let exprs = fields.iter().map(|field| {
+                let ident = field.ident.as_ref().expect("named field");
+                let span = field.span().into();
+                let attr = FieldAttr::from_attrs("from_request", &field.attrs)
+                    .unwrap_or_else(|| Ok(Default::default()))?;
+
+                if attr.skip {
+                    return Ok(quote_spanned! { span =>
+                        #ident: ::std::default::Default::default(),
+                    });
+                }
+
+                let ty = field.ty.with_stripped_lifetimes();
+                Ok(quote_spanned! { span =>
+                    #ident: match <#ty as ::rocket::request::FromRequest>::from_request(__req) {
+                        ::rocket::Outcome::Success(__v) => __v,
+                        ::rocket::Outcome::Failure((__status, _)) => {
+                            return ::rocket::Outcome::Failure((__status, ()));
+                        },
+                        ::rocket::Outcome::Forward(_) => {
+                            return ::rocket::Outcome::Forward(());
+                        },
+                    },
+                })
+            }).collect::<Result<Vec<_>>>()?;
+
+            Ok(quote! {
+                #_Ok(Self { #(#exprs)* })
+            })
+        })
+        .to_tokens()
+}
@@ -1,6 +1,6 @@
 use quote::ToTokens;
 use crate::proc_macro2::TokenStream as TokenStream2;
-use devise::{FromMeta, MetaItem, Result, ext::{Split2, PathExt}};
+use devise::{FromMeta, MetaItem, Result, Spanned, ext::{Split2, PathExt}};
 use crate::http::{self, ext::IntoOwned};
 use crate::http::uri::{Path, Query};
 use crate::attribute::segments::{parse_segments, parse_data_segment, Segment, Kind};
@@ -16,7 +16,18 @@ pub struct Status(pub http::Status);
 #[derive(Debug)]
 pub struct MediaType(pub http::MediaType);
 
+/// One or more media types, as declared by a `format` route parameter. The
+/// plural, list form is written `format(media_type, media_type, ..)`, since
+/// attribute position doesn't admit array literals.
 #[derive(Debug)]
+pub struct MediaTypes(pub Vec<http::MediaType>);
+
+/// A route-local data limit override, in bytes, as declared by a
+/// `data_limit` route parameter, e.g. `data_limit = "5MiB"`.
+#[derive(Debug)]
+pub struct DataLimit(pub u64);
+
+#[derive(Debug, PartialEq)]
 pub struct Method(pub http::Method);
 
 #[derive(Debug)]
@@ -90,6 +101,62 @@ impl FromMeta for MediaType {
     }
 }
 
+impl FromMeta for DataLimit {
+    fn from_meta(meta: MetaItem<'_>) -> Result<Self> {
+        let byte_str = String::from_meta(meta)?;
+        let (digits, unit) = match byte_str.find(|c: char| !c.is_ascii_digit()) {
+            Some(i) => byte_str.split_at(i),
+            None => (byte_str.as_str(), "B"),
+        };
+
+        let value: u64 = digits.parse().map_err(|_| {
+            meta.value_span().error("expected a byte count, e.g. \"5MiB\" or \"32768\"")
+        })?;
+
+        let multiplier: u64 = match unit {
+            "B" => 1,
+            "KiB" => 1024,
+            "MiB" => 1024 * 1024,
+            "GiB" => 1024 * 1024 * 1024,
+            _ => return Err(meta.value_span().error(format!("invalid byte unit: `{}`", unit))
+                .help("valid units are `B`, `KiB`, `MiB`, and `GiB`")),
+        };
+
+        Ok(DataLimit(value * multiplier))
+    }
+}
+
+impl ToTokens for DataLimit {
+    fn to_tokens(&self, tokens: &mut TokenStream2) {
+        self.0.to_tokens(tokens);
+    }
+}
+
+impl FromMeta for MediaTypes {
+    fn from_meta(meta: MetaItem<'_>) -> Result<Self> {
+        if let MetaItem::List(list) = meta {
+            let types = list.iter()
+                .map(|item| MediaType::from_meta(item).map(|mt| mt.0))
+                .collect::<Result<Vec<_>>>()?;
+
+            if types.is_empty() {
+                return Err(list.span().error("expected at least one media type"));
+            }
+
+            return Ok(MediaTypes(types));
+        }
+
+        MediaType::from_meta(meta).map(|mt| MediaTypes(vec![mt.0]))
+    }
+}
+
+impl ToTokens for MediaTypes {
+    fn to_tokens(&self, tokens: &mut TokenStream2) {
+        let media_types = self.0.iter().cloned().map(MediaType);
+        tokens.extend(quote!(&[#(#media_types),*]));
+    }
+}
+
 impl ToTokens for MediaType {
     fn to_tokens(&self, tokens: &mut TokenStream2) {
         use std::iter::repeat;
@@ -210,7 +277,17 @@ impl FromMeta for RoutePath {
     fn from_meta(meta: MetaItem<'_>) -> Result<Self> {
         let (origin, string) = (Origin::from_meta(meta)?, StringLit::from_meta(meta)?);
         let path_span = string.subspan(1..origin.0.path().len() + 1);
-        let path = parse_segments::<Path>(origin.0.path(), path_span);
+        let path = parse_segments::<Path>(origin.0.path(), path_span).and_then(|segments| {
+            let mut diags = crate::proc_macro_ext::Diagnostics::new();
+            for segment in &segments {
+                if segment.default.is_some() {
+                    diags.push(segment.span.error("default values are only allowed on query parameters")
+                        .help("use `<name=value>` in the query part of the path instead"));
+                }
+            }
+
+            diags.err_or(segments)
+        });
 
         let query = origin.0.query()
             .map(|q| {
@@ -91,6 +91,7 @@ vars_and_mods! {
     FromData => rocket::data::FromData,
     Transform => rocket::data::Transform,
     Query => rocket::request::Query,
+    RawStr => rocket::http::RawStr,
     Request => rocket::Request,
     Response => rocket::response::Response,
     Data => rocket::Data,
@@ -126,6 +127,7 @@ static CATCH_STRUCT_PREFIX: &str = "static_rocket_catch_info_for_";
 static CATCH_FN_PREFIX: &str = "rocket_catch_fn_";
 static ROUTE_FN_PREFIX: &str = "rocket_route_fn_";
 static URI_MACRO_PREFIX: &str = "rocket_uri_macro_";
+static CATCHER_URI_MACRO_PREFIX: &str = "rocket_catcher_uri_macro_";
 static ROCKET_PARAM_PREFIX: &str = "__rocket_param_";
 
 macro_rules! emit {
@@ -169,6 +171,14 @@ macro_rules! route_attribute {
         ///   * [`#[options]`] - `OPTIONS` specific route
         ///   * [`#[patch]`] - `PATCH` specific route
         ///
+        /// A `HEAD` request to a path with a matching `GET` route, but no
+        /// matching `HEAD` route, is automatically dispatched to that `GET`
+        /// route with the response body stripped; there's no need to
+        /// declare a `HEAD` route solely to make `HEAD` work wherever `GET`
+        /// does. Declare [`#[head]`] only when the `HEAD` response should
+        /// differ from what running the `GET` handler and dropping its body
+        /// would produce.
+        ///
         /// Additionally, [`#[route]`] allows the method and path to be
         /// explicitly specified:
         ///
@@ -182,6 +192,26 @@ macro_rules! route_attribute {
         /// }
         /// ```
         ///
+        /// [`#[route]`] also accepts more than one method, separated by
+        /// commas, to register a single handler for all of them:
+        ///
+        /// ```rust
+        /// # #![feature(proc_macro_hygiene)]
+        /// # #[macro_use] extern crate rocket;
+        /// #
+        /// #[route(GET, HEAD, path = "/")]
+        /// fn index() -> &'static str {
+        ///     "Hello, world!"
+        /// }
+        /// ```
+        ///
+        /// A separate [`Route`] is generated for each method, but the
+        /// handler, generated URI macro, and respond expression are shared.
+        /// A `data` parameter's payload-support warning (or, with
+        /// `deny_payload`, error) considers the union of all declared
+        /// methods: it only fires if _none_ of them typically support a
+        /// payload.
+        ///
         /// [`#[delete]`]: attr.delete.html
         /// [`#[get]`]: attr.get.html
         /// [`#[head]`]: attr.head.html
@@ -200,26 +230,72 @@ macro_rules! route_attribute {
         ///
         /// path := ('/' segment)*
         ///
-        /// query := segment ('&' segment)*
+        /// query := q_segment ('&' q_segment)*
         ///
         /// segment := URI_SEG
         ///          | SINGLE_PARAM
         ///          | MULTI_PARAM
         ///
-        /// parameter := 'rank' '=' INTEGER
+        /// q_segment := segment
+        ///            | QUERY_PARAM
+        ///
+        /// parameter := 'rank' '=' (INTEGER | RANK_OFFSET)
         ///            | 'format' '=' '"' MEDIA_TYPE '"'
+        ///            | 'format' '(' '"' MEDIA_TYPE '"' (',' '"' MEDIA_TYPE '"')* ')'
         ///            | 'data' '=' '"' SINGLE_PARAM '"'
+        ///            | 'data_limit' '=' '"' BYTE_COUNT '"'
+        ///            | 'deny_payload'
+        ///            | 'cors' '=' BOOLEAN
         ///
         /// SINGLE_PARAM := '<' IDENT '>'
         /// MULTI_PARAM := '<' IDENT '..>'
+        /// QUERY_PARAM := '<' IDENT '>'
+        ///              | '<' IDENT '=' DEFAULT '>'
+        ///
+        /// DEFAULT := non-empty literal, parsed by the parameter's
+        ///            `FromFormValue` implementation
         ///
         /// URI_SEG := valid, non-percent-encoded HTTP URI segment
         /// MEDIA_TYPE := valid HTTP media type or known shorthand
         ///
         /// INTEGER := unsigned integer, as defined by Rust
+        /// BOOLEAN := 'true' | 'false'
         /// IDENT := valid identifier, as defined by Rust, except `_`
+        ///
+        /// RANK_OFFSET := '"auto"' | '"auto+' INTEGER '"' | '"auto-' INTEGER '"'
+        ///
+        /// BYTE_COUNT := INTEGER ('B' | 'KiB' | 'MiB' | 'GiB')?
         /// ```
         ///
+        /// `rank = "auto+1"` (or `"auto-1"`) ranks the route relative to the
+        /// rank Rocket would otherwise compute for it from its path's
+        /// specificity, instead of replacing that computed rank outright the
+        /// way an explicit, literal `rank` does.
+        ///
+        /// `format(..)` declares more than one acceptable media type for a
+        /// single route; the type the client most prefers is exposed through
+        /// [`Request::negotiated_format()`](../rocket/struct.Request.html#method.negotiated_format).
+        ///
+        /// `data_limit = "5MiB"` overrides, for this route alone, the data
+        /// limit that would otherwise come from the `limits.forms`
+        /// configuration parameter.
+        ///
+        /// By default, a `data` parameter on a method that doesn't typically
+        /// support payloads (such as `GET` or `HEAD`) is only a warning.
+        /// Adding `deny_payload` turns that warning into a hard error.
+        ///
+        /// `cors = false` opts the route out of handling by a
+        /// [`Cors`](../rocket/fairing/struct.Cors.html) fairing entirely;
+        /// `cors = true` (the default) leaves the decision to the fairing.
+        ///
+        /// A single-valued query parameter may declare a default with
+        /// `<name=value>`, used in place of `FromFormValue::default()` when
+        /// the client's request omits the parameter entirely. For instance,
+        /// `<page=1>` falls back to the literal `1` rather than forwarding
+        /// the request. A parameter that's present but fails to parse still
+        /// forwards the request; the default only applies to a parameter
+        /// that's missing outright.
+        ///
         /// The generic route attribute is defined as:
         ///
         /// ```text
@@ -449,15 +525,29 @@ pub fn catch(args: TokenStream, input: TokenStream) -> TokenStream {
 /// The `#[form]` attribute's grammar is:
 ///
 /// ```text
-/// form := 'field' '=' STRING_LIT
+/// form := 'value' '=' STRING_LIT
+///       | 'value' '(' STRING_LIT (',' STRING_LIT)* ')'
 ///
 /// STRING_LIT := any valid string literal, as defined by Rust
 /// ```
 ///
-/// The attribute accepts a single string parameter of name `value`
-/// corresponding to the string to use to match against for the decorated
-/// variant. In the example above, the the strings `"fourth"`, `"FOUrth"` and so
-/// on would parse as `MyValue::Third`.
+/// The attribute accepts either a single string or a parenthesized list of
+/// strings, any of which will match for the decorated variant. In the
+/// example above, the the strings `"fourth"`, `"FOUrth"` and so on would
+/// parse as `MyValue::Third`. A variant that should accept several distinct
+/// spellings, such as a boolean-like value, can list them all:
+///
+/// ```rust
+/// # #[macro_use] extern crate rocket;
+/// #
+/// #[derive(FromFormValue)]
+/// enum Switch {
+///     #[form(value("on", "yes", "true"))]
+///     On,
+///     #[form(value("off", "no", "false"))]
+///     Off,
+/// }
+/// ```
 ///
 /// [`FromFormValue`]: ../rocket/request/trait.FromFormValue.html
 /// [`FromFormValue::Error`]: ../rocket/request/trait.FromFormValue.html#associatedtype.Error
@@ -659,6 +749,40 @@ pub fn derive_responder(input: TokenStream) -> TokenStream {
     emit!(derive::responder::derive_responder(input))
 }
 
+/// Derive for the [`FromRequest`] trait.
+///
+/// Composes several request guards into one: generates an implementation of
+/// [`FromRequest`] for a named struct where every field's type implements
+/// `FromRequest`. Fields are resolved in declaration order; the first
+/// `Failure` or `Forward` short-circuits the rest and is returned as-is
+/// (with the failure's error discarded, since the fields' `Error` types
+/// needn't agree), skipping any guards after it. This means a later field's
+/// guard is never run if an earlier one fails, so expensive or
+/// order-dependent guards should be declared first.
+///
+/// A field marked `#[from_request(skip)]` is built with `Default::default()`
+/// instead of via a request guard.
+///
+/// ```rust
+/// # #![feature(proc_macro_hygiene)]
+/// # #[macro_use] extern crate rocket;
+/// use rocket::http::Method;
+///
+/// #[derive(FromRequest)]
+/// struct RequestId {
+///     method: Method,
+///     #[from_request(skip)]
+///     note: Option<&'static str>,
+/// }
+/// # fn main() {}
+/// ```
+///
+/// [`FromRequest`]: ../rocket/request/trait.FromRequest.html
+#[proc_macro_derive(FromRequest, attributes(from_request))]
+pub fn derive_from_request(input: TokenStream) -> TokenStream {
+    emit!(derive::from_request::derive_from_request(input))
+}
+
 /// Derive for the [`UriDisplay<Query>`] trait.
 ///
 /// The [`UriDisplay<Query>`] derive can be applied to enums and structs. When
@@ -907,6 +1031,10 @@ pub fn catchers(input: TokenStream) -> TokenStream {
 /// let mike = uri!("/api", person: name = "Mike", age = 28);
 /// assert_eq!(mike.to_string(), "/api/person/Mike?age=28");
 ///
+/// // with an absolute mount-point, producing an absolute URI
+/// let mike = uri!("https://rocket.rs/api", person: name = "Mike", age = 28);
+/// assert_eq!(mike.to_string(), "https://rocket.rs/api/person/Mike?age=28");
+///
 /// // with unnamed values ignored
 /// let mike = uri!(person: "Mike", _);
 /// assert_eq!(mike.to_string(), "/person/Mike");
@@ -923,7 +1051,7 @@ pub fn catchers(input: TokenStream) -> TokenStream {
 /// ```text
 /// uri := (mount ',')? PATH (':' params)?
 ///
-/// mount = STRING
+/// mount = STRING  // a static origin URI ("/api") or absolute URI ("https://rocket.rs")
 /// params := unnamed | named
 /// unnamed := expr (',' expr)*
 /// named := IDENT = expr (',' named)?
@@ -952,7 +1080,11 @@ pub fn catchers(input: TokenStream) -> TokenStream {
 /// `UriDisplay` implementation ensures that the rendered value is URI-safe.
 ///
 /// If a mount-point is provided, the mount-point is prepended to the route's
-/// URI.
+/// URI. A mount-point starting with `/` is prepended as a path, as shown
+/// above; a trailing `/` on the mount-point never produces a doubled `/` in
+/// the result. If the mount-point is instead an absolute URI with a scheme
+/// and authority, such as `"https://rocket.rs/api"`, the macro returns an
+/// [`Absolute`] URI with that scheme and authority instead of an `Origin`.
 ///
 /// ### Conversion
 ///
@@ -977,6 +1109,7 @@ pub fn catchers(input: TokenStream) -> TokenStream {
 ///
 /// [`Uri`]: ../rocket/http/uri/enum.Uri.html
 /// [`Origin`]: ../rocket/http/uri/struct.Origin.html
+/// [`Absolute`]: ../rocket/http/uri/struct.Absolute.html
 /// [`FromUriParam`]: ../rocket/http/uri/trait.FromUriParam.html
 /// [`UriDisplay`]: ../rocket/http/uri/trait.UriDisplay.html
 /// [`Ignorable`]: ../rocket/http/uri/trait.Ignorable.html
@@ -985,12 +1118,95 @@ pub fn uri(input: TokenStream) -> TokenStream {
     emit!(bang::uri_macro(input))
 }
 
+/// Checks, at compile time, that none of the given routes would collide if
+/// mounted together at the same mount point.
+///
+/// `routes_checked!` takes the same kind of route-attributed functions that
+/// are normally passed to [`routes!`] by path, except it takes the items
+/// themselves, still carrying their un-expanded route attribute:
+///
+/// ```rust
+/// # #![feature(proc_macro_hygiene)]
+/// # #[macro_use] extern crate rocket;
+/// #
+/// routes_checked! {
+///     #[get("/")]
+///     fn index() -> &'static str { "hi" }
+///
+///     #[get("/hello")]
+///     fn hello() -> &'static str { "hello" }
+/// }
+///
+/// # fn main() {
+/// rocket::ignite().mount("/", routes![index, hello]);
+/// # }
+/// ```
+///
+/// If two of the given routes share an HTTP method, an explicit `rank`, and
+/// a path that could match the same request, the macro fails to compile with
+/// an error naming both routes. Routes without an explicit `rank` are never
+/// compared against each other, since Rocket assigns each a rank based on
+/// specificity at mount time; this macro only catches the common case of two
+/// routes that were explicitly given the same rank by mistake. It doesn't
+/// replace the full collision check Rocket performs when a [`Rocket`]
+/// instance is launched or ignited: that check also accounts for query
+/// segments and trailing `<param..>` segments, neither of which this macro
+/// looks at.
+///
+/// The functions themselves are emitted unchanged, so their own `#[get]`,
+/// `#[route]`, and similar attributes still expand normally; `routes_checked!`
+/// only inspects them.
+///
+/// [`routes!`]: macro.routes.html
+/// [`Rocket`]: ../rocket/struct.Rocket.html
+#[proc_macro]
+pub fn routes_checked(input: TokenStream) -> TokenStream {
+    emit!(bang::routes_checked_macro(input))
+}
+
 #[doc(hidden)]
 #[proc_macro]
 pub fn rocket_internal_uri(input: TokenStream) -> TokenStream {
     emit!(bang::uri_internal_macro(input))
 }
 
+/// Returns a [`CatcherUri`] identifying the catcher declared with `#[catch]`
+/// and named by the given path, for example:
+///
+/// ```rust
+/// # #![feature(proc_macro_hygiene)]
+/// # #[macro_use] extern crate rocket;
+/// use rocket::Request;
+///
+/// #[catch(404)]
+/// fn not_found(req: &Request) -> String {
+///     format!("I couldn't find '{}'. Try something else?", req.uri())
+/// }
+///
+/// # fn main() {
+/// let uri = catcher_uri!(not_found);
+/// assert_eq!(uri.code, 404);
+/// # }
+/// ```
+///
+/// Unlike [`uri!`], `catcher_uri!` takes no parameters: a catcher has no path
+/// of its own, so there's nothing to interpolate. Passing it a path to a
+/// route rather than a catcher fails to compile, since only `#[catch]`
+/// generates the hidden macro that `catcher_uri!` forwards to.
+///
+/// [`CatcherUri`]: ../rocket/struct.CatcherUri.html
+/// [`uri!`]: macro.uri.html
+#[proc_macro]
+pub fn catcher_uri(input: TokenStream) -> TokenStream {
+    emit!(bang::catcher_uri_macro(input))
+}
+
+#[doc(hidden)]
+#[proc_macro]
+pub fn rocket_internal_catcher_uri(input: TokenStream) -> TokenStream {
+    emit!(bang::catcher_uri_internal_macro(input))
+}
+
 #[doc(hidden)]
 #[proc_macro]
 pub fn rocket_internal_guide_tests(input: TokenStream) -> TokenStream {
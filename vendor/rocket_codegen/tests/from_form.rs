@@ -317,3 +317,215 @@ fn form_errors() {
     let form: Result<WhoopsForm, _> = strict("complete=true");
     assert_eq!(form, Err(FormParseError::Missing("other".into())));
 }
+
+#[derive(Debug, PartialEq, FromForm)]
+struct WithDefault {
+    #[form(default = "42")]
+    number: usize,
+    name: String,
+}
+
+#[test]
+fn field_default() {
+    // The default kicks in when the field is entirely absent...
+    let form: Result<WithDefault, _> = strict("name=Bob");
+    assert_eq!(form, Ok(WithDefault { number: 42, name: "Bob".into() }));
+
+    // ...but not when it's present and simply fails to parse.
+    let form: Result<WithDefault, _> = strict("number=nope&name=Bob");
+    assert_eq!(form, Err(FormParseError::BadValue("number".into(), "nope".into())));
+
+    // The value, when present, is still used as usual.
+    let form: Result<WithDefault, _> = strict("number=7&name=Bob");
+    assert_eq!(form, Ok(WithDefault { number: 7, name: "Bob".into() }));
+
+    // Defaults apply in lenient mode too.
+    let form: Result<WithDefault, _> = lenient("name=Bob&extra=1");
+    assert_eq!(form, Ok(WithDefault { number: 42, name: "Bob".into() }));
+}
+
+#[derive(Debug, PartialEq, FromForm)]
+struct WithAliases<'r> {
+    #[form(field = "a, b")]
+    value: &'r RawStr,
+}
+
+#[test]
+fn field_aliases() {
+    // Either alias is accepted...
+    let form: Result<WithAliases, _> = strict("a=1");
+    assert_eq!(form, Ok(WithAliases { value: "1".into() }));
+
+    let form: Result<WithAliases, _> = strict("b=2");
+    assert_eq!(form, Ok(WithAliases { value: "2".into() }));
+
+    // If both aliases are present, the later one in the form string wins,
+    // same as when any other field name appears more than once.
+    let form: Result<WithAliases, _> = strict("a=1&b=2");
+    assert_eq!(form, Ok(WithAliases { value: "2".into() }));
+
+    // Neither alias present is `Missing` under the primary (first) name.
+    let form: Result<WithAliases, _> = lenient("other=1");
+    assert_eq!(form, Err(FormParseError::Missing("a".into())));
+}
+
+#[derive(Debug, PartialEq, FromForm)]
+#[form(tag = "type")]
+enum Shape {
+    Circle { radius: usize },
+    Rectangle { width: usize, height: usize },
+}
+
+#[test]
+fn tagged_enum() {
+    let form: Result<Shape, _> = strict("type=Circle&radius=4");
+    assert_eq!(form, Ok(Shape::Circle { radius: 4 }));
+
+    let form: Result<Shape, _> = strict("type=Rectangle&width=2&height=3");
+    assert_eq!(form, Ok(Shape::Rectangle { width: 2, height: 3 }));
+
+    // The tag field itself isn't treated as an unknown field by the
+    // matched variant.
+    let form: Result<Shape, _> = strict("radius=4&type=Circle");
+    assert_eq!(form, Ok(Shape::Circle { radius: 4 }));
+
+    // No tag at all: `Missing` under the tag's name.
+    let form: Result<Shape, _> = strict("radius=4");
+    assert_eq!(form, Err(FormParseError::Missing("type".into())));
+
+    // A tag that doesn't match any variant: `Unknown`.
+    let form: Result<Shape, _> = strict("type=Triangle&radius=4");
+    assert_eq!(form, Err(FormParseError::Unknown("type".into(), "Triangle".into())));
+
+    // Fields are still validated against the selected variant only.
+    let form: Result<Shape, _> = strict("type=Circle&width=2&height=3");
+    assert_eq!(form, Err(FormParseError::Missing("radius".into())));
+
+    let form: Result<Shape, _> = strict("type=Circle&radius=4&width=2");
+    assert_eq!(form, Err(FormParseError::Unknown("width".into(), "2".into())));
+}
+
+#[derive(Debug, PartialEq, FromForm)]
+#[form(case_insensitive)]
+struct CaseInsensitiveContainer {
+    first_name: String,
+    age: usize,
+}
+
+#[test]
+fn case_insensitive_container() {
+    let form: Result<CaseInsensitiveContainer, _> = strict("FIRST_NAME=Bob&AGE=9");
+    assert_eq!(form, Ok(CaseInsensitiveContainer { first_name: "Bob".into(), age: 9 }));
+
+    let form: Result<CaseInsensitiveContainer, _> = strict("first_name=Bob&age=9");
+    assert_eq!(form, Ok(CaseInsensitiveContainer { first_name: "Bob".into(), age: 9 }));
+
+    let form: Result<CaseInsensitiveContainer, _> = strict("First_Name=Bob&Age=9");
+    assert_eq!(form, Ok(CaseInsensitiveContainer { first_name: "Bob".into(), age: 9 }));
+}
+
+#[derive(Debug, PartialEq, FromForm)]
+struct WithLiteralDefault {
+    // A bare literal is used as-is, with no surrounding string.
+    #[form(default = 42)]
+    number: usize,
+    #[form(default = "String::from(\"renamed\")", field = "full_name")]
+    name: String,
+}
+
+#[test]
+fn field_literal_default() {
+    let form: Result<WithLiteralDefault, _> = strict("full_name=Bob");
+    assert_eq!(form, Ok(WithLiteralDefault { number: 42, name: "Bob".into() }));
+
+    // The default composes with a field rename.
+    let form: Result<WithLiteralDefault, _> = strict("number=7");
+    assert_eq!(form, Ok(WithLiteralDefault { number: 7, name: "renamed".into() }));
+
+    // Strict parsing still rejects unknown fields even when defaults apply.
+    let form: Result<WithLiteralDefault, _> = strict("number=7&full_name=Bob&extra=1");
+    assert_eq!(form, Err(FormParseError::Unknown("extra".into(), "1".into())));
+}
+
+#[derive(Debug, PartialEq, FromForm)]
+struct CaseInsensitiveField {
+    #[form(case_insensitive)]
+    name: String,
+    age: usize,
+}
+
+#[test]
+fn case_insensitive_field() {
+    // The `case_insensitive` field matches regardless of case...
+    let form: Result<CaseInsensitiveField, _> = strict("NAME=Bob&age=9");
+    assert_eq!(form, Ok(CaseInsensitiveField { name: "Bob".into(), age: 9 }));
+
+    // ...but a field without the attribute still requires an exact match.
+    let form: Result<CaseInsensitiveField, _> = strict("name=Bob&AGE=9");
+    assert_eq!(form, Err(FormParseError::Unknown("AGE".into(), "9".into())));
+}
+
+fn in_range(age: &usize) -> Result<(), &'static str> {
+    if *age < 130 { Ok(()) } else { Err("implausible age") }
+}
+
+#[derive(Debug, PartialEq, FromForm)]
+struct Validated {
+    #[form(validate = "in_range")]
+    age: usize,
+    name: String,
+}
+
+#[test]
+fn field_validate() {
+    let form: Result<Validated, _> = strict("age=30&name=Bob");
+    assert_eq!(form, Ok(Validated { age: 30, name: "Bob".into() }));
+
+    // The validator runs after the value parses, using the field's name and
+    // raw value for the error, same as a `FromFormValue` parse failure.
+    let form: Result<Validated, _> = strict("age=200&name=Bob");
+    assert_eq!(form, Err(FormParseError::BadValue("age".into(), "200".into())));
+}
+
+#[derive(Debug, PartialEq, FromForm)]
+#[form(collect_unknown)]
+struct CollectUnknown {
+    name: String,
+}
+
+#[test]
+fn collect_unknown_fields() {
+    let form: Result<CollectUnknown, _> = strict("name=Bob");
+    assert_eq!(form, Ok(CollectUnknown { name: "Bob".into() }));
+
+    // With a single unexpected field, behaves like a one-element `Unknown`.
+    let form: Result<CollectUnknown, _> = strict("name=Bob&extra=1");
+    assert_eq!(form, Err(FormParseError::UnknownFields(vec![("extra".into(), "1".into())])));
+
+    // With several unexpected fields, every one of them is reported, in the
+    // order they appeared in the form string, rather than just the first.
+    let form: Result<CollectUnknown, _> = strict("first=a&name=Bob&second=b");
+    assert_eq!(form, Err(FormParseError::UnknownFields(vec![
+        ("first".into(), "a".into()),
+        ("second".into(), "b".into()),
+    ])));
+
+    // Lenient mode is unaffected: unknown fields are simply ignored.
+    let form: Result<CollectUnknown, _> = lenient("name=Bob&first=a&second=b");
+    assert_eq!(form, Ok(CollectUnknown { name: "Bob".into() }));
+}
+
+#[derive(Debug, PartialEq, FromForm)]
+#[form(tag = "type", collect_unknown)]
+enum TaggedCollectUnknown {
+    Circle { radius: usize },
+}
+
+#[test]
+fn collect_unknown_fields_tagged_enum() {
+    let form: Result<TaggedCollectUnknown, _> = strict("type=Circle&radius=4&a=1&b=2");
+    assert_eq!(form, Err(FormParseError::UnknownFields(vec![
+        ("a".into(), "1".into()),
+        ("b".into(), "2".into()),
+    ])));
+}
@@ -65,3 +65,44 @@ fn from_form_value_renames() {
     assert_parse!(":book", ":BOOK", ":bOOk", ":booK" => Foo::Book);
     assert_no_parse!("book", "bar" => Foo);
 }
+
+#[test]
+fn from_form_value_case_sensitive() {
+    #[derive(Debug, FromFormValue)]
+    #[form_value(case_sensitive)]
+    enum Foo { Bar, Baz }
+
+    assert_parse!("Bar" => Foo::Bar);
+    assert_parse!("Baz" => Foo::Baz);
+    assert_no_parse!("bar", "BAR", "baz", "BAZ" => Foo);
+}
+
+#[test]
+fn from_form_value_multiple_aliases() {
+    #[derive(Debug, FromFormValue)]
+    enum Switch {
+        #[form(value("on", "yes", "true"))]
+        On,
+        #[form(value("off", "no", "false"))]
+        Off,
+    }
+
+    assert_parse!("on", "ON", "yes", "YES", "true", "TRUE" => Switch::On);
+    assert_parse!("off", "OFF", "no", "NO", "false", "FALSE" => Switch::Off);
+    assert_no_parse!("maybe", "1", "0" => Switch);
+}
+
+#[test]
+fn from_form_value_catch_all() {
+    #[derive(Debug, PartialEq, FromFormValue)]
+    enum Foo {
+        Bar,
+        Baz,
+        #[form_value(catch_all)]
+        Other,
+    }
+
+    assert_parse!("bar", "BAR" => Foo::Bar);
+    assert_parse!("baz", "BAZ" => Foo::Baz);
+    assert_parse!("anything-else", "", "123" => Foo::Other);
+}
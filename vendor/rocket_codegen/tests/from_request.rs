@@ -0,0 +1,55 @@
+#![feature(proc_macro_hygiene)]
+
+use rocket::Request;
+use rocket::local::Client;
+use rocket::http::Method;
+use rocket::request::{FromRequest, Outcome};
+use rocket::outcome::Outcome::*;
+
+#[derive(Debug, PartialEq)]
+struct Flag;
+
+impl<'a, 'r> FromRequest<'a, 'r> for Flag {
+    type Error = ();
+
+    fn from_request(request: &'a Request<'r>) -> Outcome<Self, Self::Error> {
+        match request.method() {
+            Method::Get => Success(Flag),
+            _ => Forward(()),
+        }
+    }
+}
+
+#[derive(Debug, PartialEq, FromRequest)]
+struct Combo {
+    method: Method,
+    flag: Flag,
+    #[from_request(skip)]
+    note: Option<&'static str>,
+}
+
+#[test]
+fn composed_guards_run_in_order_and_succeed_together() {
+    let client = Client::new(rocket::ignite()).expect("valid rocket");
+    let local_req = client.get("/");
+    let req = local_req.inner();
+
+    let combo = match Combo::from_request(req) {
+        Success(combo) => combo,
+        _ => panic!("expected a successful composed guard"),
+    };
+
+    assert_eq!(combo, Combo { method: Method::Get, flag: Flag, note: None });
+}
+
+#[test]
+fn a_forwarding_field_guard_short_circuits_the_rest() {
+    let client = Client::new(rocket::ignite()).expect("valid rocket");
+    let local_req = client.post("/");
+    let req = local_req.inner();
+
+    match Combo::from_request(req) {
+        Forward(()) => { /* `flag`'s guard forwards on a non-GET request */ },
+        other => panic!("expected a forward, got {:?}", other),
+    }
+}
@@ -36,6 +36,32 @@ fn test_ranking() {
     assert_eq!(response.body_string().unwrap(), "3");
 }
 
+// Test that a relative `rank = "auto+N"` offsets the route's computed rank
+// without replacing it outright, so two dynamic-path routes still lose to a
+// static one while being ordered relative to each other by the offset.
+
+#[get("/hello")]
+fn hello_static() -> &'static str { "static" }
+
+#[get("/<_name>", rank = "auto+1")]
+fn hello_dynamic_lower(_name: String) -> &'static str { "dynamic-lower" }
+
+#[get("/<_name>", rank = "auto-1")]
+fn hello_dynamic_higher(_name: String) -> &'static str { "dynamic-higher" }
+
+#[test]
+fn test_relative_ranking() {
+    let rocket = rocket::ignite()
+        .mount("/", routes![hello_static, hello_dynamic_lower, hello_dynamic_higher]);
+    let client = Client::new(rocket).unwrap();
+
+    let mut response = client.get("/hello").dispatch();
+    assert_eq!(response.body_string().unwrap(), "static");
+
+    let mut response = client.get("/other").dispatch();
+    assert_eq!(response.body_string().unwrap(), "dynamic-higher");
+}
+
 // Test a collision due to same auto rank.
 
 #[get("/<_n>")]
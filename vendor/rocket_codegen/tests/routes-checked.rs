@@ -0,0 +1,36 @@
+#![feature(proc_macro_hygiene)]
+
+#[macro_use] extern crate rocket;
+
+routes_checked! {
+    #[get("/", rank = 1)]
+    fn index() -> &'static str { "hi" }
+
+    #[get("/hello", rank = 1)]
+    fn hello() -> &'static str { "hello" }
+
+    #[post("/hello", rank = 1)]
+    fn hello_post() -> &'static str { "hello" }
+}
+
+mod routes_checked_tests {
+    use super::*;
+    use rocket::local::Client;
+    use rocket::http::Status;
+
+    #[test]
+    fn non_conflicting_routes_still_mount_and_dispatch() {
+        let rocket = rocket::ignite().mount("/", routes![index, hello, hello_post]);
+        let client = Client::new(rocket).unwrap();
+
+        let mut response = client.get("/").dispatch();
+        assert_eq!(response.status(), Status::Ok);
+        assert_eq!(response.body_string(), Some("hi".into()));
+
+        let response = client.get("/hello").dispatch();
+        assert_eq!(response.status(), Status::Ok);
+
+        let response = client.post("/hello").body("x").dispatch();
+        assert_eq!(response.status(), Status::Ok);
+    }
+}
@@ -0,0 +1,22 @@
+#![feature(proc_macro_hygiene)]
+#![allow(dead_code, unused_variables)]
+
+#[macro_use] extern crate rocket;
+
+use rocket::Request;
+
+#[catch(404)]
+fn not_found(req: &Request<'_>) -> String {
+    format!("I couldn't find '{}'. Try something else?", req.uri())
+}
+
+#[catch(500)]
+fn internal_error() -> &'static str {
+    "Whoops! Looks like we messed up."
+}
+
+#[test]
+fn catcher_uri_returns_registered_status_code() {
+    assert_eq!(catcher_uri!(not_found).code, 404);
+    assert_eq!(catcher_uri!(internal_error).code, 500);
+}
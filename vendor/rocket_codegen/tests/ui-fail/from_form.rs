@@ -4,7 +4,7 @@ use rocket::http::RawStr;
 
 #[derive(FromForm)]
 enum Thing { }
-//~^ ERROR not supported
+//~^ ERROR require a `#[form(tag = "...")]` attribute
 
 #[derive(FromForm)]
 struct Foo1;
@@ -165,4 +165,19 @@ struct BadName3 {
     field: String,
 }
 
+#[derive(FromForm)]
+enum MissingTag {
+//~^ ERROR require a `#[form(tag = "...")]` attribute
+    A { name: String },
+    B { name: String },
+}
+
+#[derive(FromForm)]
+#[form(tag = "type")]
+enum TupleVariant {
+    A { name: String },
+    B(usize),
+    //~^ ERROR variants must have named fields
+}
+
 fn main() { }
@@ -16,4 +16,11 @@ struct Other {
     //~^ rocket::request::FromFormValue
 }
 
+#[derive(FromForm)]
+struct BadDefault {
+    #[form(default = "\"hello\"")]
+    //~^ mismatched types
+    number: usize,
+}
+
 fn main() {  }
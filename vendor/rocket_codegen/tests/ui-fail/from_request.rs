@@ -0,0 +1,14 @@
+#[macro_use] extern crate rocket;
+
+#[derive(FromRequest)]
+struct Tuple(usize);
+//~^ ERROR not supported
+
+#[derive(FromRequest)]
+enum Choice {
+//~^ ERROR not supported
+    A,
+    B,
+}
+
+fn main() { }
@@ -0,0 +1,12 @@
+#![feature(proc_macro_hygiene)]
+
+#[macro_use] extern crate rocket;
+
+// A `data` argument on a method that doesn't typically support payloads is
+// only an error when `deny_payload` is requested; it's a warning otherwise
+// (see `route-warnings.rs`).
+
+#[get("/", data = "<_foo>", deny_payload)] //~ ERROR non-payload-supporting method
+fn f0(_foo: rocket::Data) {}
+
+fn main() {  }
@@ -0,0 +1,15 @@
+#![feature(proc_macro_hygiene)]
+
+#[macro_use] extern crate rocket;
+
+// A name used as both a path and a query parameter is just as much a
+// duplicate as one repeated within a single source.
+
+#[get("/<id>?<id>")] //~ ERROR `id` is used as both a path and a query parameter
+//~^ NOTE previously declared as a path parameter here
+fn f0(id: usize) -> String { id.to_string() }
+
+// The usual same-source duplicate still reads the same as before.
+
+#[get("/?<id>&<id>")] //~ ERROR duplicate parameter: `id`
+fn f1(id: usize) -> String { id.to_string() }
@@ -0,0 +1,15 @@
+#![feature(proc_macro_hygiene)]
+
+#[macro_use] extern crate rocket;
+
+// A typo'd segment name becomes a confusing, far-away `FromRequest` error
+// unless the argument it was probably meant to match is called out here.
+
+#[get("/<nam>")] //~ ERROR unused dynamic parameter
+//~^ NOTE did you mean `nam`
+fn f0(name: String) -> String { name }
+
+// No nearby argument to suggest: the usual diagnostic, nothing more.
+
+#[get("/<name>")] //~ ERROR unused dynamic parameter
+fn f1(unrelated: String) -> String { unrelated }
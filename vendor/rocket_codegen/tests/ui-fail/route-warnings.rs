@@ -23,4 +23,10 @@ fn g0(_foo: rocket::Data) {}
 #[head("/", data = "<_foo>")] //~ WARNING used with non-payload-supporting method
 fn g1(_foo: rocket::Data) {}
 
+// The check considers the union of all declared methods, so no warning is
+// emitted as long as at least one of them supports a payload.
+
+#[route(GET, POST, data = "<_foo>")]
+fn g2(_foo: rocket::Data) {}
+
 fn main() {  }
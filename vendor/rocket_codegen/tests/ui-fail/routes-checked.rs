@@ -0,0 +1,14 @@
+#![feature(proc_macro_hygiene)]
+
+#[macro_use] extern crate rocket;
+
+routes_checked! {
+    #[get("/hello", rank = 1)]
+    fn hello() -> &'static str { "hello" }
+
+    #[get("/hello", rank = 1)]
+    fn hi() -> &'static str { "hi" }
+    //~^ ERROR route collides with `hello`
+}
+
+fn main() {}
@@ -0,0 +1,10 @@
+#![feature(proc_macro_hygiene)]
+
+#[macro_use] extern crate rocket;
+
+#[get("/")]
+fn simple() {}
+
+fn main() {
+    let _ = catcher_uri!(simple); //~ ERROR cannot find macro
+}
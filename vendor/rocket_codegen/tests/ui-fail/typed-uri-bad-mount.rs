@@ -0,0 +1,14 @@
+#![feature(proc_macro_hygiene)]
+
+#[macro_use] extern crate rocket;
+
+#[post("/<id>")]
+fn simple(id: i32) {}
+
+fn main() {
+    uri!("not-a-uri", simple: 100); //~ ERROR invalid mount point
+
+    uri!("mailto:bob", simple: 100); //~ ERROR invalid mount point
+
+    uri!("", simple: 100); //~ ERROR invalid mount point
+}
@@ -25,6 +25,79 @@ mod key {
     }
 }
 
+/// The default attributes applied to a cookie added via [`Cookies::add()`]
+/// when the cookie doesn't already specify a value for that attribute.
+///
+/// A `None` field means "leave as the `cookie` crate's own default", which,
+/// for all three attributes here, is to omit the attribute entirely. Explicit
+/// per-cookie settings, made via [`Cookie`]'s builder methods before the
+/// cookie is handed to [`Cookies::add()`], always take precedence over the
+/// policy.
+///
+/// A `CookiePolicy` is configured on the active [`Config`] via the `cookies`
+/// parameter, for example:
+///
+/// ```toml
+/// [global.cookies]
+/// secure = true
+/// default_same_site = "lax"
+/// ```
+///
+/// [`Config`]: https://api.rocket.rs/v0.5/rocket/config/struct.Config.html
+#[derive(Debug, Clone, Default)]
+pub struct CookiePolicy {
+    /// The default value of the `Secure` attribute.
+    pub secure: Option<bool>,
+    /// The default value of the `HttpOnly` attribute.
+    pub http_only: Option<bool>,
+    /// The default value of the `SameSite` attribute.
+    pub same_site: Option<SameSite>,
+}
+
+impl CookiePolicy {
+    fn apply(&self, cookie: &mut Cookie<'static>) {
+        if cookie.secure().is_none() {
+            if let Some(secure) = self.secure {
+                cookie.set_secure(secure);
+            }
+        }
+
+        if cookie.http_only().is_none() {
+            if let Some(http_only) = self.http_only {
+                cookie.set_http_only(http_only);
+            }
+        }
+
+        if cookie.same_site().is_none() {
+            if let Some(same_site) = self.same_site {
+                cookie.set_same_site(same_site);
+            }
+        }
+    }
+}
+
+/// Enforces the `__Host-` and `__Secure-` cookie name prefixes, per the
+/// (draft) "Cookie Prefixes" extension: a `__Secure-` cookie must have
+/// `Secure` set, and a `__Host-` cookie must additionally have `Path=/` and
+/// no `Domain`. Prefixed cookies that are missing these attributes have them
+/// filled in automatically; a `__Host-` cookie with an explicit `Domain` set
+/// can't be fixed up and is rejected outright.
+fn enforce_cookie_prefix(cookie: &mut Cookie<'static>) -> Result<(), &'static str> {
+    let name = cookie.name();
+    if name.starts_with("__Host-") {
+        if cookie.domain().is_some() {
+            return Err("a '__Host-' cookie cannot specify a Domain");
+        }
+
+        cookie.set_secure(true);
+        cookie.set_path("/");
+    } else if name.starts_with("__Secure-") {
+        cookie.set_secure(true);
+    }
+
+    Ok(())
+}
+
 /// Collection of one or more HTTP cookies.
 ///
 /// The `Cookies` type allows for retrieval of cookies from an incoming request
@@ -126,9 +199,36 @@ mod key {
 /// is usually done through tools like `openssl`. Using `openssl`, for instance,
 /// a 256-bit base64 key can be generated with the command `openssl rand -base64
 /// 32`.
+///
+/// # Signed Cookies
+///
+/// _Signed_ cookies sit between regular and private cookies: like private
+/// cookies, they cannot be tampered with or manufactured by clients, but,
+/// unlike private cookies, their value is not encrypted and so remains
+/// readable by the client. They're useful for values that are fine to
+/// expose, such as a locale preference or an A/B test bucket, but that
+/// shouldn't be forgeable.
+///
+/// Signed cookies can be retrieved, added, and removed from a `Cookies`
+/// collection via the [`get_signed()`], [`add_signed()`], and
+/// [`remove_signed()`] methods, and are authenticated with the same
+/// `secret_key` used for private cookies.
+///
+/// ## Key Rotation
+///
+/// To rotate the `secret_key` without invalidating previously-issued signed
+/// cookies, list the keys being retired in the `secret_key_fallbacks`
+/// configuration parameter. Rocket will fall back to verifying against each
+/// of them, in order, if verification against the active `secret_key`
+/// fails. Private cookies are only ever decrypted with the active
+/// `secret_key`.
+///
+/// [`get_signed()`]: #method.get_signed
+/// [`add_signed()`]: #method.add_signed
+/// [`remove_signed()`]: #method.remove_signed
 pub enum Cookies<'a> {
     #[doc(hidden)]
-    Jarred(RefMut<'a, CookieJar>, &'a Key),
+    Jarred(RefMut<'a, CookieJar>, &'a Key, &'a [Key], &'a CookiePolicy),
     #[doc(hidden)]
     Empty(CookieJar)
 }
@@ -137,8 +237,13 @@ impl<'a> Cookies<'a> {
     /// WARNING: This is unstable! Do not use this method outside of Rocket!
     #[inline]
     #[doc(hidden)]
-    pub fn new(jar: RefMut<'a, CookieJar>, key: &'a Key) -> Cookies<'a> {
-        Cookies::Jarred(jar, key)
+    pub fn new(
+        jar: RefMut<'a, CookieJar>,
+        key: &'a Key,
+        fallback_keys: &'a [Key],
+        policy: &'a CookiePolicy
+    ) -> Cookies<'a> {
+        Cookies::Jarred(jar, key, fallback_keys, policy)
     }
 
     /// WARNING: This is unstable! Do not use this method outside of Rocket!
@@ -160,7 +265,7 @@ impl<'a> Cookies<'a> {
     #[inline]
     #[doc(hidden)]
     pub fn add_original(&mut self, cookie: Cookie<'static>) {
-        if let Cookies::Jarred(ref mut jar, _) = *self {
+        if let Cookies::Jarred(ref mut jar, _, _, _) = *self {
             jar.add_original(cookie)
         }
     }
@@ -180,13 +285,23 @@ impl<'a> Cookies<'a> {
     /// ```
     pub fn get(&self, name: &str) -> Option<&Cookie<'static>> {
         match *self {
-            Cookies::Jarred(ref jar, _) => jar.get(name),
+            Cookies::Jarred(ref jar, _, _, _) => jar.get(name),
             Cookies::Empty(_) => None
         }
     }
 
     /// Adds `cookie` to this collection.
     ///
+    /// Any of the `Secure`, `HttpOnly`, and `SameSite` attributes that
+    /// `cookie` doesn't already set explicitly are filled in from the
+    /// configured `cookies` policy (see [`CookiePolicy`]). If `cookie`'s
+    /// name carries a `__Secure-` or `__Host-` prefix, the attributes that
+    /// prefix requires are enforced: `Secure` is set for either prefix, and
+    /// `Path=/` is additionally set for `__Host-`. A `__Host-` cookie that
+    /// already has an explicit, conflicting `Domain` can't be fixed up and
+    /// is dropped, with a message printed to standard error, rather than sent
+    /// to the client.
+    ///
     /// # Example
     ///
     /// ```rust
@@ -204,8 +319,15 @@ impl<'a> Cookies<'a> {
     ///     cookies.add(cookie);
     /// }
     /// ```
-    pub fn add(&mut self, cookie: Cookie<'static>) {
-        if let Cookies::Jarred(ref mut jar, _) = *self {
+    pub fn add(&mut self, mut cookie: Cookie<'static>) {
+        if let Cookies::Jarred(ref mut jar, _, _, policy) = *self {
+            policy.apply(&mut cookie);
+
+            if let Err(reason) = enforce_cookie_prefix(&mut cookie) {
+                eprintln!("refusing to set cookie '{}': {}", cookie.name(), reason);
+                return;
+            }
+
             jar.add(cookie)
         }
     }
@@ -231,7 +353,7 @@ impl<'a> Cookies<'a> {
     /// }
     /// ```
     pub fn remove(&mut self, cookie: Cookie<'static>) {
-        if let Cookies::Jarred(ref mut jar, _) = *self {
+        if let Cookies::Jarred(ref mut jar, _, _, _) = *self {
             jar.remove(cookie)
         }
     }
@@ -242,7 +364,7 @@ impl<'a> Cookies<'a> {
     #[doc(hidden)]
     pub fn reset_delta(&mut self) {
         match *self {
-            Cookies::Jarred(ref mut jar, _) => jar.reset_delta(),
+            Cookies::Jarred(ref mut jar, _, _, _) => jar.reset_delta(),
             Cookies::Empty(ref mut jar) => jar.reset_delta()
         }
     }
@@ -263,7 +385,7 @@ impl<'a> Cookies<'a> {
     /// ```
     pub fn iter(&self) -> impl Iterator<Item=&Cookie<'static>> {
         match *self {
-            Cookies::Jarred(ref jar, _) => jar.iter(),
+            Cookies::Jarred(ref jar, _, _, _) => jar.iter(),
             Cookies::Empty(ref jar) => jar.iter()
         }
     }
@@ -273,7 +395,7 @@ impl<'a> Cookies<'a> {
     #[doc(hidden)]
     pub fn delta(&self) -> Delta<'_> {
         match *self {
-            Cookies::Jarred(ref jar, _) => jar.delta(),
+            Cookies::Jarred(ref jar, _, _, _) => jar.delta(),
             Cookies::Empty(ref jar) => jar.delta()
         }
     }
@@ -301,7 +423,7 @@ impl Cookies<'_> {
     /// ```
     pub fn get_private(&mut self, name: &str) -> Option<Cookie<'static>> {
         match *self {
-            Cookies::Jarred(ref mut jar, key) => jar.private(key).get(name),
+            Cookies::Jarred(ref mut jar, key, _, _) => jar.private(key).get(name),
             Cookies::Empty(_) => None
         }
     }
@@ -337,8 +459,15 @@ impl Cookies<'_> {
     /// }
     /// ```
     pub fn add_private(&mut self, mut cookie: Cookie<'static>) {
-        if let Cookies::Jarred(ref mut jar, key) = *self {
+        if let Cookies::Jarred(ref mut jar, key, _, policy) = *self {
+            policy.apply(&mut cookie);
             Cookies::set_private_defaults(&mut cookie);
+
+            if let Err(reason) = enforce_cookie_prefix(&mut cookie) {
+                eprintln!("refusing to set cookie '{}': {}", cookie.name(), reason);
+                return;
+            }
+
             jar.private(key).add(cookie)
         }
     }
@@ -347,7 +476,7 @@ impl Cookies<'_> {
     /// WARNING: This is unstable! Do not use this method outside of Rocket!
     #[doc(hidden)]
     pub fn add_original_private(&mut self, mut cookie: Cookie<'static>) {
-        if let Cookies::Jarred(ref mut jar, key) = *self {
+        if let Cookies::Jarred(ref mut jar, key, _, _) = *self {
             Cookies::set_private_defaults(&mut cookie);
             jar.private(key).add_original(cookie)
         }
@@ -401,7 +530,7 @@ impl Cookies<'_> {
     /// }
     /// ```
     pub fn remove_private(&mut self, mut cookie: Cookie<'static>) {
-        if let Cookies::Jarred(ref mut jar, key) = *self {
+        if let Cookies::Jarred(ref mut jar, key, _, _) = *self {
             if cookie.path().is_none() {
                 cookie.set_path("/");
             }
@@ -411,10 +540,139 @@ impl Cookies<'_> {
     }
 }
 
+#[cfg(feature = "private-cookies")]
+impl Cookies<'_> {
+    /// Returns a reference to the `Cookie` inside this collection with the
+    /// name `name` and authenticates the cookie's value, returning a
+    /// `Cookie` with the authenticated value. Unlike [`get_private()`], the
+    /// value is not encrypted, so it remains visible to the client, but it
+    /// cannot be tampered with. If the cookie cannot be found, or the
+    /// cookie fails to authenticate, `None` is returned.
+    ///
+    /// Verification is first attempted against the active `secret_key`. If
+    /// that fails, each key in `secret_key_fallbacks` is tried, in order,
+    /// allowing a signed cookie issued under a retired key to still verify.
+    ///
+    /// This method is only available when the `private-cookies` feature is
+    /// enabled.
+    ///
+    /// [`get_private()`]: #method.get_private
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// # extern crate rocket;
+    /// use rocket::http::Cookies;
+    ///
+    /// fn handler(mut cookies: Cookies) {
+    ///     let cookie = cookies.get_signed("name");
+    /// }
+    /// ```
+    pub fn get_signed(&mut self, name: &str) -> Option<Cookie<'static>> {
+        if let Cookies::Jarred(ref mut jar, key, fallbacks, _) = *self {
+            if let Some(cookie) = jar.signed(key).get(name) {
+                return Some(cookie);
+            }
+
+            for fallback_key in fallbacks {
+                if let Some(cookie) = jar.signed(fallback_key).get(name) {
+                    return Some(cookie);
+                }
+            }
+        }
+
+        None
+    }
+
+    /// Adds `cookie` to the collection. The cookie's value is signed
+    /// assuring integrity and authenticity, but, unlike
+    /// [`add_private()`](#method.add_private), remains readable by the
+    /// client. The cookie can later be retrieved using
+    /// [`get_signed`](#method.get_signed) and removed using
+    /// [`remove_signed`](#method.remove_signed).
+    ///
+    /// Unless a value is supplied for the given key, the following default
+    /// is set on `cookie` before being added to `self`:
+    ///
+    ///    * `path`: `"/"`
+    ///
+    /// This method is only available when the `private-cookies` feature is
+    /// enabled.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// # extern crate rocket;
+    /// use rocket::http::{Cookie, Cookies};
+    ///
+    /// fn handler(mut cookies: Cookies) {
+    ///     cookies.add_signed(Cookie::new("name", "value"));
+    /// }
+    /// ```
+    pub fn add_signed(&mut self, mut cookie: Cookie<'static>) {
+        if let Cookies::Jarred(ref mut jar, key, _, policy) = *self {
+            policy.apply(&mut cookie);
+
+            if cookie.path().is_none() {
+                cookie.set_path("/");
+            }
+
+            if let Err(reason) = enforce_cookie_prefix(&mut cookie) {
+                eprintln!("refusing to set cookie '{}': {}", cookie.name(), reason);
+                return;
+            }
+
+            jar.signed(key).add(cookie)
+        }
+    }
+
+    /// Adds an original, signed `cookie` to the collection.
+    /// WARNING: This is unstable! Do not use this method outside of Rocket!
+    #[doc(hidden)]
+    pub fn add_original_signed(&mut self, mut cookie: Cookie<'static>) {
+        if let Cookies::Jarred(ref mut jar, key, _, _) = *self {
+            if cookie.path().is_none() {
+                cookie.set_path("/");
+            }
+
+            jar.signed(key).add_original(cookie)
+        }
+    }
+
+    /// Removes the signed `cookie` from the collection.
+    ///
+    /// For correct removal, the passed in `cookie` must contain the same
+    /// `path` and `domain` as the cookie that was initially set. If a path
+    /// is not set on `cookie`, the `"/"` path will automatically be set.
+    ///
+    /// This method is only available when the `private-cookies` feature is
+    /// enabled.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// # extern crate rocket;
+    /// use rocket::http::{Cookie, Cookies};
+    ///
+    /// fn handler(mut cookies: Cookies) {
+    ///     cookies.remove_signed(Cookie::named("name"));
+    /// }
+    /// ```
+    pub fn remove_signed(&mut self, mut cookie: Cookie<'static>) {
+        if let Cookies::Jarred(ref mut jar, key, _, _) = *self {
+            if cookie.path().is_none() {
+                cookie.set_path("/");
+            }
+
+            jar.signed(key).remove(cookie)
+        }
+    }
+}
+
 impl fmt::Debug for Cookies<'_> {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         match *self {
-            Cookies::Jarred(ref jar, _) => jar.fmt(f),
+            Cookies::Jarred(ref jar, _, _, _) => jar.fmt(f),
             Cookies::Empty(ref jar) => jar.fmt(f)
         }
     }
@@ -0,0 +1,262 @@
+//! Parsers for a handful of commonly used, but non-trivial, request headers.
+//!
+//! These are deliberately forgiving: a malformed header yields `None` or an
+//! empty `Vec` rather than an error, since a client sending garbage here
+//! shouldn't be any different, from the application's perspective, than a
+//! client that omitted the header entirely.
+
+use std::fmt;
+
+/// A language tag as found in an `Accept-Language` header, e.g. `en-US`.
+///
+/// This is intentionally unvalidated beyond "non-empty": it's stored and
+/// compared as the raw tag string rather than being decomposed into its
+/// constituent language/region/script subtags.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct LanguageTag(String);
+
+impl LanguageTag {
+    /// Returns the tag as a string, e.g. `"en-US"`.
+    #[inline(always)]
+    pub fn as_str(&self) -> &str {
+        &self.0
+    }
+}
+
+impl fmt::Display for LanguageTag {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.0)
+    }
+}
+
+/// The credentials carried by an `Authorization` header.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Authorization {
+    /// HTTP Basic credentials, decoded from the header's base64-encoded
+    /// `user:password` payload.
+    Basic {
+        /// The decoded username.
+        user: String,
+        /// The decoded password.
+        pass: String,
+    },
+    /// An HTTP Bearer token, as used by OAuth 2.0.
+    Bearer(String),
+}
+
+/// A single element of a `Forwarded` header, per RFC 7239 §4.
+///
+/// Each of the three parameters recognized here is optional: a proxy may
+/// report only some of them, and unrecognized parameters are ignored.
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
+pub struct ForwardedElement {
+    /// The `for` parameter: the client that initiated the request, as seen
+    /// by this hop. May be an IP address, a bracketed IPv6 address with an
+    /// optional port, or an obfuscated identifier like `_hidden`.
+    pub r#for: Option<String>,
+    /// The `proto` parameter: the protocol (`"http"`, `"https"`, ...) used
+    /// to make the request.
+    pub proto: Option<String>,
+    /// The `host` parameter: the `Host` header field as received by the
+    /// proxy.
+    pub host: Option<String>,
+}
+
+/// Parses an `Accept-Language` header into its language tags, each paired
+/// with its quality value, sorted by descending quality. Tags with equal
+/// quality retain their relative order from the header. A tag with no `q`
+/// parameter is given a quality of `1.0`, per RFC 7231 §5.3.1.
+///
+/// Malformed entries are skipped; a header that's entirely malformed yields
+/// an empty `Vec`.
+pub fn parse_accept_language(header: &str) -> Vec<(LanguageTag, f32)> {
+    let mut tags: Vec<(LanguageTag, f32)> = header.split(',')
+        .filter_map(|part| {
+            let mut pieces = part.trim().splitn(2, ';');
+            let tag = pieces.next()?.trim();
+            if tag.is_empty() {
+                return None;
+            }
+
+            let quality = match pieces.next() {
+                Some(param) => {
+                    let param = param.trim();
+                    if !param.starts_with("q=") {
+                        return None;
+                    }
+
+                    param[2..].trim().parse().ok()?
+                }
+                None => 1.0,
+            };
+
+            Some((LanguageTag(tag.to_string()), quality))
+        })
+        .collect();
+
+    tags.sort_by(|a, b| b.1.partial_cmp(&a.1).unwrap_or(std::cmp::Ordering::Equal));
+    tags
+}
+
+/// Parses an `Authorization` header into `Basic` or `Bearer` credentials.
+/// Returns `None` if the header names an unrecognized scheme, is malformed,
+/// or, for `Basic`, doesn't base64-decode to a valid `user:password` pair.
+///
+/// Basic credentials missing their base64 padding are still accepted, since
+/// several widely-used clients omit it.
+pub fn parse_authorization(header: &str) -> Option<Authorization> {
+    let mut parts = header.trim().splitn(2, ' ');
+    let scheme = parts.next()?;
+    let value = parts.next()?.trim();
+    if value.is_empty() {
+        return None;
+    }
+
+    if scheme.eq_ignore_ascii_case("Basic") {
+        let decoded = base64::decode(value)
+            .or_else(|_| base64::decode_config(value, base64::STANDARD_NO_PAD))
+            .ok()?;
+
+        let decoded = String::from_utf8(decoded).ok()?;
+        let mut credentials = decoded.splitn(2, ':');
+        let user = credentials.next()?.to_string();
+        let pass = credentials.next()?.to_string();
+        Some(Authorization::Basic { user, pass })
+    } else if scheme.eq_ignore_ascii_case("Bearer") {
+        Some(Authorization::Bearer(value.to_string()))
+    } else {
+        None
+    }
+}
+
+/// Parses a `Forwarded` header into its elements, per RFC 7239. Elements
+/// with no `key=value` parameter, or with a parameter lacking an `=`, are
+/// skipped; parameters other than `for`, `proto`, and `host` are ignored.
+pub fn parse_forwarded(header: &str) -> Vec<ForwardedElement> {
+    header.split(',').filter_map(parse_forwarded_element).collect()
+}
+
+fn parse_forwarded_element(element: &str) -> Option<ForwardedElement> {
+    let mut parsed = ForwardedElement::default();
+    let mut found_param = false;
+    for pair in element.split(';') {
+        let pair = pair.trim();
+        if pair.is_empty() {
+            continue;
+        }
+
+        let mut kv = pair.splitn(2, '=');
+        let key = kv.next()?.trim();
+        let value = unquote(kv.next()?.trim());
+        found_param = true;
+
+        match key.to_ascii_lowercase().as_str() {
+            "for" => parsed.r#for = Some(value),
+            "proto" => parsed.proto = Some(value),
+            "host" => parsed.host = Some(value),
+            _ => { /* unrecognized parameter; ignore */ }
+        }
+    }
+
+    if found_param {
+        Some(parsed)
+    } else {
+        None
+    }
+}
+
+fn unquote(value: &str) -> String {
+    if value.len() >= 2 && value.starts_with('"') && value.ends_with('"') {
+        value[1..value.len() - 1].replace("\\\"", "\"").replace("\\\\", "\\")
+    } else {
+        value.to_string()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn accept_language_sorts_by_quality() {
+        let tags = parse_accept_language("da, en-gb;q=0.8, en;q=0.7");
+        let tags: Vec<_> = tags.iter().map(|(t, q)| (t.as_str(), *q)).collect();
+        assert_eq!(tags, vec![("da", 1.0), ("en-gb", 0.8), ("en", 0.7)]);
+    }
+
+    #[test]
+    fn accept_language_preserves_order_on_ties() {
+        let tags = parse_accept_language("en;q=0.5, fr;q=0.5, de;q=0.5");
+        let tags: Vec<_> = tags.iter().map(|(t, _)| t.as_str()).collect();
+        assert_eq!(tags, vec!["en", "fr", "de"]);
+    }
+
+    #[test]
+    fn accept_language_skips_malformed_entries() {
+        let tags = parse_accept_language("en;q=bogus, , fr;q=0.9");
+        let tags: Vec<_> = tags.iter().map(|(t, q)| (t.as_str(), *q)).collect();
+        assert_eq!(tags, vec![("fr", 0.9)]);
+    }
+
+    #[test]
+    fn authorization_parses_basic() {
+        // "user:pass" base64-encoded, with padding.
+        let auth = parse_authorization("Basic dXNlcjpwYXNz").unwrap();
+        assert_eq!(auth, Authorization::Basic { user: "user".into(), pass: "pass".into() });
+    }
+
+    #[test]
+    fn authorization_parses_basic_without_padding() {
+        // "u:p" base64-encodes to "dTpw", which happens to need no padding;
+        // use a value that does need padding but has it stripped instead.
+        let auth = parse_authorization("Basic dXNlcjpwYXNzd29yZA").unwrap();
+        assert_eq!(auth, Authorization::Basic { user: "user".into(), pass: "password".into() });
+    }
+
+    #[test]
+    fn authorization_parses_bearer() {
+        let auth = parse_authorization("Bearer abc123.def456").unwrap();
+        assert_eq!(auth, Authorization::Bearer("abc123.def456".into()));
+    }
+
+    #[test]
+    fn authorization_rejects_unknown_scheme_and_garbage() {
+        assert_eq!(parse_authorization("Digest realm=\"x\""), None);
+        assert_eq!(parse_authorization("Basic not-valid-base64!!"), None);
+        assert_eq!(parse_authorization(""), None);
+    }
+
+    #[test]
+    fn forwarded_parses_standard_parameters() {
+        let elements = parse_forwarded("for=192.0.2.60;proto=http;by=203.0.113.43");
+        assert_eq!(elements.len(), 1);
+        assert_eq!(elements[0].r#for, Some("192.0.2.60".into()));
+        assert_eq!(elements[0].proto, Some("http".into()));
+        assert_eq!(elements[0].host, None);
+    }
+
+    #[test]
+    fn forwarded_parses_quoted_ipv6_and_multiple_elements() {
+        let elements = parse_forwarded(
+            r#"for="[2001:db8:cafe::17]:4711", for=192.0.2.43, for=198.51.100.17"#
+        );
+
+        assert_eq!(elements.len(), 3);
+        assert_eq!(elements[0].r#for, Some("[2001:db8:cafe::17]:4711".into()));
+        assert_eq!(elements[1].r#for, Some("192.0.2.43".into()));
+        assert_eq!(elements[2].r#for, Some("198.51.100.17".into()));
+    }
+
+    #[test]
+    fn forwarded_parses_obfuscated_identifier() {
+        let elements = parse_forwarded("for=_hidden, host=example.com");
+        assert_eq!(elements[0].r#for, Some("_hidden".into()));
+        assert_eq!(elements[1].host, Some("example.com".into()));
+    }
+
+    #[test]
+    fn forwarded_skips_empty_elements() {
+        let elements = parse_forwarded("for=192.0.2.1, , proto=https");
+        assert_eq!(elements.len(), 2);
+    }
+}
@@ -35,6 +35,7 @@ mod content_type;
 mod status;
 mod header;
 mod accept;
+mod headers;
 mod raw_str;
 mod parse;
 
@@ -56,9 +57,11 @@ pub mod private {
 pub use crate::method::Method;
 pub use crate::content_type::ContentType;
 pub use crate::accept::{Accept, QMediaType};
+pub use crate::headers::{LanguageTag, Authorization, ForwardedElement};
+pub use crate::headers::{parse_accept_language, parse_authorization, parse_forwarded};
 pub use crate::status::{Status, StatusClass};
 pub use crate::header::{Header, HeaderMap};
 pub use crate::raw_str::RawStr;
 
 pub use crate::media_type::MediaType;
-pub use crate::cookies::{Cookie, SameSite, Cookies};
+pub use crate::cookies::{Cookie, SameSite, Cookies, CookiePolicy};
@@ -44,13 +44,19 @@ impl<'a, T: ?Sized + ToOwned + 'a, C: Into<Cow<'a, T>>> From<C> for Indexed<'a,
 }
 
 impl<'a, T: ?Sized + ToOwned + 'a> Indexed<'a, T> {
+    /// Returns the underlying indices, or `None` if `self` is `Concrete`.
+    #[inline(always)]
+    pub fn try_indices(&self) -> Option<(usize, usize)> {
+        match *self {
+            Indexed::Indexed(a, b) => Some((a, b)),
+            Indexed::Concrete(_) => None,
+        }
+    }
+
     /// Panics if `self` is not an `Indexed`.
     #[inline(always)]
     pub fn indices(self) -> (usize, usize) {
-        match self {
-            Indexed::Indexed(a, b) => (a, b),
-            _ => panic!("cannot convert indexed T to U unless indexed")
-        }
+        self.try_indices().expect("cannot convert indexed T to U unless indexed")
     }
 
     /// Panics if `self` is not an `Indexed`.
@@ -85,24 +91,87 @@ impl<T: 'static + ?Sized + ToOwned> IntoOwned for Indexed<'_, T> {
 
 use std::ops::Add;
 
-impl<'a, T: ?Sized + ToOwned + 'a> Add for Indexed<'a, T> {
+impl<'a, T: ?Sized + ToOwned + 'a> Indexed<'a, T> {
+    /// Merges `self` and `other` into a single `Indexed` spanning both,
+    /// returning `None` if they can't be merged: two `Indexed` must be
+    /// adjacent (`self`'s end is `other`'s start), and two `Concrete`s are
+    /// merged by allocating a new owned value. An `Indexed` and a `Concrete`
+    /// are never merged.
+    pub fn try_add(self, other: Indexed<'a, T>) -> Option<Indexed<'a, T>>
+        where T: Concat
+    {
+        match (self, other) {
+            (Indexed::Indexed(a, b), Indexed::Indexed(c, d)) if b == c && a < d => {
+                Some(Indexed::Indexed(a, d))
+            }
+            (Indexed::Concrete(a), Indexed::Concrete(b)) => {
+                Some(Indexed::Concrete(Cow::Owned(Concat::concat(a.as_ref(), b.as_ref()))))
+            }
+            _ => None
+        }
+    }
+}
+
+impl<'a, T: ?Sized + ToOwned + 'a> Add for Indexed<'a, T>
+    where T: Concat
+{
     type Output = Indexed<'a, T>;
 
     #[inline]
     fn add(self, other: Indexed<'a, T>) -> Indexed<'a, T> {
-        match self {
-            Indexed::Indexed(a, b) => match other {
-                Indexed::Indexed(c, d) if b == c && a < d => Indexed::Indexed(a, d),
-                _ => panic!("+ requires indexed")
-            }
-            _ => panic!("+ requires indexed")
-        }
+        self.try_add(other).expect("+ requires indexed or concatenable concrete values")
+    }
+}
+
+/// Allows [`Indexed::try_add`] to merge two `Concrete` values by allocating a
+/// new, owned value that contains both.
+pub trait Concat: ToOwned {
+    fn concat(a: &Self, b: &Self) -> Self::Owned;
+}
+
+impl Concat for str {
+    fn concat(a: &str, b: &str) -> String {
+        let mut owned = String::with_capacity(a.len() + b.len());
+        owned.push_str(a);
+        owned.push_str(b);
+        owned
+    }
+}
+
+impl Concat for [u8] {
+    fn concat(a: &[u8], b: &[u8]) -> Vec<u8> {
+        let mut owned = Vec::with_capacity(a.len() + b.len());
+        owned.extend_from_slice(a);
+        owned.extend_from_slice(b);
+        owned
     }
 }
 
 impl<'a, T: ?Sized + ToOwned + 'a> Indexed<'a, T>
     where T: Length + AsPtr + Index<Range<usize>, Output = T>
 {
+    /// Returns the subslice of `self` spanning `range`, or `None` if `range`
+    /// is out of bounds. Works for both variants: an `Indexed` is resliced
+    /// by re-indexing, while a `Concrete` is resliced by taking a sub-`Cow`,
+    /// allocating only if the original `Cow` was already `Owned`.
+    pub fn slice(self, range: Range<usize>) -> Option<Indexed<'a, T>> {
+        if range.start > range.end || range.end > self.len() {
+            return None;
+        }
+
+        match self {
+            Indexed::Indexed(a, _) => Some(Indexed::Indexed(a + range.start, a + range.end)),
+            Indexed::Concrete(cow) => Some(Indexed::Concrete(match cow {
+                Cow::Borrowed(s) => Cow::Borrowed(&s[range]),
+                Cow::Owned(s) => {
+                    use std::borrow::Borrow;
+                    let borrowed: &T = s.borrow();
+                    Cow::Owned(borrowed[range].to_owned())
+                }
+            }))
+        }
+    }
+
     // Returns `None` if `needle` is not a substring of `haystack`.
     pub fn checked_from(needle: &T, haystack: &T) -> Option<Indexed<'a, T>> {
         let haystack_start = haystack.as_ptr() as usize;
@@ -155,13 +224,18 @@ impl<'a, T: ?Sized + ToOwned + 'a> Indexed<'a, T>
     /// Panics if `self` is an indexed string and `string` is None.
     // pub fn to_source(&self, source: Option<&'a T>) -> &T {
     pub fn from_cow_source<'s>(&'s self, source: &'s Option<Cow<'_, T>>) -> &'s T {
-        if self.is_indexed() && source.is_none() {
-            panic!("Cannot convert indexed str to str without base string!")
-        }
+        self.try_from_cow_source(source)
+            .expect("Cannot convert indexed str to str without base string!")
+    }
 
+    /// Retrieves the string `self` corresponds to. If `self` is derived from
+    /// indexes, the corresponding subslice of `source` is returned. Otherwise,
+    /// the concrete string is returned. Returns `None`, instead of panicking,
+    /// if `self` is an indexed string and `source` is `None`.
+    pub fn try_from_cow_source<'s>(&'s self, source: &'s Option<Cow<'_, T>>) -> Option<&'s T> {
         match *self {
-            Indexed::Indexed(i, j) => &source.as_ref().unwrap()[i..j],
-            Indexed::Concrete(ref mstr) => mstr.as_ref(),
+            Indexed::Indexed(i, j) => Some(&source.as_ref()?[i..j]),
+            Indexed::Concrete(ref mstr) => Some(mstr.as_ref()),
         }
     }
 
@@ -173,13 +247,82 @@ impl<'a, T: ?Sized + ToOwned + 'a> Indexed<'a, T>
     ///
     /// Panics if `self` is an indexed string and `string` is None.
     pub fn from_source<'s>(&'s self, source: Option<&'s T>) -> &'s T {
-        if self.is_indexed() && source.is_none() {
-            panic!("Cannot convert indexed str to str without base string!")
+        self.try_from_source(source)
+            .expect("Cannot convert indexed str to str without base string!")
+    }
+
+    /// Retrieves the string `self` corresponds to. If `self` is derived from
+    /// indexes, the corresponding subslice of `source` is returned. Otherwise,
+    /// the concrete string is returned. Returns `None`, instead of panicking,
+    /// if `self` is an indexed string and `source` is `None`.
+    pub fn try_from_source<'s>(&'s self, source: Option<&'s T>) -> Option<&'s T> {
+        match *self {
+            Indexed::Indexed(i, j) => Some(&source?[(i as usize)..(j as usize)]),
+            Indexed::Concrete(ref mstr) => Some(&*mstr),
         }
+    }
+}
 
+impl<'a, T: ?Sized + ToOwned + 'a> Indexed<'a, T>
+    where T: Length + AsPtr + Index<Range<usize>, Output = T> + PartialEq
+{
+    /// Returns `true` if the string `self` resolves to, given `source`, is
+    /// equal to `other`. Unlike the derived `PartialEq`, which compares
+    /// `Indexed`'s internal representation, this compares resolved string
+    /// content, so an `Indexed::Indexed` and an `Indexed::Concrete` that
+    /// refer to the same text compare equal. Returns `false`, rather than
+    /// panicking, if `self` is indexed and `source` is `None`.
+    pub fn eq_str(&self, other: &T, source: Option<&T>) -> bool {
+        self.try_from_source(source).map_or(false, |s| s == other)
+    }
+}
+
+impl<'a> Indexed<'a, str> {
+    /// Converts `self` into the equivalent `IndexedBytes`. Indices are
+    /// carried over unchanged; a `Concrete` value is converted by viewing
+    /// the underlying `str` as bytes. Unlike [`coerce()`](Indexed::coerce()),
+    /// this never panics.
+    pub fn into_bytes(self) -> IndexedBytes<'a> {
+        match self {
+            Indexed::Indexed(a, b) => Indexed::Indexed(a, b),
+            Indexed::Concrete(cow) => Indexed::Concrete(match cow {
+                Cow::Borrowed(s) => Cow::Borrowed(s.as_bytes()),
+                Cow::Owned(s) => Cow::Owned(s.into_bytes()),
+            }),
+        }
+    }
+}
+
+impl<'a> Indexed<'a, str> {
+    /// Retrieves the string `self` corresponds to, given `source`. If
+    /// `self` is derived from indices, the corresponding subslice of
+    /// `source` is returned; otherwise, the concrete string is returned.
+    ///
+    /// Unlike [`from_source()`](Indexed::from_source()), `source` isn't
+    /// `Option`al, so this never panics: it's on the caller to pass the
+    /// same source the indices were computed against.
+    #[inline]
+    pub fn resolve<'s>(&'s self, source: &'s str) -> &'s str {
         match *self {
-            Indexed::Indexed(i, j) => &source.unwrap()[(i as usize)..(j as usize)],
-            Indexed::Concrete(ref mstr) => &*mstr,
+            Indexed::Indexed(i, j) => &source[i..j],
+            Indexed::Concrete(ref s) => s.as_ref(),
+        }
+    }
+}
+
+impl<'a> Indexed<'a, [u8]> {
+    /// Converts `self` into the equivalent `IndexedStr`. Indices are
+    /// carried over unchanged; a `Concrete` value is converted by validating
+    /// the underlying bytes as UTF-8, failing with the resulting error if
+    /// they aren't. Unlike [`coerce()`](Indexed::coerce()), this never
+    /// panics.
+    pub fn into_str(self) -> Result<IndexedStr<'a>, std::str::Utf8Error> {
+        match self {
+            Indexed::Indexed(a, b) => Ok(Indexed::Indexed(a, b)),
+            Indexed::Concrete(cow) => Ok(Indexed::Concrete(match cow {
+                Cow::Borrowed(b) => Cow::Borrowed(std::str::from_utf8(b)?),
+                Cow::Owned(b) => Cow::Owned(String::from_utf8(b).map_err(|e| e.utf8_error())?),
+            })),
         }
     }
 }
@@ -340,3 +483,117 @@ impl std::fmt::Display for Context {
 
 impl_indexed_input!([u8], token = u8);
 impl_indexed_input!(str, token = char);
+
+/// Pairs an [`IndexedStr`] with the source it was indexed from so that it
+/// can be serialized, displayed, or compared without first resolving it
+/// into an owned `String`.
+#[cfg(feature = "serde")]
+pub struct SerializableIndexed<'a> {
+    indexed: &'a IndexedStr<'a>,
+    source: &'a str,
+}
+
+#[cfg(feature = "serde")]
+impl<'a> SerializableIndexed<'a> {
+    #[inline]
+    pub fn new(indexed: &'a IndexedStr<'a>, source: &'a str) -> Self {
+        SerializableIndexed { indexed, source }
+    }
+}
+
+#[cfg(feature = "serde")]
+impl serde_::Serialize for SerializableIndexed<'_> {
+    fn serialize<S: serde_::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.serialize_str(self.indexed.resolve(self.source))
+    }
+}
+
+#[cfg(feature = "serde")]
+impl fmt::Display for SerializableIndexed<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.indexed.resolve(self.source))
+    }
+}
+
+#[cfg(feature = "serde")]
+impl PartialEq<str> for SerializableIndexed<'_> {
+    fn eq(&self, other: &str) -> bool {
+        self.indexed.resolve(self.source) == other
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn try_add_merges_two_concrete_cows() {
+        let a: IndexedStr<'_> = Indexed::Concrete(Cow::Borrowed("foo"));
+        let b: IndexedStr<'_> = Indexed::Concrete(Cow::Borrowed("bar"));
+
+        let merged = a.try_add(b).expect("should merge");
+        match merged {
+            Indexed::Concrete(cow) => assert_eq!(cow.as_ref(), "foobar"),
+            Indexed::Indexed(..) => panic!("expected a concrete result"),
+        }
+    }
+
+    #[test]
+    fn try_add_rejects_indexed_and_concrete() {
+        let indexed: IndexedStr<'_> = Indexed::Indexed(0, 3);
+        let concrete: IndexedStr<'_> = Indexed::Concrete(Cow::Borrowed("bar"));
+
+        assert!(indexed.try_add(concrete).is_none());
+    }
+
+    #[test]
+    fn slice_past_end_returns_none() {
+        let concrete: IndexedStr<'_> = Indexed::Concrete(Cow::Borrowed("foo"));
+        assert!(concrete.slice(0..10).is_none());
+
+        let indexed: IndexedStr<'_> = Indexed::Indexed(0, 3);
+        assert!(indexed.slice(1..10).is_none());
+    }
+
+    #[test]
+    fn slice_reindexes_and_reslices() {
+        let indexed: IndexedStr<'_> = Indexed::Indexed(2, 8);
+        assert_eq!(indexed.slice(1..3).unwrap().indices(), (3, 5));
+
+        let concrete: IndexedStr<'_> = Indexed::Concrete(Cow::Borrowed("foobar"));
+        match concrete.slice(1..4).unwrap() {
+            Indexed::Concrete(cow) => assert_eq!(cow.as_ref(), "oob"),
+            Indexed::Indexed(..) => panic!("expected a concrete result"),
+        }
+    }
+
+    #[test]
+    fn resolve_reads_through_indexed_and_concrete() {
+        let source = "foo bar";
+
+        let indexed: IndexedStr<'_> = Indexed::Indexed(4, 7);
+        assert_eq!(indexed.resolve(source), "bar");
+
+        let concrete: IndexedStr<'_> = Indexed::Concrete(Cow::Borrowed("baz"));
+        assert_eq!(concrete.resolve(source), "baz");
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn serializable_indexed_serializes_a_mix_against_one_source() {
+        let source = "foo bar baz";
+
+        let indexed: IndexedStr<'_> = Indexed::Indexed(4, 7);
+        let concrete: IndexedStr<'_> = Indexed::Concrete(Cow::Borrowed("quux"));
+
+        let a = SerializableIndexed::new(&indexed, source);
+        let b = SerializableIndexed::new(&concrete, source);
+
+        assert!(a == *"bar");
+        assert!(b == *"quux");
+        assert_eq!(a.to_string(), "bar");
+
+        assert_eq!(serde_json::to_string(&a).unwrap(), "\"bar\"");
+        assert_eq!(serde_json::to_string(&b).unwrap(), "\"quux\"");
+    }
+}
@@ -30,6 +30,9 @@ pub struct RouteSegment<'a, P: UriPart> {
     pub kind: Kind,
     pub name: Cow<'a, str>,
     pub index: Option<usize>,
+    /// The literal fallback value from a `<name=value>` single-segment
+    /// parameter, used when the segment is absent from an incoming request.
+    pub default: Option<Cow<'a, str>>,
     _part: PhantomData<P>,
 }
 
@@ -43,6 +46,7 @@ impl<P: UriPart + 'static> IntoOwned for RouteSegment<'_, P> {
             kind: self.kind,
             name: IntoOwned::into_owned(self.name),
             index: self.index,
+            default: self.default.map(IntoOwned::into_owned),
             _part: PhantomData
         }
     }
@@ -99,6 +103,21 @@ impl<'a, P: UriPart> RouteSegment<'a, P> {
                 name = &name[..(name.len() - 2)];
             }
 
+            // A single-segment param may carry a literal default value,
+            // written `<name=value>`, used in place of a missing segment.
+            let mut default = None;
+            if kind == Kind::Single {
+                if let Some(eq_index) = name.find('=') {
+                    let value = &name[(eq_index + 1)..];
+                    if value.is_empty() {
+                        return Err(Malformed);
+                    }
+
+                    default = Some(value);
+                    name = &name[..eq_index];
+                }
+            }
+
             if name.is_empty() {
                 return Err(Empty);
             } else if !is_valid_ident(name) {
@@ -108,7 +127,8 @@ impl<'a, P: UriPart> RouteSegment<'a, P> {
             }
 
             let name = name.into();
-            return Ok(RouteSegment { string, name, kind, index, _part: PhantomData });
+            let default = default.map(Cow::from);
+            return Ok(RouteSegment { string, name, kind, index, default, _part: PhantomData });
         } else if segment.is_empty() {
             return Err(Empty);
         } else if segment.starts_with('<') && segment.len() > 1
@@ -124,6 +144,7 @@ impl<'a, P: UriPart> RouteSegment<'a, P> {
             string, index,
             name: segment.into(),
             kind: Kind::Static,
+            default: None,
             _part: PhantomData
         })
     }
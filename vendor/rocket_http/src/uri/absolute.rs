@@ -58,8 +58,9 @@ impl<'a> Absolute<'a> {
         }
     }
 
-    #[cfg(test)]
-    pub(crate) fn new(
+    // Used by code generation, and by tests in this crate.
+    #[doc(hidden)]
+    pub fn new(
         scheme: &'a str,
         authority: Option<Authority<'a>>,
         origin: Option<Origin<'a>>
@@ -88,6 +89,41 @@ impl<'a> Absolute<'a> {
         crate::parse::uri::absolute_from_str(string)
     }
 
+    /// Parses the string `string` into an `Absolute`. Parsing will never
+    /// allocate. This method should be used instead of
+    /// [`Absolute::parse()`](crate::uri::Absolute::parse()) when the source
+    /// URI is already a `String`. Returns an `Error` if `string` is not a
+    /// valid absolute URI.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// # extern crate rocket;
+    /// use rocket::http::uri::Absolute;
+    ///
+    /// let source = format!("https://{}", "rocket.rs");
+    /// let uri = Absolute::parse_owned(source).expect("valid URI");
+    /// assert_eq!(uri.scheme(), "https");
+    /// assert_eq!(uri.authority().unwrap().host(), "rocket.rs");
+    /// ```
+    pub fn parse_owned(string: String) -> Result<Absolute<'static>, Error<'static>> {
+        // See `Origin::parse_owned` for why this is correct and safe to do.
+        let copy_of_str = unsafe { &*(string.as_str() as *const str) };
+        let absolute = Absolute::parse(copy_of_str)?;
+
+        let uri = match absolute {
+            Absolute { source: Some(_), scheme, authority, origin } => Absolute {
+                scheme: scheme.into_owned(),
+                authority: authority.into_owned(),
+                origin: origin.into_owned(),
+                source: Some(Cow::Owned(string)),
+            },
+            _ => unreachable!("parser always parses with a source")
+        };
+
+        Ok(uri)
+    }
+
     /// Returns the scheme part of the absolute URI.
     ///
     /// # Example
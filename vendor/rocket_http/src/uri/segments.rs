@@ -27,23 +27,29 @@ use crate::uri::Uri;
 pub struct Segments<'a>(pub &'a str);
 
 /// Errors which can occur when attempting to interpret a segment string as a
-/// valid path segment.
+/// valid path segment. The wrapped `usize` is the index, among all segments
+/// in the original `Segments` iterator, of the segment that caused the error.
 #[derive(Debug, PartialEq, Eq, Clone)]
 pub enum SegmentError {
     /// The segment contained invalid UTF8 characters when percent decoded.
-    Utf8(Utf8Error),
+    Utf8(usize, Utf8Error),
     /// The segment started with the wrapped invalid character.
-    BadStart(char),
+    BadStart(usize, char),
     /// The segment contained the wrapped invalid character.
-    BadChar(char),
+    BadChar(usize, char),
     /// The segment ended with the wrapped invalid character.
-    BadEnd(char),
+    BadEnd(usize, char),
+    /// The segment was `..`, rejected outright instead of being used to pop
+    /// the previous segment. Only produced when `reject_dotdot` is `true`.
+    DotDot(usize),
 }
 
 impl Segments<'_> {
     /// Creates a `PathBuf` from a `Segments` iterator. The returned `PathBuf`
-    /// is percent-decoded. If a segment is equal to "..", the previous segment
-    /// (if any) is skipped.
+    /// is percent-decoded. If a segment is equal to ".." and `reject_dotdot`
+    /// is `false`, the previous segment (if any) is skipped; if `reject_dotdot`
+    /// is `true`, a `..` segment is rejected with [`SegmentError::DotDot`]
+    /// instead.
     ///
     /// For security purposes, if a segment meets any of the following
     /// conditions, an `Err` is returned indicating the condition met:
@@ -62,28 +68,36 @@ impl Segments<'_> {
     /// As a result of these conditions, a `PathBuf` derived via `FromSegments`
     /// is safe to interpolate within, or use as a suffix of, a path without
     /// additional checks.
-    pub fn into_path_buf(self, allow_dotfiles: bool) -> Result<PathBuf, SegmentError> {
+    pub fn into_path_buf(
+        self,
+        allow_dotfiles: bool,
+        reject_dotdot: bool
+    ) -> Result<PathBuf, SegmentError> {
         let mut buf = PathBuf::new();
-        for segment in self {
+        for (i, segment) in self.enumerate() {
             let decoded = Uri::percent_decode(segment.as_bytes())
-                .map_err(SegmentError::Utf8)?;
+                .map_err(|e| SegmentError::Utf8(i, e))?;
 
             if decoded == ".." {
+                if reject_dotdot {
+                    return Err(SegmentError::DotDot(i))
+                }
+
                 buf.pop();
             } else if !allow_dotfiles && decoded.starts_with('.') {
-                return Err(SegmentError::BadStart('.'))
+                return Err(SegmentError::BadStart(i, '.'))
             } else if decoded.starts_with('*') {
-                return Err(SegmentError::BadStart('*'))
+                return Err(SegmentError::BadStart(i, '*'))
             } else if decoded.ends_with(':') {
-                return Err(SegmentError::BadEnd(':'))
+                return Err(SegmentError::BadEnd(i, ':'))
             } else if decoded.ends_with('>') {
-                return Err(SegmentError::BadEnd('>'))
+                return Err(SegmentError::BadEnd(i, '>'))
             } else if decoded.ends_with('<') {
-                return Err(SegmentError::BadEnd('<'))
+                return Err(SegmentError::BadEnd(i, '<'))
             } else if decoded.contains('/') {
-                return Err(SegmentError::BadChar('/'))
+                return Err(SegmentError::BadChar(i, '/'))
             } else if cfg!(windows) && decoded.contains('\\') {
-                return Err(SegmentError::BadChar('\\'))
+                return Err(SegmentError::BadChar(i, '\\'))
             } else {
                 buf.push(&*decoded)
             }
@@ -93,6 +107,49 @@ impl Segments<'_> {
     }
 }
 
+#[cfg(test)]
+mod tests {
+    use super::{Segments, SegmentError};
+
+    #[test]
+    fn encoded_dotdot_traversal_is_rejected_when_requested() {
+        let result = Segments("a/%2e%2e/etc/passwd").into_path_buf(false, true);
+        assert_eq!(result, Err(SegmentError::DotDot(1)));
+    }
+
+    #[test]
+    fn encoded_dotdot_traversal_still_pops_by_default() {
+        let buf = Segments("a/%2e%2e/etc/passwd").into_path_buf(false, false).unwrap();
+        assert_eq!(buf, std::path::PathBuf::from("etc/passwd"));
+    }
+
+    #[test]
+    fn embedded_nul_is_valid_utf8_and_passes_through() {
+        // A NUL byte is valid UTF-8, so it's not rejected by percent-decoding;
+        // callers that open the resulting path still need to be careful.
+        let buf = Segments("a/%00/b").into_path_buf(false, false).unwrap();
+        assert_eq!(buf, std::path::PathBuf::from("a/\0/b"));
+    }
+
+    #[test]
+    fn dotfile_segment_rejected_by_default() {
+        let result = Segments("a/.env").into_path_buf(false, false);
+        assert_eq!(result, Err(SegmentError::BadStart(1, '.')));
+    }
+
+    #[test]
+    fn dotfile_segment_allowed_when_dotfiles_permitted() {
+        let buf = Segments("a/.well-known/b").into_path_buf(true, false).unwrap();
+        assert_eq!(buf, std::path::PathBuf::from("a/.well-known/b"));
+    }
+
+    #[test]
+    fn dotdot_still_rejected_when_dotfiles_permitted() {
+        let result = Segments("a/.well-known/..").into_path_buf(true, true);
+        assert_eq!(result, Err(SegmentError::DotDot(2)));
+    }
+}
+
 impl<'a> Iterator for Segments<'a> {
     type Item = &'a str;
 
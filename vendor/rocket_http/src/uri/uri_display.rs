@@ -422,6 +422,18 @@ impl<T: UriDisplay<Query>, E> UriDisplay<Query> for Result<T, E> {
     }
 }
 
+/// Writes the hyphenated, lowercase representation of the `Uuid`. The
+/// hyphenated form consists only of unreserved characters, so no
+/// percent-encoding is necessary.
+#[cfg(feature = "uuid")]
+impl<P: UriPart> UriDisplay<P> for uuid_::Uuid {
+    #[inline(always)]
+    fn fmt(&self, f: &mut Formatter<'_, P>) -> fmt::Result {
+        use std::fmt::Write;
+        write!(f, "{}", self.to_hyphenated())
+    }
+}
+
 // And finally, the `Ignorable` trait, which has sugar of `_` in the `uri!`
 // macro, which expands to a typecheck.
 